@@ -1,6 +1,7 @@
 //! Defines error infrastructure.
 
-use serde_derive::{Deserialize, Serialize};
+use proc_macro2::{Span as Span2, TokenStream as TokenStream2};
+use std::fmt;
 
 #[allow(unused)]
 macro_rules! ensure {
@@ -34,7 +35,7 @@ macro_rules! bug_detected {
 pub type DeriveResult<T> = Result<T, DeriveError>;
 
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug)]
 pub enum DeriveError {
     BugDetected {
         file: &'static str,
@@ -49,6 +50,42 @@ pub enum DeriveError {
         line: u32,
         column: u32
     },
+    /// An error in the input, spanning the offending tokens.
+    Syn(syn::Error),
 
     // Add more error variants here
 }
+
+impl DeriveError {
+    /// Convert `self` to a `compile_error!` invocation.  Errors that aren't
+    /// caused by a particular part of the input span the whole derive.
+    pub fn to_compile_error(&self) -> TokenStream2 {
+        match self {
+            Self::Syn(err) => err.to_compile_error(),
+            err => syn::Error::new(Span2::call_site(), err).to_compile_error(),
+        }
+    }
+}
+
+impl fmt::Display for DeriveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::BugDetected { file, line, column } => write!(
+                f, "deltoid-derive: bug detected at {}:{}:{}", file, line, column
+            ),
+            Self::ExpectedNamedField =>
+                write!(f, "deltoid-derive: expected a named field"),
+            Self::ExpectedPositionalField =>
+                write!(f, "deltoid-derive: expected a positional field"),
+            Self::FailedToEnsure { predicate, file, line, column } => write!(
+                f, "deltoid-derive: failed to ensure `{}` at {}:{}:{}",
+                predicate, file, line, column
+            ),
+            Self::Syn(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<syn::Error> for DeriveError {
+    fn from(err: syn::Error) -> Self { Self::Syn(err) }
+}
@@ -0,0 +1,159 @@
+//! Code generation for types marked with `#[delta(atomic)]`
+#![allow(non_snake_case)]
+
+use crate::DeriveResult;
+use crate::gen::InputType;
+use proc_macro2::{Ident as Ident2, TokenStream as TokenStream2};
+use quote::quote;
+use syn::*;
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+
+/// The generic parameter declarations of the impls for an atomic type.
+/// Since its values are only ever cloned, compared and (de)serialized as a
+/// whole, its type params don't need to implement any `deltoid` traits.
+fn type_param_decls(input: &InputType) -> DeriveResult<Vec<TokenStream2>> {
+    let in_type_param_decls: &Punctuated<GenericParam, Comma> =
+        input.type_param_decls()?;
+    Ok(in_type_param_decls.iter()
+        .map(|type_param_decl| match type_param_decl {
+            GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
+            GenericParam::Const(const_param)     => quote! { #const_param  },
+            GenericParam::Type(type_param) => input.type_param_decl(
+                type_param,
+                quote! {
+                    std::clone::Clone + std::fmt::Debug + std::cmp::PartialEq
+                    + for<'de> serde::Deserialize<'de> + serde::Serialize
+                },
+            ),
+        })
+        .collect())
+}
+
+/// The where-clause of the impls for an atomic type.
+fn where_clause(input: &InputType) -> DeriveResult<TokenStream2> {
+    let predicates: Vec<TokenStream2> = input.where_predicates(quote! {})?;
+    Ok(quote! { where #(#predicates),* })
+}
+
+pub(crate) fn define_delta_type(input: &InputType) -> DeriveResult<TokenStream2> {
+    let type_name: &Ident2 = input.type_name()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let delta_vis: &Visibility = input.delta_vis()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
+    Ok(quote! {
+        #delta_vis type #delta_type_name<#type_args> =
+            deltoid::AtomicDelta<#type_name<#type_args>>;
+    })
+}
+
+pub(crate) fn define_Debug_impl(_input: &InputType) -> DeriveResult<TokenStream2> {
+    // NOTE: `deltoid::AtomicDelta` already implements `Debug`
+    Ok(quote! {})
+}
+
+pub(crate) fn define_Core_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    let type_name: &Ident2 = input.type_name()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
+    let type_param_decls: Vec<TokenStream2> = type_param_decls(input)?;
+    let where_clause: TokenStream2 = where_clause(input)?;
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::Core
+            for #type_name<#type_args>
+            #where_clause
+        {
+            type Delta = #delta_type_name<#type_args>;
+        }
+    })
+}
+
+pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    let type_name: &Ident2 = input.type_name()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
+    let type_param_decls: Vec<TokenStream2> = type_param_decls(input)?;
+    let where_clause: TokenStream2 = where_clause(input)?;
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::Apply
+            for #type_name<#type_args>
+            #where_clause
+        {
+            #[allow(unused)]
+            fn apply(&self, delta: Self::Delta) -> deltoid::DeltaResult<Self> {
+                deltoid::atomic::apply(self, delta)
+            }
+
+            #[allow(unused)]
+            fn validate_into(
+                &self,
+                delta: &Self::Delta,
+                errors: &mut Vec<deltoid::DeltaError>,
+            ) {
+                // NOTE: Replacing a value as a whole can't fail
+            }
+        }
+    })
+}
+
+pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    let type_name: &Ident2 = input.type_name()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
+    let type_param_decls: Vec<TokenStream2> = type_param_decls(input)?;
+    let where_clause: TokenStream2 = where_clause(input)?;
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::Delta
+            for #type_name<#type_args>
+            #where_clause
+        {
+            fn delta(&self, rhs: &Self) -> deltoid::DeltaResult<Self::Delta> {
+                deltoid::atomic::delta(self, rhs)
+            }
+        }
+    })
+}
+
+pub(crate) fn define_FromDelta_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    let type_name: &Ident2 = input.type_name()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
+    let type_param_decls: Vec<TokenStream2> = type_param_decls(input)?;
+    let where_clause: TokenStream2 = where_clause(input)?;
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::FromDelta
+            for #type_name<#type_args>
+            #where_clause
+        {
+            fn from_delta(delta: Self::Delta) -> deltoid::DeltaResult<Self> {
+                deltoid::atomic::from_delta(delta)
+            }
+
+            fn validate_from_delta(
+                delta: &Self::Delta,
+                errors: &mut Vec<deltoid::DeltaError>,
+            ) {
+                deltoid::atomic::validate_from_delta(delta, errors)
+            }
+        }
+    })
+}
+
+pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    let type_name: &Ident2 = input.type_name()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
+    let type_param_decls: Vec<TokenStream2> = type_param_decls(input)?;
+    let where_clause: TokenStream2 = where_clause(input)?;
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::IntoDelta
+            for #type_name<#type_args>
+            #where_clause
+        {
+            fn into_delta(self) -> deltoid::DeltaResult<Self::Delta> {
+                deltoid::atomic::into_delta(self)
+            }
+        }
+    })
+}
+
+pub(crate) fn define_ToDynDelta_impl(_input: &InputType) -> DeriveResult<TokenStream2> {
+    // NOTE: `deltoid::AtomicDelta` already implements `ToDynDelta`
+    Ok(quote! {})
+}
@@ -14,34 +14,27 @@ pub(crate) fn define_delta_enum(input: &InputType) -> DeriveResult<TokenStream2>
     if !input.is_enum() { return bug_detected!() }
     let delta_type_name: &Ident2 = input.delta_type_name()?;
     let enum_variants: &[EnumVariant] = input.enum_variants()?;
-    let in_type_param_decls: &Punctuated<GenericParam, Comma> =
-        input.type_param_decls()?;
-    let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
-        .map(|type_param_decl| match type_param_decl {
-            GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
-            GenericParam::Const(const_param)     => quote! { #const_param  },
-            GenericParam::Type(type_param) => {
-                let T: &Ident2 = &type_param.ident;
-                // NOTE: trait bounds on the corresponding type parameter
-                //       `T` in `InputType::Struct#type_param`:
-                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
-                    .map(|trait_bound| quote! { #trait_bound })
-                    .collect();
-                quote! {
-                    #T: deltoid::Core
-                    // NOTE: don't include serde::{Deserialize, Serde}
-                    #(+ #bounds)* // Copy user-specified type/lifetime bounds
-                }
-            },
-        })
-        .collect();
-    let where_clause = quote! { /*where*/ };
+    let type_param_decls: Vec<TokenStream2> =
+        input.delta_type_param_decls(quote! { deltoid::Core })?;
+    let serde_attr: TokenStream2 = input.serde_bound_attr()?;
+    let delta_vis: &Visibility = input.delta_vis()?;
+    let delta_attrs: TokenStream2 =
+        input.delta_attrs(
+            &["Clone", "PartialEq", "Deserialize", "Serialize"],
+        )?;
+    let predicates: Vec<TokenStream2> =
+        input.delta_where_predicates(quote! { deltoid::Core })?;
+    let where_clause = quote! { where #(#predicates),* };
+    let skip_unchanged: bool = input.is_skip_unchanged()?;
     let enum_body: TokenStream2 = enum_variants.iter()
         .map(|enum_variant: &EnumVariant| -> DeriveResult<_> {
             let variant_name = &enum_variant.name;
             let field_types: Vec<TokenStream2> = enum_variant.fields()
                 .map(|field: &FieldDesc| field.type_tokens())
                 .collect();
+            let field_serde_attrs: Vec<TokenStream2> = enum_variant.fields()
+                .map(|field: &FieldDesc| field.serde_attrs(skip_unchanged))
+                .collect();
             Ok(match enum_variant.struct_variant {
                 StructVariant::NamedStruct => {
                     let field_names: Vec<&Ident2> = enum_variant.fields()
@@ -50,13 +43,16 @@ pub(crate) fn define_delta_enum(input: &InputType) -> DeriveResult<TokenStream2>
                     quote! {
                         #variant_name {
                             #(
-                                #[doc(hidden)] #field_names: #field_types,
+                                #[doc(hidden)] #field_serde_attrs
+                                #field_names: #field_types,
                             )*
                         },
                     }
                 },
                 StructVariant::TupleStruct => quote! {
-                    #variant_name( #( #[doc(hidden)] #field_types, )* ),
+                    #variant_name(
+                        #( #[doc(hidden)] #field_serde_attrs #field_types, )*
+                    ),
                 },
                 StructVariant::UnitStruct => quote! {
                     #variant_name,
@@ -67,7 +63,9 @@ pub(crate) fn define_delta_enum(input: &InputType) -> DeriveResult<TokenStream2>
     Ok(quote! {
         #[derive(Clone, PartialEq)]
         #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
-        pub enum #delta_type_name<#(#type_param_decls),*>
+        #serde_attr
+        #delta_attrs
+        #delta_vis enum #delta_type_name<#(#type_param_decls),*>
             #where_clause
         {
             #enum_body
@@ -75,36 +73,98 @@ pub(crate) fn define_delta_enum(input: &InputType) -> DeriveResult<TokenStream2>
     })
 }
 
+/// Implement `Eq` and `Hash` for the delta type if the input enum's
+/// `#[delta(derive(...))]` includes them, provided that the deltas of all
+/// of its fields implement them.
+pub(crate) fn define_Eq_Hash_impls(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_enum() { return bug_detected!() }
+    let enum_variants: &[EnumVariant] = input.enum_variants()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let type_args: Punctuated<GenericArgument, Comma> =
+        input.delta_type_args()?;
+    let type_param_decls: Vec<TokenStream2> =
+        input.delta_type_param_decls(quote! { deltoid::Core })?;
+    let predicates: Vec<TokenStream2> =
+        input.delta_where_predicates(quote! { deltoid::Core })?;
+    let field_types: Vec<TokenStream2> = enum_variants.iter()
+        .flat_map(|enum_variant: &EnumVariant| enum_variant.fields())
+        .filter(|field: &&FieldDesc| !field.ignore_field())
+        .map(|field: &FieldDesc| field.type_tokens())
+        .collect();
+    let match_arms: Vec<TokenStream2> = enum_variants.iter()
+        .map(|enum_variant: &EnumVariant| {
+            let variant_name = &enum_variant.name;
+            Ok(match enum_variant.struct_variant {
+                StructVariant::NamedStruct => {
+                    let field_names: Vec<&Ident2> = enum_variant.fields()
+                        .map(|field: &FieldDesc| field.name_ref())
+                        .collect::<DeriveResult<_>>()?;
+                    quote! {
+                        Self::#variant_name { #(#field_names),* } => {
+                            #( std::hash::Hash::hash(#field_names, state); )*
+                        },
+                    }
+                },
+                StructVariant::TupleStruct => {
+                    let field_names: Vec<Ident2> = enum_variant.fields()
+                        .map(|field: &FieldDesc| {
+                            Ok(format_ident!("field{}", field.pos_ref()?.to_string()))
+                        })
+                        .collect::<DeriveResult<_>>()?;
+                    quote! {
+                        Self::#variant_name( #(#field_names),* ) => {
+                            #( std::hash::Hash::hash(#field_names, state); )*
+                        },
+                    }
+                },
+                StructVariant::UnitStruct => quote! {
+                    Self::#variant_name => {},
+                },
+            })
+        })
+        .collect::<DeriveResult<_>>()?;
+    let hash_body = quote! {
+        std::hash::Hash::hash(&std::mem::discriminant(self), state);
+        match self { #(#match_arms)* }
+    };
+    let mut impls = TokenStream2::new();
+    if input.delta_derives_trait("Eq")? {
+        impls.extend(quote! {
+            impl<#(#type_param_decls),*> std::cmp::Eq
+                for #delta_type_name<#type_args>
+                where
+                    #(#predicates,)*
+                    Self: std::cmp::PartialEq,
+                    #(#field_types: std::cmp::Eq,)*
+            {}
+        });
+    }
+    if input.delta_derives_trait("Hash")? {
+        impls.extend(quote! {
+            impl<#(#type_param_decls),*> std::hash::Hash
+                for #delta_type_name<#type_args>
+                where #(#predicates,)* #(#field_types: std::hash::Hash,)*
+            {
+                #[allow(unused)]
+                fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                    #hash_body
+                }
+            }
+        });
+    }
+    Ok(impls)
+}
+
 pub(crate) fn define_Debug_impl(input: &InputType) -> DeriveResult<TokenStream2> {
     if !input.is_enum() { return bug_detected!() }
     let delta_type_name: &Ident2 = input.delta_type_name()?;
     let enum_variants: &[EnumVariant] = input.enum_variants()?;
-    let in_type_param_decls: &Punctuated<GenericParam, Comma> =
-        input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
-    let in_where_clause: &WhereClause = input.where_clause()?;
-    let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
-        .map(|type_param_decl| match type_param_decl {
-            GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
-            GenericParam::Const(const_param)     => quote! { #const_param  },
-            GenericParam::Type(type_param) => {
-                let T: &Ident2 = &type_param.ident;
-                // NOTE: `bounds` defines trait bounds on the corresponding
-                // type parameter `T` in `InputType::Struct#type_param`:
-                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
-                    .map(|trait_bound| quote! { #trait_bound })
-                    .collect();
-                quote! {
-                    #T: deltoid::Core
-                    + std::fmt::Debug
-                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
-                }
-            },
-        })
-        .collect();
-    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
-        .map(|where_predicate| quote! { #where_predicate })
-        .collect();
+    let type_args: Punctuated<GenericArgument, Comma> =
+        input.delta_type_args()?;
+    let type_param_decls: Vec<TokenStream2> =
+        input.delta_type_param_decls(quote! { deltoid::Core + std::fmt::Debug })?;
+    let predicates: Vec<TokenStream2> =
+        input.delta_where_predicates(quote! { deltoid::Core })?;
     let where_clause = quote! { where #(#predicates),* };
     let mut field_patterns: Vec<TokenStream2> = vec![];
     let mut match_bodies: Vec<TokenStream2> = vec![];
@@ -233,7 +293,7 @@ pub(crate) fn define_Debug_impl(input: &InputType) -> DeriveResult<TokenStream2>
     };
     Ok(quote! {
         impl<#(#type_param_decls),*> std::fmt::Debug
-            for #delta_type_name<#type_params>
+            for #delta_type_name<#type_args>
             #where_clause
         {
             fn fmt(&self, f: &mut std::fmt::Formatter)
@@ -251,41 +311,32 @@ pub(crate) fn define_Core_impl(input: &InputType) -> DeriveResult<TokenStream2>
     let delta_type_name: &Ident2 = input.delta_type_name()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
-    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
+    let delta_type_args: Punctuated<GenericArgument, Comma> =
+        input.delta_type_args()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
             GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
             GenericParam::Const(const_param)     => quote! { #const_param  },
-            GenericParam::Type(type_param) => {
-                let T: &Ident2 = &type_param.ident;
-                // NOTE: `bounds` defines trait bounds on the corresponding
-                // type parameter `T` in `InputType::Struct#type_param`:
-                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
-                    .map(|trait_bound| quote! { #trait_bound })
-                    .collect();
+            GenericParam::Type(type_param) => input.type_param_decl(
+                type_param,
                 quote! {
-                    #T: std::clone::Clone
-                    + std::fmt::Debug
-                        + std::cmp::PartialEq
-                        + deltoid::Core
-                        + for<'de> serde::Deserialize<'de>
-                        + serde::Serialize
-                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
-                }
-            },
+                    std::clone::Clone + std::fmt::Debug + std::cmp::PartialEq
+                    + deltoid::Core + for<'de> serde::Deserialize<'de>
+                    + serde::Serialize
+                },
+            ),
         })
         .collect();
-    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
-        .map(|where_predicate| quote! { #where_predicate })
-        .collect();
+    let predicates: Vec<TokenStream2> =
+        input.where_predicates(quote! { deltoid::Core })?;
     let where_clause = quote! { where #(#predicates),* };
     Ok(quote! {
         impl<#(#type_param_decls),*> deltoid::Core
-            for #type_name<#type_params>
+            for #type_name<#type_args>
             #where_clause
         {
-            type Delta = #delta_type_name<#type_params>;
+            type Delta = #delta_type_name<#delta_type_args>;
         }
     })
 }
@@ -296,39 +347,28 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
     let enum_variants: &[EnumVariant] = input.enum_variants()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
-    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
             GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
             GenericParam::Const(const_param)     => quote! { #const_param  },
-            GenericParam::Type(type_param) => {
-                let T: &Ident2 = &type_param.ident;
-                // NOTE: `bounds` defines trait bounds on the corresponding
-                // type parameter `T` in `InputType::Struct#type_param`:
-                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
-                    .map(|trait_bound| quote! { #trait_bound })
-                    .collect();
+            GenericParam::Type(type_param) => input.type_param_decl(
+                type_param,
                 quote! {
-                    #T: std::clone::Clone
-                    + std::fmt::Debug
-                        + std::cmp::PartialEq
-                        + deltoid::Apply
-                        + deltoid::FromDelta
-                        + for<'de> serde::Deserialize<'de>
-                        + serde::Serialize
-                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
-                }
-            },
+                    std::clone::Clone + std::fmt::Debug + std::cmp::PartialEq
+                    + deltoid::Apply + deltoid::FromDelta
+                    + for<'de> serde::Deserialize<'de> + serde::Serialize
+                },
+            ),
         })
         .collect();
-    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
-        .map(|where_predicate| quote! { #where_predicate })
-        .collect();
+    let predicates: Vec<TokenStream2> =
+        input.where_predicates(quote! { deltoid::Apply + deltoid::FromDelta })?;
     let where_clause = quote! { where #(#predicates),* };
     let mut   lhs_patterns: Vec<TokenStream2> = vec![];
     let mut delta_patterns: Vec<TokenStream2> = vec![];
     let mut match_bodies: Vec<TokenStream2> = vec![];
+    let mut validation_bodies: Vec<TokenStream2> = vec![];
     for v in enum_variants.iter() { match (v.struct_variant, &v.name, &v.fields) {
         (StructVariant::NamedStruct, variant_name, variant_fields) => {
             let field_names: Vec<&Ident2> = variant_fields.iter()
@@ -342,15 +382,29 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
                 .map(|field: &FieldDesc| field.name_ref().unwrap())
                 .map(|ident| format_ident!("delta_{}", ident))
                 .collect();
+            let segments: Vec<TokenStream2> = field_names.iter()
+                .map(|fname| quote! {
+                    deltoid::PathSegment::Field(stringify!(#fname).to_string())
+                })
+                .collect();
             let field_values: Vec<TokenStream2> = variant_fields.iter()
                 .zip(lhs_names.iter())
                 .zip(delta_names.iter())
-                .map(|((f, lhs_name), delta_name)| if f.ignore_field() {
+                .zip(segments.iter())
+                .map(|(((f, lhs_name), delta_name), segment)| if f.ignore_field() {
                     quote! { #lhs_name.clone() }
                 } else {
+                    let apply = f.apply_tokens(
+                        quote! { #lhs_name }, quote! { delta.clone(/*TODO*/) }
+                    );
                     quote! {
                         if let Some(delta) = #delta_name {
-                            #lhs_name.apply(delta.clone(/*TODO*/))?
+                            #apply
+                                .map_err(|e| e.at(#segment).at(
+                                    deltoid::PathSegment::Variant(
+                                        stringify!(#variant_name).to_string()
+                                    )
+                                ))?
                         } else {
                             #lhs_name.clone()
                         }
@@ -373,6 +427,34 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
                     #(#field_names: #field_values),*
                 })
             });
+            let field_validations: Vec<TokenStream2> = variant_fields.iter()
+                .zip(lhs_names.iter())
+                .zip(delta_names.iter())
+                .zip(segments.iter())
+                .filter(|(((f, _), _), _)| !f.ignore_field())
+                .map(|(((f, lhs_name), delta_name), segment)| {
+                    let validate = f.validate_tokens(
+                        quote! { #lhs_name }, quote! { delta }
+                    );
+                    quote! {
+                        if let Some(delta) = #delta_name {
+                            let start: usize = errors.len();
+                            #validate
+                            deltoid::DeltaError::scope_all(
+                                errors, start, &#segment,
+                            );
+                            deltoid::DeltaError::scope_all(
+                                errors,
+                                start,
+                                &deltoid::PathSegment::Variant(
+                                    stringify!(#variant_name).to_string()
+                                ),
+                            );
+                        }
+                    }
+                })
+                .collect();
+            validation_bodies.push(quote! { #(#field_validations)* });
             // NOTE: then, push the pairwise-unequal patterns:
             lhs_patterns.push(quote! { _ });
             delta_patterns.push(quote! {
@@ -380,7 +462,11 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
             });
             match_bodies.push(quote! {
                 use deltoid::FromDelta;
-                Self::from_delta(delta.clone(/*TODO*/))
+                <Self as deltoid::FromDelta>::from_delta(delta.clone(/*TODO*/))
+            });
+            validation_bodies.push(quote! {
+                use deltoid::FromDelta;
+                Self::validate_from_delta(delta, errors)
             });
         },
         (StructVariant::TupleStruct, variant_name, variant_fields) => {
@@ -394,15 +480,27 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
             let delta_names: Vec<Ident2> = (0 .. field_count)
                 .map(|ident| format_ident!("delta_{}", ident))
                 .collect();
+            let segments: Vec<TokenStream2> = (0 .. field_count)
+                .map(|fidx| quote! { deltoid::PathSegment::Position(#fidx) })
+                .collect();
             let field_values: Vec<TokenStream2> = variant_fields.iter()
                 .zip(lhs_names.iter())
                 .zip(delta_names.iter())
-                .map(|((f, lhs_name), delta_name)| if f.ignore_field() {
+                .zip(segments.iter())
+                .map(|(((f, lhs_name), delta_name), segment)| if f.ignore_field() {
                     quote! { #lhs_name.clone() }
                 } else {
+                    let apply = f.apply_tokens(
+                        quote! { #lhs_name }, quote! { delta.clone(/*TODO*/) }
+                    );
                     quote! {
                         if let Some(delta) = #delta_name {
-                            #lhs_name.apply(delta.clone(/*TODO*/))?
+                            #apply
+                                .map_err(|e| e.at(#segment).at(
+                                    deltoid::PathSegment::Variant(
+                                        stringify!(#variant_name).to_string()
+                                    )
+                                ))?
                         } else {
                             #lhs_name.clone()
                         }
@@ -419,6 +517,34 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
             match_bodies.push(quote! {
                 Ok(Self::#variant_name( #(#field_values),* ))
             });
+            let field_validations: Vec<TokenStream2> = variant_fields.iter()
+                .zip(lhs_names.iter())
+                .zip(delta_names.iter())
+                .zip(segments.iter())
+                .filter(|(((f, _), _), _)| !f.ignore_field())
+                .map(|(((f, lhs_name), delta_name), segment)| {
+                    let validate = f.validate_tokens(
+                        quote! { #lhs_name }, quote! { delta }
+                    );
+                    quote! {
+                        if let Some(delta) = #delta_name {
+                            let start: usize = errors.len();
+                            #validate
+                            deltoid::DeltaError::scope_all(
+                                errors, start, &#segment,
+                            );
+                            deltoid::DeltaError::scope_all(
+                                errors,
+                                start,
+                                &deltoid::PathSegment::Variant(
+                                    stringify!(#variant_name).to_string()
+                                ),
+                            );
+                        }
+                    }
+                })
+                .collect();
+            validation_bodies.push(quote! { #(#field_validations)* });
             // NOTE: then, push the pairwise-unequal patterns:
             lhs_patterns.push(quote! { _ });
             delta_patterns.push(quote! {
@@ -426,7 +552,11 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
             });
             match_bodies.push(quote! {
                 use deltoid::FromDelta;
-                Self::from_delta(delta.clone(/*TODO*/))
+                <Self as deltoid::FromDelta>::from_delta(delta.clone(/*TODO*/))
+            });
+            validation_bodies.push(quote! {
+                use deltoid::FromDelta;
+                Self::validate_from_delta(delta, errors)
             });
         },
         (StructVariant::UnitStruct, variant_name, _variant_fields) => {
@@ -434,22 +564,36 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
             lhs_patterns.push(quote! { Self::#variant_name });
             delta_patterns.push(quote! { Self::Delta::#variant_name });
             match_bodies.push(quote! { Ok(Self::#variant_name) });
+            validation_bodies.push(quote! {});
             // NOTE: then, push the pairwise-unequal patterns:
             lhs_patterns.push(quote! { _ });
             delta_patterns.push(quote! { delta @ Self::Delta::#variant_name });
             match_bodies.push(quote! {
                 use deltoid::FromDelta;
-                Self::from_delta(delta.clone(/*TODO*/))
+                <Self as deltoid::FromDelta>::from_delta(delta.clone(/*TODO*/))
+            });
+            validation_bodies.push(quote! {
+                use deltoid::FromDelta;
+                Self::validate_from_delta(delta, errors)
             });
         },
     }}
     Ok(quote! {
         impl<#(#type_param_decls),*> deltoid::Apply
-            for #type_name<#type_params>
+            for #type_name<#type_args>
             #where_clause
         {
             #[allow(unused)]
             fn apply(&self, delta: Self::Delta) -> deltoid::DeltaResult<Self> {
+                self.apply_with(delta, &deltoid::ApplyPolicy::default())
+            }
+
+            #[allow(unused)]
+            fn apply_with(
+                &self,
+                delta: Self::Delta,
+                policy: &deltoid::ApplyPolicy,
+            ) -> deltoid::DeltaResult<Self> {
                 match (self, &delta/*TODO*/) {
                     #(
                         (#lhs_patterns, #delta_patterns) => {
@@ -458,6 +602,22 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
                     )*
                 }
             }
+
+            #[allow(unused)]
+            fn validate_into(
+                &self,
+                delta: &Self::Delta,
+                errors: &mut Vec<deltoid::DeltaError>,
+            ) {
+                use deltoid::Apply;
+                match (self, delta) {
+                    #(
+                        (#lhs_patterns, #delta_patterns) => {
+                            #validation_bodies
+                        },
+                    )*
+                }
+            }
         }
     })
 }
@@ -468,35 +628,23 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
     let enum_variants: &[EnumVariant] = input.enum_variants()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
-    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
             GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
             GenericParam::Const(const_param)     => quote! { #const_param  },
-            GenericParam::Type(type_param) => {
-                let T: &Ident2 = &type_param.ident;
-                // NOTE: `bounds` defines trait bounds on the corresponding
-                // type parameter `T` in `InputType::Struct#type_param`:
-                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
-                    .map(|trait_bound| quote! { #trait_bound })
-                    .collect();
+            GenericParam::Type(type_param) => input.type_param_decl(
+                type_param,
                 quote! {
-                    #T: std::clone::Clone
-                    + std::fmt::Debug
-                        + std::cmp::PartialEq
-                        + deltoid::Delta
-                        + deltoid::IntoDelta
-                        + for<'de> serde::Deserialize<'de>
-                        + serde::Serialize
-                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
-                }
-            },
+                    std::clone::Clone + std::fmt::Debug + std::cmp::PartialEq
+                    + deltoid::Delta + deltoid::IntoDelta
+                    + for<'de> serde::Deserialize<'de> + serde::Serialize
+                },
+            ),
         })
         .collect();
-    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
-        .map(|where_predicate| quote! { #where_predicate })
-        .collect();
+    let predicates: Vec<TokenStream2> =
+        input.where_predicates(quote! { deltoid::Delta + deltoid::IntoDelta })?;
     let where_clause = quote! { where #(#predicates),* };
     let mut lhs_patterns: Vec<TokenStream2> = vec![];
     let mut rhs_patterns: Vec<TokenStream2> = vec![];
@@ -520,11 +668,14 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
                 .map(|((f, lhs_name), rhs_name)| if f.ignore_field() {
                     quote! { std::marker::PhantomData }
                 } else {
+                    let delta = f.delta_tokens(
+                        quote! { #lhs_name }, quote! { *#rhs_name }
+                    );
                     quote! {
                         if #lhs_name == #rhs_name {
                             None
                         } else {
-                            Some(#lhs_name.delta(#rhs_name)?)
+                            Some(#delta?)
                         }
                     }
                 })
@@ -565,11 +716,14 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
                 .map(|(f, (lhs_name, rhs_name))| if f.ignore_field() {
                     quote! { std::marker::PhantomData }
                 } else {
+                    let delta = f.delta_tokens(
+                        quote! { #lhs_name }, quote! { *#rhs_name }
+                    );
                     quote! {
                         if #lhs_name == #rhs_name {
                             None
                         } else {
-                            Some(#lhs_name.delta(#rhs_name)?)
+                            Some(#delta?)
                         }
                     }
                 })
@@ -608,7 +762,7 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
     }}
     Ok(quote! {
         impl<#(#type_param_decls),*> deltoid::Delta
-            for #type_name<#type_params>
+            for #type_name<#type_args>
             #where_clause
         {
             #[allow(unused)]
@@ -631,40 +785,32 @@ pub(crate) fn define_FromDelta_impl(input: &InputType) -> DeriveResult<TokenStre
     let enum_variants: &[EnumVariant] = input.enum_variants()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
-    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
             GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
             GenericParam::Const(const_param)     => quote! { #const_param  },
-            GenericParam::Type(type_param) => {
-                let T: &Ident2 = &type_param.ident;
-                // NOTE: `bounds` defines trait bounds on the corresponding
-                // type parameter `T` in `InputType::Struct#type_param`:
-                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
-                    .map(|trait_bound| quote! { #trait_bound })
-                    .collect();
+            GenericParam::Type(type_param) => input.type_param_decl(
+                type_param,
                 quote! {
-                    #T:
-                    std::clone::Clone
-                        + std::fmt::Debug
-                        + std::cmp::PartialEq
-                        + deltoid::FromDelta
-                        + for<'de> serde::Deserialize<'de>
-                        + serde::Serialize
-                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
-                }
-            },
+                    std::clone::Clone + std::fmt::Debug + std::cmp::PartialEq
+                    + deltoid::FromDelta + for<'de> serde::Deserialize<'de>
+                    + serde::Serialize
+                },
+            ),
         })
         .collect();
-    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
-        .map(|where_predicate| quote! { #where_predicate })
-        .collect();
+    let predicates: Vec<TokenStream2> =
+        input.where_predicates(quote! { deltoid::FromDelta })?;
     let where_clause = quote! { where #(#predicates),* };
     let mut match_body = TokenStream2::new();
+    let mut validation_body = TokenStream2::new();
     for variant in enum_variants.iter() {
         let variant_name = &variant.name;
-        match_body.extend(match variant.struct_variant {
+        let variant_segment = quote! {
+            deltoid::PathSegment::Variant(stringify!(#variant_name).to_string())
+        };
+        let (arm, validation_arm) = match variant.struct_variant {
             StructVariant::NamedStruct => {
                 let field_names: Vec<_> = variant.fields()
                     .map(|field: &FieldDesc| field.name_ref())
@@ -673,27 +819,57 @@ pub(crate) fn define_FromDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                     .map(|field: &FieldDesc| {
                         let fname = field.name_ref()?;
                         let ftype = field.type_ref();
+                        let from_delta = field.value_from_delta_path();
                         Ok(if field.ignore_field() {
                             quote! { #fname: Default::default() }
                         } else {
                             quote! {
-                                #fname: <#ftype>::from_delta(
-                                    #fname.ok_or_else(|| DeltaError::ExpectedValue {
+                                #fname: #fname
+                                    .ok_or_else(|| DeltaError::ExpectedValue {
                                         type_name: stringify!(#ftype).to_string(),
                                         file: file!().to_string(),
                                         line: line!(),
                                         column: column!(),
-                                    })?
-                                )?
+                                    })
+                                    .and_then(#from_delta)
+                                    .map_err(|e| e
+                                        .at(deltoid::PathSegment::Field(
+                                            stringify!(#fname).to_string()
+                                        ))
+                                        .at(deltoid::PathSegment::Variant(
+                                            stringify!(#variant_name).to_string()
+                                        ))
+                                    )?
                             }
                         })
                     })
                     .collect::<DeriveResult<_>>()?;
-                quote! {
-                    #delta_type_name::#variant_name { #(#field_names),* } => {
-                        Self::#variant_name { #(#field_assignments),* }
+                let field_validations: Vec<TokenStream2> = variant.fields()
+                    .filter(|field: &&FieldDesc| !field.ignore_field())
+                    .map(|field: &FieldDesc| {
+                        let fname = field.name_ref()?;
+                        Ok(field.validate_from_delta_tokens(quote! { #fname }, &[
+                            quote! {
+                                deltoid::PathSegment::Field(
+                                    stringify!(#fname).to_string()
+                                )
+                            },
+                            variant_segment.clone(),
+                        ]))
+                    })
+                    .collect::<DeriveResult<_>>()?;
+                (
+                    quote! {
+                        #delta_type_name::#variant_name { #(#field_names),* } => {
+                            Self::#variant_name { #(#field_assignments),* }
+                        },
                     },
-                }
+                    quote! {
+                        #delta_type_name::#variant_name { #(#field_names),* } => {
+                            #(#field_validations)*
+                        },
+                    },
+                )
             },
             StructVariant::TupleStruct => {
                 let field_types: Vec<_> = variant.fields()
@@ -708,38 +884,68 @@ pub(crate) fn define_FromDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                     .map(|(fidx, field): (usize, &FieldDesc)| {
                         let fname = &field_names[fidx];
                         let ftype = field.type_ref();
+                        let from_delta = field.value_from_delta_path();
                         Ok(if field.ignore_field() {
                             quote! { Default::default() }
                         } else {
                             quote! {
-                                <#ftype>::from_delta(
-                                    #fname.ok_or_else(|| DeltaError::ExpectedValue {
+                                #fname
+                                    .ok_or_else(|| DeltaError::ExpectedValue {
                                         type_name: stringify!(#ftype).to_string(),
                                         file: file!().to_string(),
                                         line: line!(),
                                         column: column!(),
-                                    })?
-                                )?
+                                    })
+                                    .and_then(#from_delta)
+                                    .map_err(|e| e
+                                        .at(deltoid::PathSegment::Position(#fidx))
+                                        .at(deltoid::PathSegment::Variant(
+                                            stringify!(#variant_name).to_string()
+                                        ))
+                                    )?
                             }
                         })
                     })
                     .collect::<DeriveResult<_>>()?;
-                quote! {
-                    #delta_type_name::#variant_name( #(#field_names),* ) => {
-                        Self::#variant_name( #(#field_assignments),* )
+                let field_validations: Vec<TokenStream2> = variant.fields()
+                    .enumerate()
+                    .filter(|(_, field): &(usize, &FieldDesc)| !field.ignore_field())
+                    .map(|(fidx, field): (usize, &FieldDesc)| {
+                        let fname = &field_names[fidx];
+                        field.validate_from_delta_tokens(quote! { #fname }, &[
+                            quote! { deltoid::PathSegment::Position(#fidx) },
+                            variant_segment.clone(),
+                        ])
+                    })
+                    .collect();
+                (
+                    quote! {
+                        #delta_type_name::#variant_name( #(#field_names),* ) => {
+                            Self::#variant_name( #(#field_assignments),* )
+                        },
                     },
-                }
+                    quote! {
+                        #delta_type_name::#variant_name( #(#field_names),* ) => {
+                            #(#field_validations)*
+                        },
+                    },
+                )
             },
-            StructVariant::UnitStruct => quote! {
-                #delta_type_name::#variant_name => {
-                    Self::#variant_name
+            StructVariant::UnitStruct => (
+                quote! {
+                    #delta_type_name::#variant_name => {
+                        Self::#variant_name
+                    },
                 },
-            },
-        });
+                quote! { #delta_type_name::#variant_name => {}, },
+            ),
+        };
+        match_body.extend(arm);
+        validation_body.extend(validation_arm);
     }
     Ok(quote! {
         impl<#(#type_param_decls),*> deltoid::FromDelta
-            for #type_name<#type_params>
+            for #type_name<#type_args>
             #where_clause
         {
             #[allow(unused)]
@@ -749,6 +955,16 @@ pub(crate) fn define_FromDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                     #match_body
                 })
             }
+
+            #[allow(unused)]
+            fn validate_from_delta(
+                delta: &Self::Delta,
+                errors: &mut Vec<deltoid::DeltaError>,
+            ) {
+                match delta {
+                    #validation_body
+                }
+            }
         }
     })
 }
@@ -760,35 +976,23 @@ pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStre
     let enum_variants: &[EnumVariant] = input.enum_variants()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
-    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
             GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
             GenericParam::Const(const_param)     => quote! { #const_param  },
-            GenericParam::Type(type_param) => {
-                let T: &Ident2 = &type_param.ident;
-                // NOTE: `bounds` defines trait bounds on the corresponding
-                // type parameter `T` in `InputType::Struct#type_param`:
-                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
-                    .map(|trait_bound| quote! { #trait_bound })
-                    .collect();
+            GenericParam::Type(type_param) => input.type_param_decl(
+                type_param,
                 quote! {
-                    #T:
-                    std::clone::Clone
-                        + std::fmt::Debug
-                        + std::cmp::PartialEq
-                        + deltoid::IntoDelta
-                        + for<'de> serde::Deserialize<'de>
-                        + serde::Serialize
-                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
-                }
-            },
+                    std::clone::Clone + std::fmt::Debug + std::cmp::PartialEq
+                    + deltoid::IntoDelta + for<'de> serde::Deserialize<'de>
+                    + serde::Serialize
+                },
+            ),
         })
         .collect();
-    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
-        .map(|where_predicate| quote! { #where_predicate })
-        .collect();
+    let predicates: Vec<TokenStream2> =
+        input.where_predicates(quote! { deltoid::IntoDelta })?;
     let where_clause = quote! { where #(#predicates),* };
     let mut match_body = TokenStream2::new();
     for enum_variant in enum_variants.iter() {
@@ -805,7 +1009,9 @@ pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                         Ok(if field.ignore_field() {
                             quote! { #fname: std::marker::PhantomData }
                         } else {
-                            quote! { #fname: Some(#fname.into_delta()?) }
+                            let into_delta =
+                                field.delta_from_value_tokens(quote! { #fname });
+                            quote! { #fname: Some(#into_delta?) }
                         })
                     })
                     .collect::<DeriveResult<_>>()?;
@@ -829,7 +1035,9 @@ pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                         Ok(if field.ignore_field() {
                             quote! { std::marker::PhantomData }
                         } else {
-                            quote! { Some(#fname.into_delta()?) }
+                            let into_delta =
+                                field.delta_from_value_tokens(quote! { #fname });
+                            quote! { Some(#into_delta?) }
                         })
                     })
                     .collect::<DeriveResult<_>>()?;
@@ -850,7 +1058,7 @@ pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStre
     }
     Ok(quote! {
         impl<#(#type_param_decls),*> deltoid::IntoDelta
-            for #type_name<#type_params>
+            for #type_name<#type_args>
             #where_clause
         {
             #[allow(unused)]
@@ -863,3 +1071,96 @@ pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStre
         }
     })
 }
+
+pub(crate) fn define_ToDynDelta_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_enum() { return bug_detected!() }
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let enum_variants: &[EnumVariant] = input.enum_variants()?;
+    let type_args: Punctuated<GenericArgument, Comma> =
+        input.delta_type_args()?;
+    let type_param_decls: Vec<TokenStream2> =
+        input.delta_type_param_decls(quote! { deltoid::Core })?;
+    let predicates: Vec<TokenStream2> = input.dyn_delta_predicates()?;
+    let where_clause = quote! { where #(#predicates),* };
+    let mut field_patterns: Vec<TokenStream2> = vec![];
+    let mut match_bodies: Vec<TokenStream2> = vec![];
+    for v in enum_variants.iter() { match (v.struct_variant, &v.name, &v.fields) {
+        (StructVariant::NamedStruct, variant_name, variant_fields) => {
+            let field_names: Vec<&Ident2> = variant_fields.iter()
+                .map(|field: &FieldDesc| field.name_ref().unwrap())
+                .collect();
+            let field_changes: Vec<TokenStream2> = variant_fields.iter()
+                .filter(|field: &&FieldDesc| !field.ignore_field())
+                .map(|field: &FieldDesc| {
+                    let fname = field.name_ref()?;
+                    Ok(quote! {
+                        deltoid::DynDelta::field(
+                            stringify!(#fname),
+                            #fname.to_dyn_delta()
+                        )
+                    })
+                })
+                .collect::<DeriveResult<_>>()?;
+            field_patterns.push(quote! {
+                Self::#variant_name { #(#field_names),* }
+            });
+            match_bodies.push(quote! {
+                deltoid::DynDelta::variant(
+                    stringify!(#variant_name),
+                    deltoid::DynDelta::changes(vec![ #(#field_changes),* ])
+                )
+            });
+        },
+        (StructVariant::TupleStruct, variant_name, variant_fields) => {
+            let field_names: Vec<Ident2> = (0 .. variant_fields.len())
+                .map(|ident| format_ident!("field_{}", ident))
+                .collect();
+            let field_changes: Vec<TokenStream2> = variant_fields.iter()
+                .enumerate()
+                .filter(|(_, field)| !field.ignore_field())
+                .map(|(fidx, _)| {
+                    let fname = &field_names[fidx];
+                    quote! {
+                        deltoid::DynDelta::field(#fidx, #fname.to_dyn_delta())
+                    }
+                })
+                .collect();
+            field_patterns.push(quote! {
+                Self::#variant_name( #(#field_names),* )
+            });
+            match_bodies.push(quote! {
+                deltoid::DynDelta::variant(
+                    stringify!(#variant_name),
+                    deltoid::DynDelta::changes(vec![ #(#field_changes),* ])
+                )
+            });
+        },
+        (StructVariant::UnitStruct, variant_name, _variant_fields) => {
+            field_patterns.push(quote! {
+                Self::#variant_name
+            });
+            match_bodies.push(quote! {
+                deltoid::DynDelta::variant(
+                    stringify!(#variant_name),
+                    deltoid::DynDelta::Unchanged
+                )
+            });
+        },
+    }}
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::ToDynDelta
+            for #delta_type_name<#type_args>
+            #where_clause
+        {
+            #[allow(unused)]
+            fn to_dyn_delta(&self) -> deltoid::DynDelta {
+                use deltoid::ToDynDelta;
+                match self {
+                    #(
+                        #field_patterns => #match_bodies,
+                    )*
+                }
+            }
+        }
+    })
+}
@@ -1,33 +1,342 @@
 //!
 
-use proc_macro2::{
-    Delimiter as Delimiter2,
-    TokenTree as TokenTree2
-};
+use crate::DeriveResult;
 use syn::*;
 
 
+/// The arguments of all `#[delta(...)]` attributes in `attrs`.
+pub(crate) fn delta_args(attrs: &[Attribute]) -> DeriveResult<Vec<NestedMeta>> {
+    let mut args: Vec<NestedMeta> = vec![];
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("delta")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => args.extend(list.nested),
+            meta => return Err(Error::new_spanned(
+                meta, "expected `#[delta(...)]`"
+            ).into()),
+        }
+    }
+    Ok(args)
+}
+
+/// An error for a `#[delta(...)]` argument that isn't supported in its place.
+pub(crate) fn unknown_arg(arg: &NestedMeta) -> crate::DeriveError {
+    let name: String = match arg {
+        NestedMeta::Meta(meta) => meta.path().get_ident()
+            .map(|ident| ident.to_string())
+            .unwrap_or_else(|| quote::quote!(#meta).to_string()),
+        NestedMeta::Lit(lit) => quote::quote!(#lit).to_string(),
+    };
+    Error::new_spanned(arg, format!("unknown delta argument `{}`", name)).into()
+}
+
+/// The `#[delta(...)]` arguments supported on the input type.
+pub(crate) const TYPE_ARGS: &[&str] = &[
+    "atomic", "bound", "derive", "name", "remote", "serde", "skip_unchanged",
+    "transparent", "vis",
+];
+
+/// The `#[delta(...)]` arguments supported on a field.
+pub(crate) const FIELD_ARGS: &[&str] = &[
+    "atomic", "bound", "flatten", "ignore_field", "remote", "with",
+];
+
+/// The name of a `#[delta(...)]` argument e.g. `bound` in `bound = "..."`.
+fn arg_name(arg: &NestedMeta) -> Option<&Ident> {
+    match arg {
+        NestedMeta::Meta(meta) => meta.path().get_ident(),
+        NestedMeta::Lit(_) => None,
+    }
+}
+
+/// The arguments of all `#[delta(...)]` attributes in `attrs`,
+/// ensuring that each of them is one of the `supported` arguments.
+pub(crate) fn supported_delta_args(
+    attrs: &[Attribute],
+    supported: &[&str],
+) -> DeriveResult<Vec<NestedMeta>> {
+    let args: Vec<NestedMeta> = delta_args(attrs)?;
+    for arg in args.iter() {
+        match arg_name(arg) {
+            Some(name) if supported.iter().any(|s| name == s) => {},
+            _ => return Err(unknown_arg(arg)),
+        }
+    }
+    Ok(args)
+}
+
+/// Ensure `attrs` contains no `#[delta(...)]` arguments, e.g. for the
+/// attributes of an input enum variant.
+pub(crate) fn no_delta_args(attrs: &[Attribute]) -> DeriveResult<()> {
+    match delta_args(attrs)?.first() {
+        Some(arg) => Err(unknown_arg(arg)),
+        None => Ok(()),
+    }
+}
+
+/// `true` iff. `arg` is named `name`.
+fn is_named(arg: &NestedMeta, name: &str) -> bool {
+    arg_name(arg).is_some_and(|arg_name| arg_name == name)
+}
+
+/// `true` iff. `args` contains the flag argument `#[delta(#name)]`.
+fn flag_arg(args: &[NestedMeta], name: &str) -> DeriveResult<bool> {
+    let mut found = false;
+    for arg in args.iter().filter(|arg| is_named(arg, name)) {
+        match arg {
+            NestedMeta::Meta(Meta::Path(_)) => found = true,
+            arg => return Err(Error::new_spanned(
+                arg, format!("expected `#[delta({})]`", name)
+            ).into()),
+        }
+    }
+    Ok(found)
+}
+
+/// The string literal of the argument `#[delta(#name = "...")]` in `args`.
+fn str_arg(args: &[NestedMeta], name: &str) -> DeriveResult<Option<LitStr>> {
+    let mut found: Option<LitStr> = None;
+    for arg in args.iter().filter(|arg| is_named(arg, name)) {
+        match arg {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(lit), ..
+            })) if found.is_none() => found = Some(lit.clone()),
+            NestedMeta::Meta(Meta::NameValue(_)) => return Err(Error::new_spanned(
+                arg, format!("duplicate delta argument `{}`", name)
+            ).into()),
+            arg => return Err(Error::new_spanned(
+                arg, format!("expected `#[delta({} = \"...\")]`", name)
+            ).into()),
+        }
+    }
+    Ok(found)
+}
+
+/// The where-predicates of a `#[delta(bound = "...")]` argument in `args`.
+fn bound_arg(args: &[NestedMeta]) -> DeriveResult<Option<Vec<WherePredicate>>> {
+    let lit: LitStr = match str_arg(args, "bound")? {
+        Some(lit) => lit,
+        None => return Ok(None),
+    };
+    let predicates = lit.parse_with(
+        punctuated::Punctuated::<WherePredicate, token::Comma>::parse_terminated
+    )?;
+    Ok(Some(predicates.into_iter().collect()))
+}
+
+/// The input type is marked with `#[delta(bound = "...")]`, which replaces
+/// the bounds that would otherwise be inferred for the generated items.
+pub(crate) fn type_bound(attrs: &[Attribute])
+                         -> DeriveResult<Option<Vec<WherePredicate>>> {
+    bound_arg(&supported_delta_args(attrs, TYPE_ARGS)?)
+}
+
 /// A `field` in the input struct or input enum variant
 /// is marked with #[delta(ignore_field)].
-pub(crate) fn ignore_field(field: &Field) -> bool {
-    let mut ignore = false;
-    for attr in field.attrs.iter() {
-        let attr_segments: Vec<String> = attr.path.segments.iter()
-            .map(|path_segment| format!("{}", path_segment.ident))
-            .collect();
-        let is_delta_attr = attr_segments == &["delta"];
-        let arg_tokens_iter = attr.tokens.clone().into_iter().next();
-        const DELIM: Delimiter2 = Delimiter2::Parenthesis;
-        let arg_is_ignore_field = match arg_tokens_iter {
-            Some(TokenTree2::Group(g)) if g.delimiter() == DELIM => {
-                let tokens: Vec<String> = g.stream().clone().into_iter()
-                    .map(|tt| format!("{}", tt))
-                    .collect();
-                tokens == &["ignore_field"]
-            },
-            _ => false,
-        };
-        ignore = ignore || is_delta_attr && arg_is_ignore_field
-    }
-    ignore
+pub(crate) fn ignore_field(field: &Field) -> DeriveResult<bool> {
+    flag_arg(&supported_delta_args(&field.attrs, FIELD_ARGS)?, "ignore_field")
+}
+
+/// A `field` in the input struct or input enum variant is marked with
+/// `#[delta(bound = "...")]`, which replaces the bounds that would
+/// otherwise be inferred for the field's type.
+pub(crate) fn field_bound(field: &Field)
+                          -> DeriveResult<Option<Vec<WherePredicate>>> {
+    bound_arg(&supported_delta_args(&field.attrs, FIELD_ARGS)?)
+}
+
+/// A `field` in the input struct or input enum variant is marked with
+/// `#[delta(with = "module")]`, which routes the delta logic for the field
+/// through the functions in `module`.
+pub(crate) fn field_with(field: &Field) -> DeriveResult<Option<Path>> {
+    let args: Vec<NestedMeta> = supported_delta_args(&field.attrs, FIELD_ARGS)?;
+    let lit: LitStr = match str_arg(&args, "with")? {
+        Some(lit) => lit,
+        None => return Ok(None),
+    };
+    for other in &["ignore_field", "atomic"] {
+        if flag_arg(&args, other)? {
+            return Err(Error::new_spanned(
+                lit, format!("`with` can't be combined with `{}`", other)
+            ).into());
+        }
+    }
+    if str_arg(&args, "remote")?.is_some() {
+        return Err(Error::new_spanned(
+            lit, "`with` can't be combined with `remote`"
+        ).into());
+    }
+    Ok(Some(lit.parse()?))
+}
+
+/// The input type is marked with `#[delta(atomic)]`, i.e. its delta
+/// replaces a value as a whole rather than diffing its fields.
+pub(crate) fn type_atomic(attrs: &[Attribute]) -> DeriveResult<bool> {
+    flag_arg(&supported_delta_args(attrs, TYPE_ARGS)?, "atomic")
+}
+
+/// A `field` in the input struct or input enum variant is marked with
+/// `#[delta(atomic)]`, i.e. its delta replaces the field value as a whole.
+pub(crate) fn field_atomic(field: &Field) -> DeriveResult<bool> {
+    let args: Vec<NestedMeta> = supported_delta_args(&field.attrs, FIELD_ARGS)?;
+    let atomic: bool = flag_arg(&args, "atomic")?;
+    if atomic && flag_arg(&args, "ignore_field")? {
+        let arg = args.iter().find(|arg| is_named(arg, "atomic"));
+        return Err(Error::new_spanned(
+            arg, "`atomic` can't be combined with `ignore_field`"
+        ).into());
+    }
+    Ok(atomic)
+}
+
+/// The input type is marked with `#[delta(remote = "path::to::Type")]`,
+/// i.e. it mirrors the definition of a type from another crate.
+pub(crate) fn type_remote(attrs: &[Attribute]) -> DeriveResult<Option<Path>> {
+    match str_arg(&supported_delta_args(attrs, TYPE_ARGS)?, "remote")? {
+        Some(lit) => Ok(Some(lit.parse()?)),
+        None => Ok(None),
+    }
+}
+
+/// A `field` in the input struct or input enum variant is marked with
+/// `#[delta(remote = "Mirror")]`, which routes the delta logic for the
+/// field through the remote functions generated for the type `Mirror`.
+pub(crate) fn field_remote(field: &Field) -> DeriveResult<Option<Type>> {
+    let args: Vec<NestedMeta> = supported_delta_args(&field.attrs, FIELD_ARGS)?;
+    let lit: LitStr = match str_arg(&args, "remote")? {
+        Some(lit) => lit,
+        None => return Ok(None),
+    };
+    for other in &["ignore_field", "atomic"] {
+        if flag_arg(&args, other)? {
+            return Err(Error::new_spanned(
+                lit, format!("`remote` can't be combined with `{}`", other)
+            ).into());
+        }
+    }
+    Ok(Some(lit.parse()?))
+}
+
+/// The input type is marked with `#[delta(transparent)]`, i.e. its delta
+/// is the delta of its only field.
+pub(crate) fn type_transparent(attrs: &[Attribute]) -> DeriveResult<bool> {
+    let args: Vec<NestedMeta> = supported_delta_args(attrs, TYPE_ARGS)?;
+    let transparent: bool = flag_arg(&args, "transparent")?;
+    if transparent && flag_arg(&args, "atomic")? {
+        let arg = args.iter().find(|arg| is_named(arg, "transparent"));
+        return Err(Error::new_spanned(
+            arg, "`transparent` can't be combined with `atomic`"
+        ).into());
+    }
+    Ok(transparent)
+}
+
+/// The input type is marked with `#[delta(skip_unchanged)]`, i.e. the
+/// unchanged named fields of its delta are left out when serialized.
+pub(crate) fn type_skip_unchanged(attrs: &[Attribute]) -> DeriveResult<bool> {
+    let args: Vec<NestedMeta> = supported_delta_args(attrs, TYPE_ARGS)?;
+    let skip_unchanged: bool = flag_arg(&args, "skip_unchanged")?;
+    for other in &["atomic", "transparent"] {
+        if skip_unchanged && flag_arg(&args, other)? {
+            let arg = args.iter().find(|arg| is_named(arg, "skip_unchanged"));
+            return Err(Error::new_spanned(
+                arg, format!("`skip_unchanged` can't be combined with `{}`", other)
+            ).into());
+        }
+    }
+    Ok(skip_unchanged)
+}
+
+/// A `field` in the input struct is marked with `#[delta(flatten)]`, i.e.
+/// the fields of its delta are merged into the delta of the input struct.
+pub(crate) fn field_flatten(field: &Field) -> DeriveResult<bool> {
+    let args: Vec<NestedMeta> = supported_delta_args(&field.attrs, FIELD_ARGS)?;
+    let flatten: bool = flag_arg(&args, "flatten")?;
+    for other in &["ignore_field", "atomic"] {
+        if flatten && flag_arg(&args, other)? {
+            let arg = args.iter().find(|arg| is_named(arg, "flatten"));
+            return Err(Error::new_spanned(
+                arg, format!("`flatten` can't be combined with `{}`", other)
+            ).into());
+        }
+    }
+    Ok(flatten)
+}
+
+/// Ensure a `field` of the input type isn't marked with `#[delta(flatten)]`,
+/// e.g. for a positional field or a field of an input enum variant.
+pub(crate) fn no_field_flatten(field: &Field) -> DeriveResult<()> {
+    if field_flatten(field)? {
+        return Err(Error::new_spanned(
+            field,
+            "`flatten` is only supported on the named fields of a struct"
+        ).into());
+    }
+    Ok(())
+}
+
+/// The name of the delta type of the input type named `type_name`, which
+/// is set with `#[delta(name = "...")]` and is `{TypeName}Delta` otherwise.
+pub(crate) fn type_delta_name(attrs: &[Attribute], type_name: &Ident)
+                              -> DeriveResult<Ident> {
+    match str_arg(&supported_delta_args(attrs, TYPE_ARGS)?, "name")? {
+        Some(lit) => Ok(lit.parse()?),
+        None => Ok(quote::format_ident!("{}Delta", type_name)),
+    }
+}
+
+/// The visibility of the delta type of the input type, which is set with
+/// `#[delta(vis = "...")]` and is `pub` otherwise.
+pub(crate) fn type_delta_vis(attrs: &[Attribute]) -> DeriveResult<Visibility> {
+    match str_arg(&supported_delta_args(attrs, TYPE_ARGS)?, "vis")? {
+        Some(lit) => Ok(lit.parse()?),
+        None => Ok(parse_quote! { pub }),
+    }
+}
+
+/// The lists of the arguments `#[delta(#name(...))]` in `args`, ensuring
+/// the input type isn't marked with `#[delta(atomic)]` or
+/// `#[delta(transparent)]`, whose delta types aren't generated.
+fn list_args<'a>(args: &'a [NestedMeta], name: &str)
+                 -> DeriveResult<Vec<&'a MetaList>> {
+    let mut lists: Vec<&MetaList> = vec![];
+    for arg in args.iter().filter(|arg| is_named(arg, name)) {
+        match arg {
+            NestedMeta::Meta(Meta::List(list)) => lists.push(list),
+            arg => return Err(Error::new_spanned(
+                arg, format!("expected `#[delta({}(...))]`", name)
+            ).into()),
+        }
+        for other in &["atomic", "transparent"] {
+            if flag_arg(args, other)? {
+                return Err(Error::new_spanned(
+                    arg, format!("`{}` can't be combined with `{}`", name, other)
+                ).into());
+            }
+        }
+    }
+    Ok(lists)
+}
+
+/// The extra traits of `#[delta(derive(...))]` to derive for the delta type.
+pub(crate) fn type_delta_derives(attrs: &[Attribute]) -> DeriveResult<Vec<Path>> {
+    let args: Vec<NestedMeta> = supported_delta_args(attrs, TYPE_ARGS)?;
+    let mut derives: Vec<Path> = vec![];
+    for list in list_args(&args, "derive")? {
+        for nested in list.nested.iter() {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) => derives.push(path.clone()),
+                nested => return Err(Error::new_spanned(
+                    nested, "expected a trait to derive"
+                ).into()),
+            }
+        }
+    }
+    Ok(derives)
+}
+
+/// The `#[delta(serde(...))]` arguments to pass through to the delta type
+/// as `#[serde(...)]` attributes.
+pub(crate) fn type_delta_serde(attrs: &[Attribute]) -> DeriveResult<Vec<MetaList>> {
+    let args: Vec<NestedMeta> = supported_delta_args(attrs, TYPE_ARGS)?;
+    Ok(list_args(&args, "serde")?.into_iter().cloned().collect())
 }
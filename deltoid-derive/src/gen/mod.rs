@@ -2,15 +2,18 @@
 #![allow(unused)]
 #![allow(non_snake_case)]
 
+pub(crate) mod atomic;
 pub(crate) mod enums;
 pub(crate) mod markers;
+pub(crate) mod remote;
 pub(crate) mod structs;
+pub(crate) mod transparent;
 
 use crate::{DeriveError, DeriveResult};
 use crate::gen::markers::*;
 use proc_macro2::{
-    Ident as Ident2, Literal as Literal2, Span as Span2,
-    TokenStream as TokenStream2
+    Group as Group2, Ident as Ident2, Literal as Literal2, Span as Span2,
+    TokenStream as TokenStream2, TokenTree as TokenTree2
 };
 use syn::*;
 use syn::punctuated::*;
@@ -34,9 +37,29 @@ pub enum InputType {
         type_param_decls: Punctuated<GenericParam, Comma>,
         /// The input enum's type parameters without trait bounds e.g. <T, U, V>
         type_params: Punctuated<Ident, Comma>,
-        // TODO: defined lifetimes
+        /// The input enum's lifetime and type parameters without bounds,
+        /// as used in the input enum's type e.g. <'a, T, U, V>
+        type_args: Punctuated<GenericArgument, Comma>,
         /// The input enum's where clause
         where_clause: WhereClause,
+        /// The predicates of the input enum's `#[delta(bound = "...")]`,
+        /// which replace the inferred bounds of the generated items
+        bound: Option<Vec<WherePredicate>>,
+        /// Whether the input enum is marked with `#[delta(atomic)]`
+        atomic: bool,
+        /// The foreign type of the input enum's
+        /// `#[delta(remote = "...")]`, which the input enum mirrors
+        remote: Option<Path>,
+        /// Whether the input enum is marked with `#[delta(transparent)]`
+        transparent: bool,
+        /// Whether the input enum is marked with `#[delta(skip_unchanged)]`
+        skip_unchanged: bool,
+        /// The visibility of the generated delta type
+        delta_vis: Visibility,
+        /// The extra traits of the input enum's `#[delta(derive(...))]`
+        delta_derives: Vec<Path>,
+        /// The input enum's `#[delta(serde(...))]` arguments
+        delta_serde: Vec<MetaList>,
     },
     /// The input type is a struct
     Struct {
@@ -55,9 +78,29 @@ pub enum InputType {
         /// The input struct's type parameters without
         /// trait bounds e.g. <T, U, V>
         type_params: Punctuated<Ident, Comma>,
-        // TODO: defined lifetimes
+        /// The input struct's lifetime and type parameters without bounds,
+        /// as used in the input struct's type e.g. <'a, T, U, V>
+        type_args: Punctuated<GenericArgument, Comma>,
         /// The input struct's where clause
         where_clause: WhereClause,
+        /// The predicates of the input struct's `#[delta(bound = "...")]`,
+        /// which replace the inferred bounds of the generated items
+        bound: Option<Vec<WherePredicate>>,
+        /// Whether the input struct is marked with `#[delta(atomic)]`
+        atomic: bool,
+        /// The foreign type of the input struct's
+        /// `#[delta(remote = "...")]`, which the input struct mirrors
+        remote: Option<Path>,
+        /// Whether the input struct is marked with `#[delta(transparent)]`
+        transparent: bool,
+        /// Whether the input struct is marked with `#[delta(skip_unchanged)]`
+        skip_unchanged: bool,
+        /// The visibility of the generated delta type
+        delta_vis: Visibility,
+        /// The extra traits of the input struct's `#[delta(derive(...))]`
+        delta_derives: Vec<Path>,
+        /// The input struct's `#[delta(serde(...))]` arguments
+        delta_serde: Vec<MetaList>,
     },
     /// The input type is a union.  This is unsupported.
     #[allow(unused)]
@@ -67,22 +110,69 @@ pub enum InputType {
 
 impl InputType {
     pub fn parse(input: &DeriveInput) -> DeriveResult<Self> {
-        match &input.data {
+        for param in input.generics.params.iter() { match param {
+            GenericParam::Type(_) | GenericParam::Lifetime(_) => {},
+            GenericParam::Const(_) => return Err(Error::new_spanned(
+                param, "Delta cannot be derived for types with const parameters"
+            ).into()),
+        }}
+        let new = match &input.data {
             Data::Struct(DataStruct { fields, .. }) if !fields.is_empty() =>
-                Self::parse_struct(input, fields),
+                Self::parse_struct(input, fields)?,
             Data::Struct(DataStruct { .. }) =>
-                Self::parse_unit_struct(input),
+                Self::parse_unit_struct(input)?,
             Data::Enum(DataEnum { variants, .. }) =>
-                Self::parse_enum(input, variants),
-            Data::Union(_) => Ok(Self::Union),
+                Self::parse_enum(input, variants)?,
+            Data::Union(DataUnion { union_token, .. }) => return Err(Error::new_spanned(
+                union_token, "Delta cannot be derived for unions"
+            ).into()),
+        };
+        if new.is_transparent()? && new.transparent_field()?.is_none() {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "`transparent` requires a struct with exactly one field \
+                 that isn't ignored"
+            ).into());
         }
+        let erased: Vec<Ident2> = new.erased_lifetimes()?;
+        let fields: Vec<&FieldDesc> = match &new {
+            Self::Enum { enum_variants, .. } => enum_variants.iter()
+                .flat_map(|enum_variant| enum_variant.fields())
+                .collect(),
+            Self::Struct { fields, .. } => fields.iter().collect(),
+            Self::Union => vec![],
+        };
+        for field in fields.into_iter() {
+            let ty: &Type = field.type_ref();
+            let tokens: TokenStream2 = quote! { #ty };
+            let static_ty = erase_lifetimes(tokens.clone(), &erased);
+            let borrows: bool = static_ty.to_string() != tokens.to_string();
+            let message: &str = if !borrows
+                || field.ignore_field()
+                || field.with().is_some()
+                || field.remote().is_some()
+            {
+                continue;
+            } else if field.is_atomic() {
+                "`atomic` can't be used on a field that borrows, since the \
+                 delta type would have to borrow too"
+            } else if owned_cow_type(ty, &erased).is_none() {
+                "a field that borrows must be a `Cow` of an owned type, \
+                 since the delta type doesn't borrow; use \
+                 `#[delta(with = \"...\")]` for other types"
+            } else {
+                continue;
+            };
+            return Err(Error::new_spanned(ty, message).into());
+        }
+        Ok(new)
     }
 
     fn parse_struct(
         input: &DeriveInput,
         input_fields: &Fields,
     ) -> DeriveResult<Self> {
-        let mut new = Self::new_struct(input);
+        let mut new = Self::new_struct(input)?;
         if let Self::Struct { struct_variant, fields, .. } = &mut new {
             for (fidx, field) in input_fields.iter().enumerate() {
                 if let Some(field_ident) = field.ident.as_ref() {
@@ -90,14 +180,24 @@ impl InputType {
                     fields.push(FieldDesc::Named {
                         name: field_ident.clone(),
                         ty: field.ty.clone(),
-                        ignore_field: ignore_field(field),
+                        ignore_field: ignore_field(field)?,
+                        bound: field_bound(field)?,
+                        with: field_with(field)?,
+                        atomic: field_atomic(field)?,
+                        remote: field_remote(field)?,
+                        flatten: field_flatten(field)?,
                     });
                 } else {
                     *struct_variant = StructVariant::TupleStruct;
+                    no_field_flatten(field)?;
                     fields.push(FieldDesc::Positional {
                         position: Literal2::usize_unsuffixed(fidx),
                         ty: field.ty.clone(),
-                        ignore_field: ignore_field(field),
+                        ignore_field: ignore_field(field)?,
+                        bound: field_bound(field)?,
+                        with: field_with(field)?,
+                        atomic: field_atomic(field)?,
+                        remote: field_remote(field)?,
                     });
                 }
             }
@@ -110,7 +210,7 @@ impl InputType {
     }
 
     fn parse_unit_struct(input: &DeriveInput) -> DeriveResult<Self> {
-        let mut new = Self::new_struct(input);
+        let mut new = Self::new_struct(input)?;
         if let Self::Struct { struct_variant, .. } = &mut new {
             *struct_variant = StructVariant::UnitStruct;
         }
@@ -121,24 +221,35 @@ impl InputType {
         input: &DeriveInput,
         input_enum_variants: &Punctuated<Variant, Comma>,
     ) -> DeriveResult<Self> {
-        let mut new = Self::new_enum(input);
+        let mut new = Self::new_enum(input)?;
         if let Self::Enum { enum_variants, .. } = &mut new {
             for iev in input_enum_variants {
+                no_delta_args(&iev.attrs)?;
                 let mut variant = EnumVariant::new(&iev.ident);
                 for (fidx, field) in iev.fields.iter().enumerate() {
+                    no_field_flatten(field)?;
                     if let Some(field_ident) = field.ident.as_ref() {
                         variant.struct_variant = StructVariant::NamedStruct;
                         variant.add_field(FieldDesc::Named {
                             name: field_ident.clone(),
                             ty: field.ty.clone(),
-                            ignore_field: ignore_field(field),
+                            ignore_field: ignore_field(field)?,
+                            bound: field_bound(field)?,
+                            with: field_with(field)?,
+                            atomic: field_atomic(field)?,
+                            remote: field_remote(field)?,
+                            flatten: false,
                         });
                     } else {
                         variant.struct_variant = StructVariant::TupleStruct;
                         variant.add_field(FieldDesc::Positional {
                             position: Literal2::usize_unsuffixed(fidx),
                             ty: field.ty.clone(),
-                            ignore_field: ignore_field(field),
+                            ignore_field: ignore_field(field)?,
+                            bound: field_bound(field)?,
+                            with: field_with(field)?,
+                            atomic: field_atomic(field)?,
+                            remote: field_remote(field)?,
                         });
                     }
                 }
@@ -152,39 +263,73 @@ impl InputType {
         Ok(new)
     }
 
-    fn new_enum(input: &DeriveInput) -> Self {
-        Self::Enum {
+    fn parse_type_args(input: &DeriveInput)
+                       -> Punctuated<GenericArgument, Comma> {
+        input.generics.params.iter()
+            .filter_map(|param| match param {
+                GenericParam::Lifetime(lifetime_def) => Some(
+                    GenericArgument::Lifetime(lifetime_def.lifetime.clone())
+                ),
+                GenericParam::Type(type_param) => {
+                    let T: &Ident2 = &type_param.ident;
+                    Some(GenericArgument::Type(parse_quote! { #T }))
+                },
+                GenericParam::Const(_) => None,
+            })
+            .collect()
+    }
+
+    fn new_enum(input: &DeriveInput) -> DeriveResult<Self> {
+        Ok(Self::Enum {
             type_name: input.ident.clone(),
-            delta_type_name: format_ident!("{}Delta", &input.ident),
+            delta_type_name: type_delta_name(&input.attrs, &input.ident)?,
             enum_variants: vec![],
             type_param_decls: input.generics.params.clone(),
             type_params: input.generics.type_params()
                 .map(|type_param| type_param.ident.clone())
                 .collect(),
+            type_args: Self::parse_type_args(input),
             where_clause: input.generics.where_clause.clone()
                 .unwrap_or_else(|| WhereClause {
                     where_token: Token![where](Span2::call_site()),
                     predicates: Punctuated::new(),
                 }),
-        }
+            bound: type_bound(&input.attrs)?,
+            atomic: type_atomic(&input.attrs)?,
+            remote: type_remote(&input.attrs)?,
+            transparent: type_transparent(&input.attrs)?,
+            skip_unchanged: type_skip_unchanged(&input.attrs)?,
+            delta_vis: type_delta_vis(&input.attrs)?,
+            delta_derives: type_delta_derives(&input.attrs)?,
+            delta_serde: type_delta_serde(&input.attrs)?,
+        })
     }
 
-    fn new_struct(input: &DeriveInput) -> Self {
-        Self::Struct {
+    fn new_struct(input: &DeriveInput) -> DeriveResult<Self> {
+        Ok(Self::Struct {
             struct_variant: StructVariant::UnitStruct,
             type_name: input.ident.clone(),
-            delta_type_name: format_ident!("{}Delta", &input.ident),
+            delta_type_name: type_delta_name(&input.attrs, &input.ident)?,
             fields: vec![],
             type_param_decls: input.generics.params.clone(),
             type_params: input.generics.type_params()
                 .map(|type_param| type_param.ident.clone())
                 .collect(),
+            type_args: Self::parse_type_args(input),
             where_clause: input.generics.where_clause.clone()
                 .unwrap_or_else(|| WhereClause {
                     where_token: Token![where](Span2::call_site()),
                     predicates: Punctuated::new(),
                 }),
-        }
+            bound: type_bound(&input.attrs)?,
+            atomic: type_atomic(&input.attrs)?,
+            remote: type_remote(&input.attrs)?,
+            transparent: type_transparent(&input.attrs)?,
+            skip_unchanged: type_skip_unchanged(&input.attrs)?,
+            delta_vis: type_delta_vis(&input.attrs)?,
+            delta_derives: type_delta_derives(&input.attrs)?,
+            delta_serde: type_delta_serde(&input.attrs)?,
+        })
     }
 
     pub fn is_enum(&self) -> bool { matches!(self, Self::Enum { .. }) }
@@ -233,6 +378,14 @@ impl InputType {
         })
     }
 
+    pub fn type_args(&self) -> DeriveResult<&Punctuated<GenericArgument, Comma>> {
+        Ok(match self {
+            Self::Enum   { type_args, .. } => type_args,
+            Self::Struct { type_args, .. } => type_args,
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
     pub fn type_param_decls(&self) -> DeriveResult<&Punctuated<GenericParam, Comma>> {
         Ok(match self {
             Self::Enum   { type_param_decls, .. } => type_param_decls,
@@ -259,25 +412,420 @@ impl InputType {
         })
     }
 
+    /// The predicates of the input type's `#[delta(bound = "...")]`, if any.
+    pub fn bound(&self) -> DeriveResult<Option<&[WherePredicate]>> {
+        Ok(match self {
+            Self::Enum   { bound, .. } => bound.as_deref(),
+            Self::Struct { bound, .. } => bound.as_deref(),
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
+    /// Returns true iff. the input type is marked with `#[delta(atomic)]`.
+    pub fn is_atomic(&self) -> DeriveResult<bool> {
+        Ok(match self {
+            Self::Enum   { atomic, .. } => *atomic,
+            Self::Struct { atomic, .. } => *atomic,
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
+    /// Returns true iff. the input type is marked with `#[delta(transparent)]`.
+    pub fn is_transparent(&self) -> DeriveResult<bool> {
+        Ok(match self {
+            Self::Enum   { transparent, .. } => *transparent,
+            Self::Struct { transparent, .. } => *transparent,
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
+    /// Returns true iff. the input type is marked with
+    /// `#[delta(skip_unchanged)]`.
+    pub fn is_skip_unchanged(&self) -> DeriveResult<bool> {
+        Ok(match self {
+            Self::Enum   { skip_unchanged, .. } => *skip_unchanged,
+            Self::Struct { skip_unchanged, .. } => *skip_unchanged,
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
+    /// The only field of the input struct that isn't ignored, if any.
+    pub fn transparent_field(&self) -> DeriveResult<Option<&FieldDesc>> {
+        let fields: &[FieldDesc] = match self {
+            Self::Struct { fields, .. } => fields,
+            _ => return Ok(None),
+        };
+        let mut diffed = fields.iter().filter(|field| !field.ignore_field());
+        match (diffed.next(), diffed.next()) {
+            (Some(field), None) => Ok(Some(field)),
+            _ => Ok(None),
+        }
+    }
+
+    /// The foreign type of the input type's `#[delta(remote = "...")]`.
+    pub fn remote(&self) -> DeriveResult<Option<&Path>> {
+        Ok(match self {
+            Self::Enum   { remote, .. } => remote.as_ref(),
+            Self::Struct { remote, .. } => remote.as_ref(),
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
+    /// The visibility of the generated delta type.
+    pub fn delta_vis(&self) -> DeriveResult<&Visibility> {
+        Ok(match self {
+            Self::Enum   { delta_vis, .. } => delta_vis,
+            Self::Struct { delta_vis, .. } => delta_vis,
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
+    fn delta_derives(&self) -> DeriveResult<&[Path]> {
+        Ok(match self {
+            Self::Enum   { delta_derives, .. } => delta_derives,
+            Self::Struct { delta_derives, .. } => delta_derives,
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
+    /// Returns true iff. the input type's `#[delta(derive(...))]` includes
+    /// the trait named `name`, e.g. `Eq` or `std::cmp::Eq`.
+    pub fn delta_derives_trait(&self, name: &str) -> DeriveResult<bool> {
+        Ok(self.delta_derives()?.iter().any(|path| {
+            path.segments.last().is_some_and(|segment| segment.ident == name)
+        }))
+    }
+
+    /// Return the attributes that the input type's `#[delta(derive(...))]`
+    /// and `#[delta(serde(...))]` add to the delta type, leaving out the
+    /// traits that are `derived` regardless, since deriving them twice
+    /// wouldn't compile.  `Debug` is left out too, since it's implemented
+    /// by hand, and so are `Eq` and `Hash`, since the std derives don't
+    /// bound the field deltas of a generic type; they're implemented by
+    /// hand instead.
+    pub fn delta_attrs(&self, derived: &[&str]) -> DeriveResult<TokenStream2> {
+        let delta_serde: &[MetaList] = match self {
+            Self::Enum   { delta_serde, .. } => delta_serde,
+            Self::Struct { delta_serde, .. } => delta_serde,
+            Self::Union => panic!("Unions are not supported."),
+        };
+        let derives: Vec<&Path> = self.delta_derives()?.iter()
+            .filter(|path| {
+                let name = path.segments.last().map(|segment| &segment.ident);
+                !derived.iter().chain(&["Debug", "Eq", "Hash"])
+                    .any(|derived| name.is_some_and(|name| name == derived))
+            })
+            .collect();
+        let derive_attr: TokenStream2 = if derives.is_empty() {
+            quote! {}
+        } else {
+            quote! { #[derive(#(#derives),*)] }
+        };
+        Ok(quote! { #derive_attr #( #[#delta_serde] )* })
+    }
+
+    /// Return a `#[serde(bound = "...")]` attribute that forwards the input
+    /// type's `#[delta(bound = "...")]` to the delta type.
+    pub fn serde_bound_attr(&self) -> DeriveResult<TokenStream2> {
+        let erased: Vec<Ident2> = self.erased_lifetimes()?;
+        Ok(match self.bound()? {
+            Some(bound) => {
+                let bound: Vec<TokenStream2> = bound.iter()
+                    .filter_map(|pred| delta_predicate(pred, &erased))
+                    .collect();
+                let bound: String = quote! { #(#bound),* }.to_string();
+                quote! { #[serde(bound = #bound)] }
+            },
+            None => quote! {},
+        })
+    }
+
+    /// Return the declaration of `type_param` for a generated item: its
+    /// own bounds, plus the `inferred` bounds the item requires unless the
+    /// input type has a `#[delta(bound = "...")]` that replaces them.
+    pub fn type_param_decl(
+        &self,
+        type_param: &TypeParam,
+        inferred: TokenStream2,
+    ) -> TokenStream2 {
+        let T: &Ident2 = &type_param.ident;
+        let bounds: Vec<TokenStream2> = type_param.bounds.iter()
+            .map(|trait_bound| quote! { #trait_bound })
+            .collect();
+        match self.bound() {
+            Ok(Some(_)) => quote! { #T: #(#bounds +)* },
+            _ => quote! { #T: #inferred #(+ #bounds)* },
+        }
+    }
+
+    /// The lifetime parameters of the input type.  The delta type doesn't
+    /// have them, since a delta owns its data and so can outlive the
+    /// values it was calculated from; in the types of its fields they're
+    /// replaced by `'static`.  The exception is an atomic type, whose delta
+    /// type is an alias of `deltoid::AtomicDelta<Self>`.
+    pub fn erased_lifetimes(&self) -> DeriveResult<Vec<Ident2>> {
+        if self.is_atomic()? { return Ok(vec![]) }
+        Ok(self.type_param_decls()?.iter()
+            .filter_map(|param| match param {
+                GenericParam::Lifetime(lifetime_def) =>
+                    Some(lifetime_def.lifetime.ident.clone()),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Replace the `erased_lifetimes()` in `tokens` by `'static`.
+    pub fn erase_lifetimes(&self, tokens: TokenStream2)
+                           -> DeriveResult<TokenStream2> {
+        Ok(erase_lifetimes(tokens, &self.erased_lifetimes()?))
+    }
+
+    /// Return the generic parameter declarations for a generated item of
+    /// the delta type, where `inferred` is the bound the item requires of
+    /// the type parameters.  Unlike `type_param_decls()`, these don't
+    /// include the `erased_lifetimes()`, nor bounds on them.
+    pub fn delta_type_param_decls(&self, inferred: TokenStream2)
+                                  -> DeriveResult<Vec<TokenStream2>> {
+        let erased: Vec<Ident2> = self.erased_lifetimes()?;
+        Ok(self.type_param_decls()?.iter()
+            .filter_map(|type_param_decl| match type_param_decl {
+                GenericParam::Lifetime(lifetime_def)
+                    if erased.contains(&lifetime_def.lifetime.ident) => None,
+                GenericParam::Lifetime(lifetime_def) =>
+                    Some(quote! { #lifetime_def }),
+                GenericParam::Const(const_param) =>
+                    Some(quote! { #const_param }),
+                GenericParam::Type(type_param) => {
+                    let mut type_param: TypeParam = type_param.clone();
+                    type_param.bounds = type_param.bounds.into_iter()
+                        .filter(|bound| !is_erased_bound(bound, &erased))
+                        .collect();
+                    let decl = self.type_param_decl(&type_param, inferred.clone());
+                    Some(erase_lifetimes(decl, &erased))
+                },
+            })
+            .collect())
+    }
+
+    /// Return the generic arguments of the delta type, i.e. `type_args()`
+    /// without the `erased_lifetimes()`.
+    pub fn delta_type_args(&self)
+                           -> DeriveResult<Punctuated<GenericArgument, Comma>> {
+        let erased: Vec<Ident2> = self.erased_lifetimes()?;
+        Ok(self.type_args()?.iter()
+            .filter(|type_arg| !matches!(
+                type_arg,
+                GenericArgument::Lifetime(lifetime)
+                    if erased.contains(&lifetime.ident)
+            ))
+            .cloned()
+            .collect())
+    }
+
+    /// Returns true iff. `ty` uses one of the input type's type parameters.
+    pub fn uses_type_param(&self, ty: &Type) -> DeriveResult<bool> {
+        Ok(self.type_params()?.iter()
+            .any(|T| mentions_ident(quote! { #ty }, T)))
+    }
+
+    /// Return the where-predicates for a generated item that requires
+    /// `bounds` of the input type's fields: the predicates of the input
+    /// type's `WhereClause`, followed by `FieldType: #bounds` for each
+    /// field type that uses a type parameter, except for ignored fields and
+    /// fields with a `#[delta(with = "...")]` or `#[delta(atomic)]`.  A
+    /// `#[delta(bound = "...")]` on a field replaces the latter for that
+    /// field, and one on the input type replaces both.
+    ///
+    /// The bounds on the type parameters themselves don't suffice when a
+    /// type parameter `T` is only used as part of a field type such as
+    /// `Vec<T>`, or when its bounds are only given in the where-clause.
+    /// Bare type parameters are left to the type parameter bounds, and
+    /// field types that mention the input type itself are skipped so that
+    /// recursive types don't produce cyclic predicates.
+    ///
+    /// A field that borrows with one of the `erased_lifetimes()` is a
+    /// `Cow<'a, B>`, so the predicate is on `B` instead, with the bounds
+    /// that the impls for `Cow` require.  A type parameter `T` that it
+    /// uses also requires `T: 'static`, so that the field type of the
+    /// delta type is well-formed.  For the delta type, the predicates have
+    /// the lifetimes replaced by `'static` and leave out bounds on them.
+    pub fn where_predicates(&self, bounds: TokenStream2)
+                            -> DeriveResult<Vec<TokenStream2>> {
+        self.predicates(bounds, false)
+    }
+
+    /// Like `where_predicates()`, but for a generated item of the delta
+    /// type, which doesn't have the `erased_lifetimes()`.
+    pub fn delta_where_predicates(&self, bounds: TokenStream2)
+                                  -> DeriveResult<Vec<TokenStream2>> {
+        self.predicates(bounds, true)
+    }
+
+    /// Return the where-predicates for the `ToDynDelta` impl of the delta
+    /// type: those of `delta_where_predicates()`, followed by
+    /// `FieldDelta: deltoid::ToDynDelta` for the delta type of each field
+    /// whose type uses a type parameter, since `Core::Delta` doesn't
+    /// require `ToDynDelta`.  As with `where_predicates()`, field types that
+    /// mention the input type itself are skipped.
+    pub fn dyn_delta_predicates(&self) -> DeriveResult<Vec<TokenStream2>> {
+        let type_name: &Ident2 = self.type_name()?;
+        let mut predicates: Vec<TokenStream2> =
+            self.delta_where_predicates(quote! { deltoid::Core })?;
+        let fields: Vec<&FieldDesc> = match self {
+            Self::Enum { enum_variants, .. } => enum_variants.iter()
+                .flat_map(|enum_variant| enum_variant.fields())
+                .collect(),
+            Self::Struct { fields, .. } => fields.iter().collect(),
+            Self::Union => panic!("Unions are not supported."),
+        };
+        let mut seen: Vec<String> = vec![];
+        for field in fields {
+            let ty: &Type = field.type_ref();
+            if field.ignore_field()
+                || !self.uses_type_param(ty)?
+                || mentions_ident(quote! { #ty }, type_name)
+            {
+                continue;
+            }
+            let delta_type: TokenStream2 =
+                self.erase_lifetimes(field.delta_type())?;
+            if seen.contains(&delta_type.to_string()) { continue }
+            seen.push(delta_type.to_string());
+            predicates.push(quote! { #delta_type: deltoid::ToDynDelta });
+        }
+        Ok(predicates)
+    }
+
+    fn predicates(&self, bounds: TokenStream2, for_delta_type: bool)
+                  -> DeriveResult<Vec<TokenStream2>> {
+        let type_name: &Ident2 = self.type_name()?;
+        let type_params: &Punctuated<Ident2, Comma> = self.type_params()?;
+        let erased: Vec<Ident2> = self.erased_lifetimes()?;
+        let fields: Vec<&FieldDesc> = match self {
+            Self::Enum { enum_variants, .. } => enum_variants.iter()
+                .flat_map(|enum_variant| enum_variant.fields())
+                .collect(),
+            Self::Struct { fields, .. } => fields.iter().collect(),
+            Self::Union => panic!("Unions are not supported."),
+        };
+        let predicate = |where_predicate: &WherePredicate| if for_delta_type {
+            delta_predicate(where_predicate, &erased)
+        } else {
+            Some(quote! { #where_predicate })
+        };
+        let mut predicates: Vec<TokenStream2> = self.where_clause()?
+            .predicates.iter()
+            .filter_map(predicate)
+            .collect();
+        for T in type_params.iter() {
+            let borrowed_by_field = fields.iter().any(|field| {
+                let ty: &Type = field.type_ref();
+                let tokens: TokenStream2 = quote! { #ty };
+                mentions_ident(tokens.clone(), T)
+                    && erase_lifetimes(tokens.clone(), &erased).to_string()
+                        != tokens.to_string()
+            });
+            if borrowed_by_field {
+                predicates.push(quote! { #T: 'static });
+            }
+        }
+        if let Some(bound) = self.bound()? {
+            predicates.extend(bound.iter().filter_map(predicate));
+            return Ok(predicates);
+        } else if self.is_atomic()? {
+            // NOTE: the fields of an atomic type aren't diffed
+            return Ok(predicates);
+        }
+        let mut seen: Vec<String> = vec![];
+        for field in fields.into_iter() {
+            if let Some(bound) = field.bound() {
+                predicates.extend(bound.iter().filter_map(predicate));
+                continue;
+            } else if field.ignore_field() || field.with_module().is_some() {
+                continue;
+            }
+            let ty: &Type = field.type_ref();
+            let tokens: TokenStream2 = quote! { #ty };
+            let is_type_param = match ty {
+                Type::Path(TypePath { qself: None, path }) => path.get_ident()
+                    .is_some_and(|ident| type_params.iter().any(|T| ident == T)),
+                _ => false,
+            };
+            let uses_type_param = type_params.iter()
+                .any(|T| mentions_ident(tokens.clone(), T));
+            if is_type_param
+                || !uses_type_param
+                || mentions_ident(tokens.clone(), type_name)
+                || seen.contains(&tokens.to_string())
+            {
+                continue;
+            }
+            seen.push(tokens.to_string());
+            match owned_cow_type(ty, &erased) {
+                Some(owned) if !for_delta_type => {
+                    // NOTE: bound the owned type rather than the `Cow`, so
+                    //       that `<Cow<'a, B> as Core>::Delta` is normalized
+                    //       with the impl for `Cow`, to the delta type of the
+                    //       delta type's `Cow<'static, B>`
+                    predicates.push(quote! {
+                        #owned: #bounds + deltoid::FromDelta
+                            + std::clone::Clone
+                            + std::fmt::Debug
+                            + std::cmp::PartialEq
+                            + serde::de::DeserializeOwned
+                            + serde::Serialize
+                    });
+                },
+                _ => predicates.push(if for_delta_type {
+                    erase_lifetimes(quote! { #ty: #bounds }, &erased)
+                } else {
+                    quote! { #ty: #bounds }
+                }),
+            }
+        }
+        Ok(predicates)
+    }
+
     pub fn define_delta_type(&self) -> DeriveResult<TokenStream2> {
+        if self.is_atomic()? { return atomic::define_delta_type(self) }
+        if self.is_transparent()? { return transparent::define_delta_type(self) }
         Ok(match self {
-            Self::Struct { .. } => structs::define_delta_struct(self)?,
-            Self::Enum   { .. } => enums::define_delta_enum(self)?,
+            Self::Struct { .. } => {
+                let delta_struct = structs::define_delta_struct(self)?;
+                let impls_Eq_Hash = structs::define_Eq_Hash_impls(self)?;
+                let accessors = structs::define_accessors(self)?;
+                let delta_struct = self.erase_lifetimes(delta_struct)?;
+                let impls_Eq_Hash = self.erase_lifetimes(impls_Eq_Hash)?;
+                // NOTE: the accessors erase the lifetimes themselves, since
+                //       the builder setters still take borrowed values
+                quote! { #delta_struct #impls_Eq_Hash #accessors }
+            },
+            Self::Enum   { .. } => {
+                let delta_enum = enums::define_delta_enum(self)?;
+                let impls_Eq_Hash = enums::define_Eq_Hash_impls(self)?;
+                self.erase_lifetimes(quote! { #delta_enum #impls_Eq_Hash })?
+            },
             Self::Union => panic!("Unions are not supported."),
         })
     }
 
     #[allow(non_snake_case)]
     pub fn define_Debug_impl(&self) -> DeriveResult<TokenStream2> {
+        if self.is_atomic()? { return atomic::define_Debug_impl(self) }
+        if self.is_transparent()? { return transparent::define_Debug_impl(self) }
         Ok(match self {
-            Self::Struct { .. } => structs::define_Debug_impl(self)?,
-            Self::Enum   { .. } => enums::define_Debug_impl(self)?,
+            Self::Struct { .. } =>
+                self.erase_lifetimes(structs::define_Debug_impl(self)?)?,
+            Self::Enum   { .. } =>
+                self.erase_lifetimes(enums::define_Debug_impl(self)?)?,
             Self::Union => panic!("Unions are not supported."),
         })
     }
 
     #[allow(non_snake_case)]
     pub fn define_Core_impl(&self) -> DeriveResult<TokenStream2> {
+        if self.is_atomic()? { return atomic::define_Core_impl(self) }
+        if self.is_transparent()? { return transparent::define_Core_impl(self) }
         Ok(match self {
             Self::Struct { .. } => structs::define_Core_impl(self)?,
             Self::Enum   { .. } => enums::define_Core_impl(self)?,
@@ -287,6 +835,8 @@ impl InputType {
 
     #[allow(non_snake_case)]
     pub fn define_Apply_impl(&self) -> DeriveResult<TokenStream2> {
+        if self.is_atomic()? { return atomic::define_Apply_impl(self) }
+        if self.is_transparent()? { return transparent::define_Apply_impl(self) }
         Ok(match self {
             Self::Struct { .. } => structs::define_Apply_impl(self)?,
             Self::Enum   { .. } => enums::define_Apply_impl(self)?,
@@ -296,6 +846,8 @@ impl InputType {
 
     #[allow(non_snake_case)]
     pub fn define_Delta_impl(&self) -> DeriveResult<TokenStream2> {
+        if self.is_atomic()? { return atomic::define_Delta_impl(self) }
+        if self.is_transparent()? { return transparent::define_Delta_impl(self) }
         Ok(match self {
             Self::Struct { .. } => structs::define_Delta_impl(self)?,
             Self::Enum   { .. } => enums::define_Delta_impl(self)?,
@@ -305,6 +857,8 @@ impl InputType {
 
     #[allow(non_snake_case)]
     pub fn define_FromDelta_impl(&self) -> DeriveResult<TokenStream2> {
+        if self.is_atomic()? { return atomic::define_FromDelta_impl(self) }
+        if self.is_transparent()? { return transparent::define_FromDelta_impl(self) }
         Ok(match self {
             Self::Struct { .. } => structs::define_FromDelta_impl(self)?,
             Self::Enum   { .. } => enums::define_FromDelta_impl(self)?,
@@ -314,12 +868,34 @@ impl InputType {
 
     #[allow(non_snake_case)]
     pub fn define_IntoDelta_impl(&self) -> DeriveResult<TokenStream2> {
+        if self.is_atomic()? { return atomic::define_IntoDelta_impl(self) }
+        if self.is_transparent()? { return transparent::define_IntoDelta_impl(self) }
         Ok(match self {
             Self::Struct { .. } => structs::define_IntoDelta_impl(self)?,
             Self::Enum   { .. } => enums::define_IntoDelta_impl(self)?,
             Self::Union => panic!("Unions are not supported."),
         })
     }
+
+    #[allow(non_snake_case)]
+    pub fn define_ToDynDelta_impl(&self) -> DeriveResult<TokenStream2> {
+        if self.is_atomic()? { return atomic::define_ToDynDelta_impl(self) }
+        if self.is_transparent()? { return transparent::define_ToDynDelta_impl(self) }
+        Ok(match self {
+            Self::Struct { .. } =>
+                self.erase_lifetimes(structs::define_ToDynDelta_impl(self)?)?,
+            Self::Enum   { .. } =>
+                self.erase_lifetimes(enums::define_ToDynDelta_impl(self)?)?,
+            Self::Union => panic!("Unions are not supported."),
+        })
+    }
+
+    pub fn define_remote_impls(&self) -> DeriveResult<TokenStream2> {
+        match self.remote()? {
+            Some(_) => remote::define_remote_impls(self),
+            None => Ok(quote! {}),
+        }
+    }
 }
 
 
@@ -372,12 +948,21 @@ pub enum FieldDesc {
         name: Ident2,
         ty: Type,
         ignore_field: bool,
+        bound: Option<Vec<WherePredicate>>,
+        with: Option<Path>,
+        atomic: bool,
+        remote: Option<Type>,
+        flatten: bool,
     },
     /// A field that's part of a tuple struct
     Positional {
         position: Literal2,
         ty: Type,
         ignore_field: bool,
+        bound: Option<Vec<WherePredicate>>,
+        with: Option<Path>,
+        atomic: bool,
+        remote: Option<Type>,
     }
 }
 
@@ -419,13 +1004,311 @@ impl FieldDesc {
         }
     }
 
+    /// The predicates of the field's `#[delta(bound = "...")]`, if any.
+    pub fn bound(&self) -> Option<&[WherePredicate]> {
+        match self {
+            Self::Named      { bound, .. } => bound.as_deref(),
+            Self::Positional { bound, .. } => bound.as_deref(),
+        }
+    }
+
+    /// The module of the field's `#[delta(with = "...")]`, if any.
+    pub fn with(&self) -> Option<&Path> {
+        match self {
+            Self::Named      { with, .. } => with.as_ref(),
+            Self::Positional { with, .. } => with.as_ref(),
+        }
+    }
+
+    /// Returns true iff. the field was marked with `#[delta(atomic)]`.
+    pub fn is_atomic(&self) -> bool {
+        match self {
+            Self::Named      { atomic, .. } => *atomic,
+            Self::Positional { atomic, .. } => *atomic,
+        }
+    }
+
+    /// Returns true iff. the field was marked with `#[delta(flatten)]`.
+    pub fn is_flatten(&self) -> bool {
+        match self {
+            Self::Named { flatten, .. } => *flatten,
+            Self::Positional { .. } => false,
+        }
+    }
+
+    /// Returns the mirror type of the field's `#[delta(remote = "...")]`.
+    pub fn remote(&self) -> Option<&Type> {
+        match self {
+            Self::Named      { remote, .. } => remote.as_ref(),
+            Self::Positional { remote, .. } => remote.as_ref(),
+        }
+    }
+
+    /// The module that defines the delta logic for the field, if it isn't
+    /// the field type's own impls: either the module of the field's
+    /// `#[delta(with = "...")]`, the mirror type of the field's
+    /// `#[delta(remote = "...")]`, or `deltoid::atomic` for
+    /// `#[delta(atomic)]`.
+    pub fn with_module(&self) -> Option<TokenStream2> {
+        match (self.with(), self.remote(), self.is_atomic()) {
+            (Some(with), _, _) => Some(quote! { #with }),
+            (None, Some(remote), _) => Some(quote! { <#remote> }),
+            (None, None, true) => Some(quote! { deltoid::atomic }),
+            (None, None, false) => None,
+        }
+    }
+
+    /// Return the delta type of the field i.e. the `Delta` type of the
+    /// field's `#[delta(with = "...")]` module, the delta type of the
+    /// mirror type of its `#[delta(remote = "...")]`, `deltoid::AtomicDelta`
+    /// for `#[delta(atomic)]`, or else `<FieldType as deltoid::Core>::Delta`.
+    pub fn delta_type(&self) -> TokenStream2 {
+        let ty: &Type = self.type_ref();
+        match (self.with(), self.remote()) {
+            (Some(with), _) => quote! { #with::Delta },
+            (None, Some(remote)) => quote! { <#remote as deltoid::Core>::Delta },
+            (None, None) if self.is_atomic() => quote! { deltoid::AtomicDelta<#ty> },
+            (None, None) => quote! { <#ty as deltoid::Core>::Delta },
+        }
+    }
+
+    /// Return an expression that applies `delta` to the field `value`
+    /// according to the `policy` in scope.
+    pub fn apply_tokens(&self, value: TokenStream2, delta: TokenStream2)
+                        -> TokenStream2 {
+        match self.with_module() {
+            Some(with) => quote! { #with::apply_with(&#value, #delta, policy) },
+            None => quote! { #value.apply_with(#delta, policy) },
+        }
+    }
+
+    /// Return a statement that pushes every problem with applying the
+    /// `delta` reference to the field `value` onto the `errors` in scope.
+    pub fn validate_tokens(&self, value: TokenStream2, delta: TokenStream2)
+                           -> TokenStream2 {
+        match self.with_module() {
+            Some(with) => quote! { #with::validate_into(&#value, #delta, errors); },
+            None => quote! { #value.validate_into(#delta, errors); },
+        }
+    }
+
+    /// Return statements that push every problem with converting the field
+    /// delta `delta` to a field value onto the `errors` in scope, scoped to
+    /// `segments`.  Unless the field is flattened, `delta` is a reference
+    /// to an `Option` of the field delta.
+    pub fn validate_from_delta_tokens(
+        &self,
+        delta: TokenStream2,
+        segments: &[TokenStream2],
+    ) -> TokenStream2 {
+        let ftype: &Type = self.type_ref();
+        let validate_from_delta: TokenStream2 =
+            self.validate_value_from_delta_path();
+        let validate: TokenStream2 = if self.is_flatten() {
+            quote! { #validate_from_delta(#delta, errors); }
+        } else {
+            quote! {
+                match #delta {
+                    Some(d) => #validate_from_delta(d, errors),
+                    None => errors.push(deltoid::DeltaError::ExpectedValue {
+                        type_name: stringify!(#ftype).to_string(),
+                        file: file!().to_string(),
+                        line: line!(),
+                        column: column!(),
+                    }),
+                }
+            }
+        };
+        quote! {
+            let start: usize = errors.len();
+            #validate
+            #( deltoid::DeltaError::scope_all(errors, start, &#segments); )*
+        }
+    }
+
+    /// Return an expression that calculates the delta between the field
+    /// values `lhs` and `rhs`.
+    pub fn delta_tokens(&self, lhs: TokenStream2, rhs: TokenStream2)
+                        -> TokenStream2 {
+        match self.with_module() {
+            Some(with) => quote! { #with::delta(&#lhs, &#rhs) },
+            None => quote! { #lhs.delta(&#rhs) },
+        }
+    }
+
+    /// Return the path of a function that converts a field delta to a
+    /// field value.
+    pub fn value_from_delta_path(&self) -> TokenStream2 {
+        let ty: &Type = self.type_ref();
+        match self.with_module() {
+            Some(with) => quote! { #with::from_delta },
+            None => quote! { <#ty>::from_delta },
+        }
+    }
+
+    /// Return the path of a function that pushes every problem with
+    /// converting a field delta to a field value onto an `errors` `Vec`.
+    pub fn validate_value_from_delta_path(&self) -> TokenStream2 {
+        let ty: &Type = self.type_ref();
+        match self.with_module() {
+            Some(with) => quote! { #with::validate_from_delta },
+            None => quote! { <#ty as deltoid::FromDelta>::validate_from_delta },
+        }
+    }
+
+    /// Return an expression that converts the field `value` to a delta.
+    pub fn delta_from_value_tokens(&self, value: TokenStream2) -> TokenStream2 {
+        match self.with_module() {
+            Some(with) => quote! { #with::into_delta(#value) },
+            None => quote! { #value.into_delta() },
+        }
+    }
+
+    /// Return the `#[serde(...)]` attributes of the field of the delta type,
+    /// which forward the field's `#[delta(bound = "...")]` and
+    /// `#[delta(flatten)]`.  If `skip_unchanged`, an unchanged named field
+    /// is left out when serializing, and is `None` when it's missing while
+    /// deserializing.
+    pub fn serde_attrs(&self, skip_unchanged: bool) -> TokenStream2 {
+        let bound_attr: TokenStream2 = serde_bound_attr(self.bound());
+        if self.is_flatten() {
+            quote! { #bound_attr #[serde(flatten)] }
+        } else if skip_unchanged && self.is_named() && !self.ignore_field() {
+            quote! {
+                #bound_attr
+                // NOTE: `default = "..."` rather than `default`, which
+                //       would make serde bound every type param by `Default`
+                #[serde(
+                    default = "Option::default",
+                    skip_serializing_if = "Option::is_none",
+                )]
+            }
+        } else {
+            bound_attr
+        }
+    }
+
     /// Return the tokens for the type of `self`.
     pub fn type_tokens(&self) -> TokenStream2 {
         let ty: &Type = self.type_ref();
         if self.ignore_field() {
             quote! { std::marker::PhantomData<#ty> }
+        } else if self.is_flatten() {
+            // NOTE: a flattened delta is always present, since serde can't
+            //       tell an absent one apart from one without any changes
+            self.delta_type()
         } else {
-            quote! { Option<<#ty as deltoid::Core>::Delta> }
+            let delta_type: TokenStream2 = self.delta_type();
+            quote! { Option<#delta_type> }
         }
     }
 }
+
+
+/// Returns true iff. `tokens` contains the identifier `ident`.
+fn mentions_ident(tokens: TokenStream2, ident: &Ident2) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        proc_macro2::TokenTree::Ident(i) => &i == ident,
+        proc_macro2::TokenTree::Group(g) => mentions_ident(g.stream(), ident),
+        _ => false,
+    })
+}
+
+
+/// Replace the `lifetimes` in `tokens` by `'static`.
+fn erase_lifetimes(tokens: TokenStream2, lifetimes: &[Ident2]) -> TokenStream2 {
+    replace_lifetimes(tokens, lifetimes, "static")
+}
+
+/// Replace the `lifetimes` in `tokens` by the lifetime `'#replacement`.
+fn replace_lifetimes(
+    tokens: TokenStream2,
+    lifetimes: &[Ident2],
+    replacement: &str,
+) -> TokenStream2 {
+    let mut erased: Vec<TokenTree2> = vec![];
+    let mut after_quote = false;
+    for tt in tokens {
+        let tt: TokenTree2 = match tt {
+            TokenTree2::Ident(ident)
+                if after_quote && lifetimes.contains(&ident) =>
+                TokenTree2::Ident(Ident2::new(replacement, ident.span())),
+            TokenTree2::Group(group) => {
+                let stream =
+                    replace_lifetimes(group.stream(), lifetimes, replacement);
+                let mut erased_group = Group2::new(group.delimiter(), stream);
+                erased_group.set_span(group.span());
+                TokenTree2::Group(erased_group)
+            },
+            tt => tt,
+        };
+        after_quote = matches!(
+            &tt,
+            TokenTree2::Punct(punct) if punct.as_char() == '\''
+        );
+        erased.push(tt);
+    }
+    erased.into_iter().collect()
+}
+
+/// If `ty` is a `Cow<'_, B>` where `B` doesn't borrow with the `lifetimes`,
+/// return `B`.  The delta type of the `Cow` is then `CowDelta<B>` for
+/// every lifetime, so the delta type can use `Cow<'static, B>` instead.
+fn owned_cow_type<'t>(ty: &'t Type, lifetimes: &[Ident2]) -> Option<&'t Type> {
+    let segment: &PathSegment = match ty {
+        Type::Path(TypePath { qself: None, path }) => path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Cow" { return None }
+    let args: Vec<&GenericArgument> = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter().collect(),
+        _ => return None,
+    };
+    match args.as_slice() {
+        [GenericArgument::Lifetime(_), GenericArgument::Type(owned)] => {
+            let tokens: TokenStream2 = quote! { #owned };
+            let borrows: bool = erase_lifetimes(tokens.clone(), lifetimes)
+                .to_string() != tokens.to_string();
+            if borrows { None } else { Some(owned) }
+        },
+        _ => None,
+    }
+}
+
+/// Returns true iff. `bound` is one of the erased `lifetimes`.
+fn is_erased_bound(bound: &TypeParamBound, lifetimes: &[Ident2]) -> bool {
+    matches!(
+        bound,
+        TypeParamBound::Lifetime(lifetime) if lifetimes.contains(&lifetime.ident)
+    )
+}
+
+/// Return `where_predicate` for a generated item of the delta type, in
+/// which the erased `lifetimes` are `'static`.  Predicates on lifetimes,
+/// and bounds that are erased lifetimes, are left out.
+fn delta_predicate(where_predicate: &WherePredicate, lifetimes: &[Ident2])
+                   -> Option<TokenStream2> {
+    match where_predicate {
+        WherePredicate::Lifetime(_) if !lifetimes.is_empty() => None,
+        WherePredicate::Type(predicate_type) => {
+            let mut predicate_type: PredicateType = predicate_type.clone();
+            predicate_type.bounds = predicate_type.bounds.into_iter()
+                .filter(|bound| !is_erased_bound(bound, lifetimes))
+                .collect();
+            if predicate_type.bounds.is_empty() { return None }
+            Some(erase_lifetimes(quote! { #predicate_type }, lifetimes))
+        },
+        where_predicate => Some(quote! { #where_predicate }),
+    }
+}
+
+/// Return a `#[serde(bound = "...")]` attribute for `bound`, if any.
+fn serde_bound_attr(bound: Option<&[WherePredicate]>) -> TokenStream2 {
+    match bound {
+        Some(bound) => {
+            let bound: String = quote! { #(#bound),* }.to_string();
+            quote! { #[serde(bound = #bound)] }
+        },
+        None => quote! {},
+    }
+}
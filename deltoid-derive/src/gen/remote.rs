@@ -0,0 +1,229 @@
+//! Code generation for types marked with `#[delta(remote = "...")]`
+#![allow(non_snake_case)]
+
+use crate::DeriveResult;
+use crate::gen::{EnumVariant, FieldDesc, InputType, StructVariant};
+use proc_macro2::{Ident as Ident2, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::*;
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+
+/// Define conversions between the input type and the remote type it
+/// mirrors, along with the functions that `#[delta(remote = "...")]`
+/// fields use to route their delta logic through the input type's impls.
+pub(crate) fn define_remote_impls(input: &InputType) -> DeriveResult<TokenStream2> {
+    let remote: &Path = match input.remote()? {
+        Some(remote) => remote,
+        None => return bug_detected!(),
+    };
+    let type_name: &Ident2 = input.type_name()?;
+    let in_type_param_decls: &Punctuated<GenericParam, Comma> =
+        input.type_param_decls()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
+    let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
+        .map(|type_param_decl| match type_param_decl {
+            GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
+            GenericParam::Const(const_param)     => quote! { #const_param  },
+            GenericParam::Type(type_param) => input.type_param_decl(
+                type_param,
+                quote! { std::clone::Clone },
+            ),
+        })
+        .collect();
+    let predicates: Vec<TokenStream2> =
+        input.where_predicates(quote! { std::clone::Clone })?;
+    let where_clause = quote! { where #(#predicates),* };
+    let in_where_clause: &WhereClause = input.where_clause()?;
+    let (from_remote, into_remote): (TokenStream2, TokenStream2) = match input {
+        InputType::Struct { .. } => convert_struct(input)?,
+        InputType::Enum { .. } => convert_enum(input, remote)?,
+        InputType::Union => panic!("Unions are not supported."),
+    };
+    Ok(quote! {
+        impl<'__remote, #(#type_param_decls),*>
+            std::convert::From<&'__remote #remote<#type_args>>
+            for #type_name<#type_args>
+            #where_clause
+        {
+            #[allow(unused)]
+            fn from(value: &'__remote #remote<#type_args>) -> Self {
+                #from_remote
+            }
+        }
+
+        impl<#(#type_param_decls),*>
+            std::convert::From<#type_name<#type_args>>
+            for #remote<#type_args>
+            #where_clause
+        {
+            #[allow(unused)]
+            fn from(value: #type_name<#type_args>) -> Self {
+                #into_remote
+            }
+        }
+
+        impl<#in_type_param_decls> #type_name<#type_args> #in_where_clause {
+            pub fn apply(
+                value: &#remote<#type_args>,
+                delta: <Self as deltoid::Core>::Delta,
+            ) -> deltoid::DeltaResult<#remote<#type_args>>
+            where
+                Self: deltoid::Apply
+                    + for<'__remote> std::convert::From<&'__remote #remote<#type_args>>
+                    + std::convert::Into<#remote<#type_args>>,
+            {
+                let value: Self = Self::from(value);
+                Ok(deltoid::Apply::apply(&value, delta)?.into())
+            }
+
+            pub fn apply_with(
+                value: &#remote<#type_args>,
+                delta: <Self as deltoid::Core>::Delta,
+                policy: &deltoid::ApplyPolicy,
+            ) -> deltoid::DeltaResult<#remote<#type_args>>
+            where
+                Self: deltoid::Apply
+                    + for<'__remote> std::convert::From<&'__remote #remote<#type_args>>
+                    + std::convert::Into<#remote<#type_args>>,
+            {
+                let value: Self = Self::from(value);
+                Ok(deltoid::Apply::apply_with(&value, delta, policy)?.into())
+            }
+
+            pub fn validate_into(
+                value: &#remote<#type_args>,
+                delta: &<Self as deltoid::Core>::Delta,
+                errors: &mut Vec<deltoid::DeltaError>,
+            )
+            where
+                Self: deltoid::Apply
+                    + for<'__remote> std::convert::From<&'__remote #remote<#type_args>>,
+            {
+                deltoid::Apply::validate_into(&Self::from(value), delta, errors)
+            }
+
+            pub fn delta(
+                lhs: &#remote<#type_args>,
+                rhs: &#remote<#type_args>,
+            ) -> deltoid::DeltaResult<<Self as deltoid::Core>::Delta>
+            where
+                Self: deltoid::Delta
+                    + for<'__remote> std::convert::From<&'__remote #remote<#type_args>>,
+            {
+                deltoid::Delta::delta(&Self::from(lhs), &Self::from(rhs))
+            }
+
+            pub fn from_delta(
+                delta: <Self as deltoid::Core>::Delta,
+            ) -> deltoid::DeltaResult<#remote<#type_args>>
+            where
+                Self: deltoid::FromDelta + std::convert::Into<#remote<#type_args>>,
+            {
+                Ok(<Self as deltoid::FromDelta>::from_delta(delta)?.into())
+            }
+
+            pub fn validate_from_delta(
+                delta: &<Self as deltoid::Core>::Delta,
+                errors: &mut Vec<deltoid::DeltaError>,
+            )
+            where
+                Self: deltoid::FromDelta,
+            {
+                <Self as deltoid::FromDelta>::validate_from_delta(delta, errors)
+            }
+
+            pub fn into_delta(
+                value: #remote<#type_args>,
+            ) -> deltoid::DeltaResult<<Self as deltoid::Core>::Delta>
+            where
+                Self: deltoid::IntoDelta
+                    + for<'__remote> std::convert::From<&'__remote #remote<#type_args>>,
+            {
+                deltoid::IntoDelta::into_delta(Self::from(&value))
+            }
+        }
+    })
+}
+
+/// The bodies of the conversions from and into the remote struct.
+fn convert_struct(input: &InputType) -> DeriveResult<(TokenStream2, TokenStream2)> {
+    let fields: &[FieldDesc] = input.fields()?;
+    Ok(match input.struct_variant()? {
+        StructVariant::NamedStruct => {
+            let field_names: Vec<&Ident2> = fields.iter()
+                .map(|field: &FieldDesc| field.name_ref())
+                .collect::<DeriveResult<_>>()?;
+            (
+                quote! { Self { #( #field_names: value.#field_names.clone() ),* } },
+                quote! { Self { #( #field_names: value.#field_names ),* } },
+            )
+        },
+        StructVariant::TupleStruct => {
+            let field_positions: Vec<_> = fields.iter()
+                .map(|field: &FieldDesc| field.pos_ref())
+                .collect::<DeriveResult<_>>()?;
+            (
+                quote! { Self( #( value.#field_positions.clone() ),* ) },
+                quote! { Self( #( value.#field_positions ),* ) },
+            )
+        },
+        StructVariant::UnitStruct => (quote! { Self }, quote! { Self }),
+    })
+}
+
+/// The bodies of the conversions from and into the remote enum.
+fn convert_enum(input: &InputType, remote: &Path)
+                -> DeriveResult<(TokenStream2, TokenStream2)> {
+    let type_name: &Ident2 = input.type_name()?;
+    let enum_variants: &[EnumVariant] = input.enum_variants()?;
+    let mut from_remote_arms: Vec<TokenStream2> = vec![];
+    let mut into_remote_arms: Vec<TokenStream2> = vec![];
+    for v in enum_variants.iter() {
+        let variant_name: &Ident2 = &v.name;
+        match v.struct_variant {
+            StructVariant::NamedStruct => {
+                let field_names: Vec<&Ident2> = v.fields()
+                    .map(|field: &FieldDesc| field.name_ref())
+                    .collect::<DeriveResult<_>>()?;
+                from_remote_arms.push(quote! {
+                    #remote::#variant_name { #(#field_names),* } =>
+                        Self::#variant_name {
+                            #( #field_names: #field_names.clone() ),*
+                        },
+                });
+                into_remote_arms.push(quote! {
+                    #type_name::#variant_name { #(#field_names),* } =>
+                        Self::#variant_name { #(#field_names),* },
+                });
+            },
+            StructVariant::TupleStruct => {
+                let field_names: Vec<Ident2> = v.fields()
+                    .map(|field: &FieldDesc| {
+                        Ok(format_ident!("field{}", field.pos_ref()?.to_string()))
+                    })
+                    .collect::<DeriveResult<_>>()?;
+                from_remote_arms.push(quote! {
+                    #remote::#variant_name( #(#field_names),* ) =>
+                        Self::#variant_name( #( #field_names.clone() ),* ),
+                });
+                into_remote_arms.push(quote! {
+                    #type_name::#variant_name( #(#field_names),* ) =>
+                        Self::#variant_name( #(#field_names),* ),
+                });
+            },
+            StructVariant::UnitStruct => {
+                from_remote_arms.push(quote! {
+                    #remote::#variant_name => Self::#variant_name,
+                });
+                into_remote_arms.push(quote! {
+                    #type_name::#variant_name => Self::#variant_name,
+                });
+            },
+        }
+    }
+    Ok((
+        quote! { match value { #(#from_remote_arms)* } },
+        quote! { match value { #(#into_remote_arms)* } },
+    ))
+}
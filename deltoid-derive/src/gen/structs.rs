@@ -14,30 +14,28 @@ pub(crate) fn define_delta_struct(input: &InputType) -> DeriveResult<TokenStream
     let struct_variant: &StructVariant = input.struct_variant()?;
     let delta_type_name: &Ident2 = input.delta_type_name()?;
     let fields: &[FieldDesc] = input.fields()?;
-    let in_type_param_decls: &Punctuated<GenericParam, Comma> =
-        input.type_param_decls()?;
-    let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
-        .map(|type_param_decl| match type_param_decl {
-            GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
-            GenericParam::Const(const_param)     => quote! { #const_param  },
-            GenericParam::Type(type_param) => {
-                let T: &Ident2 = &type_param.ident;
-                // NOTE: trait bounds on the corresponding type parameter
-                //       `T` in `InputType::Struct#type_param`:
-                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
-                    .map(|trait_bound| quote! { #trait_bound })
-                    .collect();
-                quote! {
-                    #T: deltoid::Core
-                    #(+ #bounds)* // Copy user-specified type/lifetime bounds
-                }
-            },
-        })
-        .collect();
+    let type_param_decls: Vec<TokenStream2> =
+        input.delta_type_param_decls(quote! { deltoid::Core })?;
     let field_types: Vec<TokenStream2> = fields.iter()
         .map(|field: &FieldDesc| field.type_tokens())
         .collect();
-    let where_clause = quote! { where };
+    let skip_unchanged: bool = input.is_skip_unchanged()?;
+    let field_serde_attrs: Vec<TokenStream2> = fields.iter()
+        .map(|field: &FieldDesc| field.serde_attrs(skip_unchanged))
+        .collect();
+    let serde_attr: TokenStream2 = input.serde_bound_attr()?;
+    let delta_vis: &Visibility = input.delta_vis()?;
+    let delta_attrs: TokenStream2 =
+        input.delta_attrs(
+            &["Clone", "PartialEq", "Deserialize", "Serialize"],
+        )?;
+    let unit_delta_attrs: TokenStream2 =
+        input.delta_attrs(&[
+            "Clone", "PartialEq", "Eq", "Hash", "Deserialize", "Serialize",
+        ])?;
+    let predicates: Vec<TokenStream2> =
+        input.delta_where_predicates(quote! { deltoid::Core })?;
+    let where_clause = quote! { where #(#predicates),* };
     match struct_variant {
         StructVariant::NamedStruct => {
             let field_names: Vec<&Ident2> = fields.iter()
@@ -46,64 +44,346 @@ pub(crate) fn define_delta_struct(input: &InputType) -> DeriveResult<TokenStream
             Ok(quote! {
                 #[derive(Clone, PartialEq)]
                 #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
-                pub struct #delta_type_name<#(#type_param_decls),*>
+                #serde_attr
+                #delta_attrs
+                #delta_vis struct #delta_type_name<#(#type_param_decls),*>
                     #where_clause
                 {
-                    #( #[doc(hidden)] pub(self) #field_names: #field_types, )*
+                    #(
+                        #[doc(hidden)] #field_serde_attrs
+                        pub(self) #field_names: #field_types,
+                    )*
                 }
-                // TODO: Add a {Eq, Hash} impl for `#delta_type_name`
-                // where `T: {Eq, Hash}` for every generic type arg `T`.
             })
         },
         StructVariant::TupleStruct => Ok(quote! {
             #[derive(Clone, PartialEq)]
             #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
-            pub struct #delta_type_name<#(#type_param_decls),*> (
-                #( #[doc(hidden)] pub(self) #field_types, )*
+            #serde_attr
+            #delta_attrs
+            #delta_vis struct #delta_type_name<#(#type_param_decls),*> (
+                #(
+                    #[doc(hidden)] #field_serde_attrs
+                    pub(self) #field_types,
+                )*
             ) #where_clause ;
-            // TODO: Add a {Eq, Hash} impl for `#delta_type_name`
-            // where `T: {Eq, Hash}` for every generic type arg `T`.
         }),
         StructVariant::UnitStruct => Ok(quote! {
             #[derive(Clone, PartialEq, Eq, Hash)]
-                #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
-            pub struct #delta_type_name<#(#type_param_decls),*>
+            #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+            #serde_attr
+            #unit_delta_attrs
+            #delta_vis struct #delta_type_name<#(#type_param_decls),*>
                 #where_clause ;
         }),
     }
 }
 
-pub(crate) fn define_Debug_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+/// Implement `Eq` and `Hash` for the delta type if the input struct's
+/// `#[delta(derive(...))]` includes them, provided that the deltas of all
+/// of its fields implement them.
+pub(crate) fn define_Eq_Hash_impls(input: &InputType) -> DeriveResult<TokenStream2> {
     if !input.is_struct() { return bug_detected!() }
     let struct_variant: &StructVariant = input.struct_variant()?;
-    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    if *struct_variant == StructVariant::UnitStruct {
+        // NOTE: the delta type of a unit struct derives `Eq` and `Hash`
+        return Ok(quote! {});
+    }
     let fields: &[FieldDesc] = input.fields()?;
-    let in_type_param_decls: &Punctuated<GenericParam, Comma> =
-        input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
-    let in_where_clause: &WhereClause = input.where_clause()?;
-    let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
-        .map(|type_param_decl| match type_param_decl {
-            GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
-            GenericParam::Const(const_param)     => quote! { #const_param  },
-            GenericParam::Type(type_param) => {
-                let T: &Ident2 = &type_param.ident;
-                // NOTE: `bounds` defines trait bounds on the corresponding
-                // type parameter `T` in `InputType::Struct#type_param`:
-                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
-                    .map(|trait_bound| quote! { #trait_bound })
-                    .collect();
-                quote! {
-                    #T: deltoid::Core
-                    + std::fmt::Debug
-                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let type_args: Punctuated<GenericArgument, Comma> =
+        input.delta_type_args()?;
+    let type_param_decls: Vec<TokenStream2> =
+        input.delta_type_param_decls(quote! { deltoid::Core })?;
+    let predicates: Vec<TokenStream2> =
+        input.delta_where_predicates(quote! { deltoid::Core })?;
+    let field_types: Vec<TokenStream2> = fields.iter()
+        .filter(|field: &&FieldDesc| !field.ignore_field())
+        .map(|field: &FieldDesc| field.type_tokens())
+        .collect();
+    let field_members: Vec<TokenStream2> = fields.iter()
+        .map(|field: &FieldDesc| Ok(if field.is_named() {
+            let fname = field.name_ref()?;
+            quote! { #fname }
+        } else {
+            let fpos = field.pos_ref()?;
+            quote! { #fpos }
+        }))
+        .collect::<DeriveResult<_>>()?;
+    let hash_body = quote! {
+        #( std::hash::Hash::hash(&self.#field_members, state); )*
+    };
+    let mut impls = TokenStream2::new();
+    if input.delta_derives_trait("Eq")? {
+        impls.extend(quote! {
+            impl<#(#type_param_decls),*> std::cmp::Eq
+                for #delta_type_name<#type_args>
+                where
+                    #(#predicates,)*
+                    Self: std::cmp::PartialEq,
+                    #(#field_types: std::cmp::Eq,)*
+            {}
+        });
+    }
+    if input.delta_derives_trait("Hash")? {
+        impls.extend(quote! {
+            impl<#(#type_param_decls),*> std::hash::Hash
+                for #delta_type_name<#type_args>
+                where #(#predicates,)* #(#field_types: std::hash::Hash,)*
+            {
+                #[allow(unused)]
+                fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                    #hash_body
                 }
-            },
-        })
+            }
+        });
+    }
+    Ok(impls)
+}
+
+/// Ensure that the methods that `define_accessors` generates for the
+/// `diffed` fields don't clash with each other, which would otherwise
+/// surface as a confusing error in the generated code.
+fn ensure_distinct_accessors(diffed: &[&FieldDesc]) -> DeriveResult<()> {
+    let names: Vec<&Ident2> = diffed.iter()
+        .map(|field: &&FieldDesc| field.name_ref())
+        .collect::<DeriveResult<_>>()?;
+    for &fname in names.iter() {
+        let clash: Option<String> = if fname == "builder" {
+            Some(format!("the getter of field `{}` clashes with the \
+                          generated `builder()` of the delta type", fname))
+        } else if fname == "build" {
+            Some(format!("the builder setter of field `{}` clashes with the \
+                          generated `build()` of the delta builder", fname))
+        } else {
+            names.iter()
+                .find(|&&other| format_ident!("{}_delta", other) == *fname)
+                .map(|other| format!(
+                    "the builder setter of field `{}` clashes with the \
+                     generated `{}_delta()` setter of field `{}`",
+                    fname, other, other
+                ))
+        };
+        if let Some(message) = clash {
+            return Err(Error::new_spanned(fname, message).into());
+        }
+    }
+    Ok(())
+}
+
+/// Define getters for the fields of the delta type of a named struct, a
+/// `Default` impl for the delta without any changes, and a builder for it.
+pub(crate) fn define_accessors(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    if *input.struct_variant()? != StructVariant::NamedStruct {
+        return Ok(quote! {});
+    }
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let delta_vis: &Visibility = input.delta_vis()?;
+    let builder_name: Ident2 = format_ident!("{}Builder", delta_type_name);
+    let fields: &[FieldDesc] = input.fields()?;
+    let type_args: Punctuated<GenericArgument, Comma> =
+        input.delta_type_args()?;
+    let type_param_decls: Vec<TokenStream2> =
+        input.delta_type_param_decls(quote! { deltoid::Core })?;
+    let predicates: Vec<TokenStream2> =
+        input.delta_where_predicates(quote! { deltoid::Core })?;
+    let where_clause = quote! { where #(#predicates),* };
+    let diffed: Vec<&FieldDesc> = fields.iter()
+        .filter(|field: &&FieldDesc| !field.ignore_field())
         .collect();
-    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
-        .map(|where_predicate| quote! { #where_predicate })
+    ensure_distinct_accessors(&diffed)?;
+    let flattened_types: Vec<TokenStream2> = diffed.iter()
+        .filter(|field: &&&FieldDesc| field.is_flatten())
+        .map(|field: &&FieldDesc| input.erase_lifetimes(field.delta_type()))
+        .collect::<DeriveResult<_>>()?;
+    let erased: Vec<Lifetime> = input.erased_lifetimes()?.iter()
+        .map(|lifetime| Lifetime::new(&format!("'{}", lifetime), lifetime.span()))
         .collect();
+    let unchanged: Vec<TokenStream2> = fields.iter()
+        .map(|field: &FieldDesc| {
+            let fname = field.name_ref()?;
+            Ok(if field.ignore_field() {
+                quote! { #fname: std::marker::PhantomData }
+            } else if field.is_flatten() {
+                quote! { #fname: Default::default() }
+            } else {
+                quote! { #fname: None }
+            })
+        })
+        .collect::<DeriveResult<_>>()?;
+    let mut getters = TokenStream2::new();
+    let mut setters = TokenStream2::new();
+    for field in diffed.iter() {
+        let (fname, ftype) = (field.name_ref()?, field.type_ref());
+        let delta_setter: Ident2 = format_ident!("{}_delta", fname);
+        let delta_type: TokenStream2 =
+            input.erase_lifetimes(field.delta_type())?;
+        let static_ftype: TokenStream2 =
+            input.erase_lifetimes(quote! { #ftype })?;
+        let borrows: bool =
+            quote! { #ftype }.to_string() != static_ftype.to_string();
+        let into_delta: TokenStream2 =
+            field.delta_from_value_tokens(quote! { value });
+        let (lifetimes, value_type, into_delta_bound, into_delta)
+            : (&[Lifetime], TokenStream2, TokenStream2, TokenStream2)
+            = match field.with_module()
+        {
+            Some(_) => (&[], static_ftype, quote! {}, into_delta),
+            // NOTE: a setter of a `Cow` field that borrows takes a value with
+            //       any lifetime, and converts it to the owned value of the
+            //       field type of the delta type, whose delta doesn't borrow
+            None if borrows => (
+                &erased,
+                quote! { #ftype },
+                quote! { where #static_ftype: deltoid::IntoDelta },
+                quote! {{
+                    let value: #static_ftype =
+                        std::borrow::Cow::Owned(value.into_owned());
+                    #into_delta
+                }},
+            ),
+            None => (
+                &[],
+                quote! { #ftype },
+                quote! { where #ftype: deltoid::IntoDelta },
+                into_delta,
+            ),
+        };
+        let (getter_type, getter_body, set) = if field.is_flatten() {
+            (
+                quote! { &#delta_type },
+                quote! { &self.#fname },
+                quote! { self.delta.#fname = delta },
+            )
+        } else {
+            (
+                quote! { Option<&#delta_type> },
+                quote! { self.#fname.as_ref() },
+                quote! { self.delta.#fname = Some(delta) },
+            )
+        };
+        let setter_doc: String = format!(
+            "Set the delta of `{0}` to the delta that `value` converts to \
+             with `IntoDelta`.  For a collection, that delta adds each of \
+             its elements, so applying it to a non-empty collection appends \
+             to it rather than replacing it.  To replace the elements, pass \
+             a delta calculated with `Delta::delta()` to `{0}_delta()`.",
+            fname,
+        );
+        getters.extend(quote! {
+            pub fn #fname(&self) -> #getter_type {
+                #getter_body
+            }
+        });
+        setters.extend(quote! {
+            #[doc = #setter_doc]
+            pub fn #fname<#(#lifetimes),*>(mut self, value: #value_type) -> Self
+            #into_delta_bound
+            {
+                #[allow(unused)] use deltoid::IntoDelta;
+                match #into_delta {
+                    Ok(delta) => self.#delta_setter(delta),
+                    Err(error) => {
+                        self.errors.push(error.at(deltoid::PathSegment::Field(
+                            stringify!(#fname).to_string()
+                        )));
+                        self
+                    },
+                }
+            }
+
+            pub fn #delta_setter(mut self, delta: #delta_type) -> Self {
+                let fname: &'static str = stringify!(#fname);
+                if let Err(error) = deltoid::ensure!(
+                    !self.set.contains(&fname),
+                    "field `{}` is set more than once", fname
+                ) {
+                    let field = deltoid::PathSegment::Field(fname.to_string());
+                    self.errors.push(error.at(field));
+                    return self;
+                }
+                self.set.push(fname);
+                #set;
+                self
+            }
+        });
+    }
+    let impl_Default: TokenStream2 = if input.delta_derives_trait("Default")? {
+        // NOTE: `#[delta(derive(Default))]` already implements it
+        quote! {}
+    } else {
+        quote! {
+            impl<#(#type_param_decls),*> std::default::Default
+                for #delta_type_name<#type_args>
+                where
+                    #(#predicates,)*
+                    #(#flattened_types: std::default::Default,)*
+            {
+                fn default() -> Self {
+                    Self { #(#unchanged),* }
+                }
+            }
+        }
+    };
+    Ok(quote! {
+        #impl_Default
+
+        #[allow(unused)]
+        impl<#(#type_param_decls),*> #delta_type_name<#type_args>
+            #where_clause
+        {
+            #getters
+
+            pub fn builder() -> #builder_name<#type_args>
+            where Self: std::default::Default {
+                #builder_name {
+                    delta: std::default::Default::default(),
+                    set: vec![],
+                    errors: vec![],
+                }
+            }
+        }
+
+        #delta_vis struct #builder_name<#(#type_param_decls),*>
+            #where_clause
+        {
+            delta: #delta_type_name<#type_args>,
+            set: Vec<&'static str>,
+            errors: Vec<deltoid::DeltaError>,
+        }
+
+        #[allow(unused)]
+        impl<#(#type_param_decls),*> #builder_name<#type_args>
+            #where_clause
+        {
+            #setters
+
+            /// Build the delta, or return the first problem with it.
+            pub fn build(mut self)
+                         -> deltoid::DeltaResult<#delta_type_name<#type_args>>
+            {
+                if self.errors.is_empty() {
+                    Ok(self.delta)
+                } else {
+                    Err(self.errors.remove(0))
+                }
+            }
+        }
+    })
+}
+
+pub(crate) fn define_Debug_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    let struct_variant: &StructVariant = input.struct_variant()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let fields: &[FieldDesc] = input.fields()?;
+    let type_args: Punctuated<GenericArgument, Comma> =
+        input.delta_type_args()?;
+    let type_param_decls: Vec<TokenStream2> =
+        input.delta_type_param_decls(quote! { deltoid::Core + std::fmt::Debug })?;
+    let predicates: Vec<TokenStream2> =
+        input.delta_where_predicates(quote! { deltoid::Core })?;
     let where_clause = quote! { where #(#predicates),* };
     match struct_variant {
         StructVariant::NamedStruct => {
@@ -111,9 +391,10 @@ pub(crate) fn define_Debug_impl(input: &InputType) -> DeriveResult<TokenStream2>
             let buf: Ident2 = format_ident!("buf");
             for field in fields.iter() {
                 let (fname, ftype) = (field.name_ref()?, field.type_ref());
-                body.extend(if field.ignore_field() {
+                body.extend(if field.ignore_field() || field.is_flatten() {
                     quote! {
-                        // NOTE: format the PhantomData field itself
+                        // NOTE: format the PhantomData field
+                        //       or the flattened delta itself
                         #buf.field(stringify!(#fname), &self.#fname);
                     }
                 } else {
@@ -130,7 +411,7 @@ pub(crate) fn define_Debug_impl(input: &InputType) -> DeriveResult<TokenStream2>
             }
             Ok(quote! {
                 impl<#(#type_param_decls),*> std::fmt::Debug
-                    for #delta_type_name<#type_params>
+                    for #delta_type_name<#type_args>
                     #where_clause
                 {
                     fn fmt(&self, f: &mut std::fmt::Formatter)
@@ -193,7 +474,7 @@ pub(crate) fn define_Debug_impl(input: &InputType) -> DeriveResult<TokenStream2>
             };
             Ok(quote! {
                 impl<#(#type_param_decls),*> std::fmt::Debug
-                    for #delta_type_name<#type_params>
+                    for #delta_type_name<#type_args>
                     #where_clause
                 {
                     fn fmt(&self, f: &mut std::fmt::Formatter)
@@ -207,7 +488,7 @@ pub(crate) fn define_Debug_impl(input: &InputType) -> DeriveResult<TokenStream2>
         StructVariant::UnitStruct => {
             Ok(quote! {
                 impl<#(#type_param_decls),*> std::fmt::Debug
-                    for #delta_type_name<#type_params>
+                    for #delta_type_name<#type_args>
                     #where_clause
                 {
                     fn fmt(&self, f: &mut std::fmt::Formatter)
@@ -229,58 +510,49 @@ pub(crate) fn define_Core_impl(input: &InputType) -> DeriveResult<TokenStream2>
     let delta_type_name: &Ident2 = input.delta_type_name()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
-    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
+    let delta_type_args: Punctuated<GenericArgument, Comma> =
+        input.delta_type_args()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
             GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
             GenericParam::Const(const_param)     => quote! { #const_param  },
-            GenericParam::Type(type_param) => {
-                let T: &Ident2 = &type_param.ident;
-                // NOTE: `bounds` defines trait bounds on the corresponding
-                // type parameter `T` in `InputType::Struct#type_param`:
-                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
-                    .map(|trait_bound| quote! { #trait_bound })
-                    .collect();
+            GenericParam::Type(type_param) => input.type_param_decl(
+                type_param,
                 quote! {
-                    #T: std::clone::Clone
-                    + std::fmt::Debug
-                        + std::cmp::PartialEq
-                        + deltoid::Core
-                        + for<'de> serde::Deserialize<'de>
-                        + serde::Serialize
-                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
-                }
-            },
+                    std::clone::Clone + std::fmt::Debug + std::cmp::PartialEq
+                    + deltoid::Core + for<'de> serde::Deserialize<'de>
+                    + serde::Serialize
+                },
+            ),
         })
         .collect();
-    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
-        .map(|where_predicate| quote! { #where_predicate })
-        .collect();
+    let predicates: Vec<TokenStream2> =
+        input.where_predicates(quote! { deltoid::Core })?;
     let where_clause = quote! { where #(#predicates),* };
     match struct_variant {
         StructVariant::NamedStruct => Ok(quote! {
             impl<#(#type_param_decls),*> deltoid::Core
-                for #type_name<#type_params>
+                for #type_name<#type_args>
                 #where_clause
             {
-                type Delta = #delta_type_name<#type_params>;
+                type Delta = #delta_type_name<#delta_type_args>;
             }
         }),
         StructVariant::TupleStruct => Ok(quote! {
             impl<#(#type_param_decls),*> deltoid::Core
-                for #type_name<#type_params>
+                for #type_name<#type_args>
                 #where_clause
             {
-                type Delta = #delta_type_name<#type_params>;
+                type Delta = #delta_type_name<#delta_type_args>;
             }
         }),
         StructVariant::UnitStruct => Ok(quote! {
             impl<#(#type_param_decls),*> deltoid::Core
-                for #type_name<#type_params>
+                for #type_name<#type_args>
                 #where_clause
             {
-                type Delta = #delta_type_name<#type_params>;
+                type Delta = #delta_type_name<#delta_type_args>;
             }
         }),
     }
@@ -293,34 +565,23 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
     let fields: &[FieldDesc] = input.fields()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
-    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
             GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
             GenericParam::Const(const_param)     => quote! { #const_param  },
-            GenericParam::Type(type_param) => {
-                let T: &Ident2 = &type_param.ident;
-                // NOTE: `bounds` defines trait bounds on the corresponding
-                // type parameter `T` in `InputType::Struct#type_param`:
-                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
-                    .map(|trait_bound| quote! { #trait_bound })
-                    .collect();
+            GenericParam::Type(type_param) => input.type_param_decl(
+                type_param,
                 quote! {
-                    #T: std::clone::Clone
-                    + std::fmt::Debug
-                        + std::cmp::PartialEq
-                        + deltoid::Apply
-                        + for<'de> serde::Deserialize<'de>
-                        + serde::Serialize
-                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
-                }
-            },
+                    std::clone::Clone + std::fmt::Debug + std::cmp::PartialEq
+                    + deltoid::Apply + for<'de> serde::Deserialize<'de>
+                    + serde::Serialize
+                },
+            ),
         })
         .collect();
-    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
-        .map(|where_predicate| quote! { #where_predicate })
-        .collect();
+    let predicates: Vec<TokenStream2> =
+        input.where_predicates(quote! { deltoid::Apply })?;
     let where_clause = quote! { where #(#predicates),* };
     match struct_variant {
         StructVariant::NamedStruct => {
@@ -331,10 +592,26 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
                         quote! {
                             #fname: self.#fname.clone(),
                         }
+                    } else if field.is_flatten() {
+                        let apply = field.apply_tokens(
+                            quote! { self.#fname }, quote! { delta.#fname }
+                        );
+                        quote! {
+                            #fname: #apply
+                                .map_err(|e| e.at(deltoid::PathSegment::Field(
+                                    stringify!(#fname).to_string()
+                                )))?,
+                        }
                     } else {
+                        let apply = field.apply_tokens(
+                            quote! { self.#fname }, quote! { d }
+                        );
                         quote! {
                             #fname: if let Some(d) = delta.#fname {
-                                self.#fname.apply(d)?
+                                #apply
+                                    .map_err(|e| e.at(deltoid::PathSegment::Field(
+                                        stringify!(#fname).to_string()
+                                    )))?
                             } else {
                                 self.#fname.clone()
                             },
@@ -342,17 +619,62 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
                     })
                 })
                 .collect::<DeriveResult<_>>()?;
+            let field_validations: Vec<TokenStream2> = fields.iter()
+                .filter(|field: &&FieldDesc| !field.ignore_field())
+                .map(|field: &FieldDesc| {
+                    let fname = field.name_ref()?;
+                    let validate = field.validate_tokens(
+                        quote! { self.#fname }, quote! { d }
+                    );
+                    let delta = if field.is_flatten() {
+                        quote! { Some(&delta.#fname) }
+                    } else {
+                        quote! { &delta.#fname }
+                    };
+                    Ok(quote! {
+                        if let Some(d) = #delta {
+                            let start: usize = errors.len();
+                            #validate
+                            deltoid::DeltaError::scope_all(
+                                errors, start,
+                                &deltoid::PathSegment::Field(
+                                    stringify!(#fname).to_string()
+                                ),
+                            );
+                        }
+                    })
+                })
+                .collect::<DeriveResult<_>>()?;
             Ok(quote! {
                 impl<#(#type_param_decls),*> deltoid::Apply
-                    for #type_name<#type_params>
+                    for #type_name<#type_args>
                     #where_clause
                 {
                     #[allow(unused)]
                     fn apply(&self, delta: Self::Delta)
                              -> deltoid::DeltaResult<Self>
                     {
+                        self.apply_with(delta, &deltoid::ApplyPolicy::default())
+                    }
+
+                    #[allow(unused)]
+                    fn apply_with(
+                        &self,
+                        delta: Self::Delta,
+                        policy: &deltoid::ApplyPolicy,
+                    ) -> deltoid::DeltaResult<Self> {
                         Ok(Self { #(#field_assignments)* })
                     }
+
+                    #[allow(unused)]
+                    fn validate_into(
+                        &self,
+                        delta: &Self::Delta,
+                        errors: &mut Vec<deltoid::DeltaError>,
+                    ) {
+                        #[allow(unused)] use deltoid::Apply;
+                        #(#field_validations)*
+                    }
                 }
             })
         },
@@ -365,9 +687,15 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
                             self.#fpos.clone(),
                         }
                     } else {
+                        let apply = field.apply_tokens(
+                            quote! { self.#fpos }, quote! { d }
+                        );
                         quote! {
                             if let Some(d) = delta.#fpos {
-                                self.#fpos.apply(d)?
+                                #apply
+                                    .map_err(|e| e.at(
+                                        deltoid::PathSegment::Position(#fpos)
+                                    ))?
                             } else {
                                 self.#fpos.clone()
                             },
@@ -375,24 +703,62 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
                     })
                 })
                 .collect::<DeriveResult<_>>()?;
+            let field_validations: Vec<TokenStream2> = fields.iter()
+                .filter(|field: &&FieldDesc| !field.ignore_field())
+                .map(|field: &FieldDesc| {
+                    let fpos = field.pos_ref()?;
+                    let validate = field.validate_tokens(
+                        quote! { self.#fpos }, quote! { d }
+                    );
+                    Ok(quote! {
+                        if let Some(d) = &delta.#fpos {
+                            let start: usize = errors.len();
+                            #validate
+                            deltoid::DeltaError::scope_all(
+                                errors, start,
+                                &deltoid::PathSegment::Position(#fpos),
+                            );
+                        }
+                    })
+                })
+                .collect::<DeriveResult<_>>()?;
             Ok(quote! {
                 impl<#(#type_param_decls),*> deltoid::Apply
-                    for #type_name<#type_params>
+                    for #type_name<#type_args>
                     #where_clause
                 {
                     #[allow(unused)]
                     fn apply(&self, delta: Self::Delta)
                              -> deltoid::DeltaResult<Self>
                     {
+                        self.apply_with(delta, &deltoid::ApplyPolicy::default())
+                    }
+
+                    #[allow(unused)]
+                    fn apply_with(
+                        &self,
+                        delta: Self::Delta,
+                        policy: &deltoid::ApplyPolicy,
+                    ) -> deltoid::DeltaResult<Self> {
                         Ok(Self( #(#field_assignments)* ))
                     }
+
+                    #[allow(unused)]
+                    fn validate_into(
+                        &self,
+                        delta: &Self::Delta,
+                        errors: &mut Vec<deltoid::DeltaError>,
+                    ) {
+                        #[allow(unused)] use deltoid::Apply;
+                        #(#field_validations)*
+                    }
                 }
             })
         },
         StructVariant::UnitStruct => {
             Ok(quote! {
                 impl<#(#type_param_decls),*> deltoid::Apply
-                    for #type_name<#type_params>
+                    for #type_name<#type_args>
                     #where_clause
                 {
                     #[allow(unused)]
@@ -401,6 +767,15 @@ pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2>
                     {
                         Ok(Self)
                     }
+
+                    #[allow(unused)]
+                    fn validate_into(
+                        &self,
+                        delta: &Self::Delta,
+                        errors: &mut Vec<deltoid::DeltaError>,
+                    ) {
+                        // NOTE: applying a delta to a unit struct always succeeds
+                    }
                 }
             })
         },
@@ -415,35 +790,23 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
     let fields: &[FieldDesc] = input.fields()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
-    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
             GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
             GenericParam::Const(const_param)     => quote! { #const_param  },
-            GenericParam::Type(type_param) => {
-                let T: &Ident2 = &type_param.ident;
-                // NOTE: `bounds` defines trait bounds on the corresponding
-                // type parameter `T` in `InputType::Struct#type_param`:
-                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
-                    .map(|trait_bound| quote! { #trait_bound })
-                    .collect();
+            GenericParam::Type(type_param) => input.type_param_decl(
+                type_param,
                 quote! {
-                    #T:
-                    std::clone::Clone
-                        + std::fmt::Debug
-                        + std::cmp::PartialEq
-                        + deltoid::Delta
-                        + for<'de> serde::Deserialize<'de>
-                        + serde::Serialize
-                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
-                }
-            },
+                    std::clone::Clone + std::fmt::Debug + std::cmp::PartialEq
+                    + deltoid::Delta + for<'de> serde::Deserialize<'de>
+                    + serde::Serialize
+                },
+            ),
         })
         .collect();
-    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
-        .map(|where_predicate| quote! { #where_predicate })
-        .collect();
+    let predicates: Vec<TokenStream2> =
+        input.where_predicates(quote! { deltoid::Delta })?;
     let where_clause = quote! { where #(#predicates),* };
     match struct_variant {
         StructVariant::NamedStruct => {
@@ -452,10 +815,18 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
                     let fname = field.name_ref()?;
                     Ok(if field.ignore_field() {
                         quote! { #fname: std::marker::PhantomData }
+                    } else if field.is_flatten() {
+                        let delta = field.delta_tokens(
+                            quote! { self.#fname }, quote! { rhs.#fname }
+                        );
+                        quote! { #fname: #delta? }
                     } else {
+                        let delta = field.delta_tokens(
+                            quote! { self.#fname }, quote! { rhs.#fname }
+                        );
                         quote! {
                             #fname: if self.#fname != rhs.#fname {
-                                Some(self.#fname.delta(&rhs.#fname)?)
+                                Some(#delta?)
                             } else {
                                 None
                             }
@@ -465,7 +836,7 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
                 .collect::<DeriveResult<_>>()?;
             Ok(quote! {
                 impl<#(#type_param_decls),*> deltoid::Delta
-                    for #type_name<#type_params>
+                    for #type_name<#type_args>
                     #where_clause
                 {
                     fn delta(&self, rhs: &Self) -> deltoid::DeltaResult<Self::Delta> {
@@ -482,9 +853,12 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
                     Ok(if field.ignore_field() {
                         quote! { std::marker::PhantomData }
                     } else {
+                        let delta = field.delta_tokens(
+                            quote! { self.#fpos }, quote! { rhs.#fpos }
+                        );
                         quote! {
                             if self.#fpos != rhs.#fpos {
-                                Some(self.#fpos.delta(&rhs.#fpos)?)
+                                Some(#delta?)
                             } else {
                                 None
                             }
@@ -494,7 +868,7 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
                 .collect::<DeriveResult<_>>()?;
             Ok(quote! {
                 impl<#(#type_param_decls),*> deltoid::Delta
-                    for #type_name<#type_params>
+                    for #type_name<#type_args>
                     #where_clause
                 {
                     fn delta(&self,rhs: &Self) -> deltoid::DeltaResult<Self::Delta> {
@@ -507,7 +881,7 @@ pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2>
         StructVariant::UnitStruct =>
             Ok(quote! {
                 impl<#(#type_param_decls),*> deltoid::Delta
-                    for #type_name<#type_params>
+                    for #type_name<#type_args>
                     #where_clause
                 {
                     fn delta(&self,rhs: &Self) -> deltoid::DeltaResult<Self::Delta> {
@@ -526,37 +900,25 @@ pub(crate) fn define_FromDelta_impl(input: &InputType) -> DeriveResult<TokenStre
     let fields: &[FieldDesc] = input.fields()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
-    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
             GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
             GenericParam::Const(const_param)     => quote! { #const_param  },
-            GenericParam::Type(type_param) => {
-                let T: &Ident2 = &type_param.ident;
-                // NOTE: `bounds` defines trait bounds on the corresponding
-                // type parameter `T` in `InputType::Struct#type_param`:
-                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
-                    .map(|trait_bound| quote! { #trait_bound })
-                    .collect();
+            GenericParam::Type(type_param) => input.type_param_decl(
+                type_param,
                 quote! {
-                    #T:
-                    std::clone::Clone
-                        + std::fmt::Debug
-                        + std::cmp::PartialEq
-                        + deltoid::FromDelta
-                        + for<'de> serde::Deserialize<'de>
-                        + serde::Serialize
-                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
-                }
-            },
+                    std::clone::Clone + std::fmt::Debug + std::cmp::PartialEq
+                    + deltoid::FromDelta + for<'de> serde::Deserialize<'de>
+                    + serde::Serialize
+                },
+            ),
         })
         .collect();
-    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
-        .map(|where_predicate| quote! { #where_predicate })
-        .collect();
+    let predicates: Vec<TokenStream2> =
+        input.where_predicates(quote! { deltoid::FromDelta })?;
     let where_clause = quote! { where #(#predicates),* };
-    let match_body: TokenStream2 = match struct_variant {
+    let (match_body, validation_body) = match struct_variant {
         StructVariant::NamedStruct => {
             let field_names: Vec<_> = fields.iter()
                 .map(|field: &FieldDesc| field.name_ref())
@@ -565,27 +927,56 @@ pub(crate) fn define_FromDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                 .map(|field: &FieldDesc| {
                     let fname = field.name_ref()?;
                     let ftype = field.type_ref();
+                    let from_delta = field.value_from_delta_path();
                     Ok(if field.ignore_field() {
                         quote! { #fname: Default::default() }
+                    } else if field.is_flatten() {
+                        quote! {
+                            #fname: #from_delta(#fname)
+                                .map_err(|e| e.at(deltoid::PathSegment::Field(
+                                    stringify!(#fname).to_string()
+                                )))?
+                        }
                     } else {
                         quote! {
-                            #fname: <#ftype>::from_delta(
-                                #fname.ok_or_else(|| DeltaError::ExpectedValue {
+                            #fname: #fname
+                                .ok_or_else(|| DeltaError::ExpectedValue {
                                     type_name: stringify!(#ftype).to_string(),
                                     file: file!().to_string(),
                                     line: line!(),
                                     column: column!(),
-                                })?
-                            )?
+                                })
+                                .and_then(#from_delta)
+                                .map_err(|e| e.at(deltoid::PathSegment::Field(
+                                    stringify!(#fname).to_string()
+                                )))?
                         }
                     })
                 })
                 .collect::<DeriveResult<_>>()?;
-            quote! {
-                #delta_type_name { #(#field_names),* } => {
-                    Self { #(#field_assignments),* }
+            let field_validations: Vec<TokenStream2> = fields.iter()
+                .filter(|field: &&FieldDesc| !field.ignore_field())
+                .map(|field: &FieldDesc| {
+                    let fname = field.name_ref()?;
+                    Ok(field.validate_from_delta_tokens(quote! { #fname }, &[
+                        quote! {
+                            deltoid::PathSegment::Field(stringify!(#fname).to_string())
+                        },
+                    ]))
+                })
+                .collect::<DeriveResult<_>>()?;
+            (
+                quote! {
+                    #delta_type_name { #(#field_names),* } => {
+                        Self { #(#field_assignments),* }
+                    },
                 },
-            }
+                quote! {
+                    #delta_type_name { #(#field_names),* } => {
+                        #(#field_validations)*
+                    },
+                },
+            )
         },
         StructVariant::TupleStruct => {
             let field_types: Vec<_> = fields.iter()
@@ -600,35 +991,57 @@ pub(crate) fn define_FromDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                 .map(|(fidx, field): (usize, &FieldDesc)| {
                     let fname = &field_names[fidx];
                     let ftype = field.type_ref();
+                    let from_delta = field.value_from_delta_path();
                     Ok(if field.ignore_field() {
                         quote! { Default::default() }
                     } else {
                         quote! {
-                            <#ftype>::from_delta(
-                                #fname.ok_or_else(|| DeltaError::ExpectedValue {
+                            #fname
+                                .ok_or_else(|| DeltaError::ExpectedValue {
                                     type_name: stringify!(#ftype).to_string(),
                                     file: file!().to_string(),
                                     line: line!(),
                                     column: column!(),
-                                })?
-                            )?
+                                })
+                                .and_then(#from_delta)
+                                .map_err(|e| e.at(
+                                    deltoid::PathSegment::Position(#fidx)
+                                ))?
                         }
                     })
                 })
                 .collect::<DeriveResult<_>>()?;
-            quote! {
-                #delta_type_name( #(#field_names),* ) => {
-                    Self( #(#field_assignments),* )
+            let field_validations: Vec<TokenStream2> = fields.iter()
+                .enumerate()
+                .filter(|(_, field): &(usize, &FieldDesc)| !field.ignore_field())
+                .map(|(fidx, field): (usize, &FieldDesc)| {
+                    let fname = &field_names[fidx];
+                    field.validate_from_delta_tokens(quote! { #fname }, &[
+                        quote! { deltoid::PathSegment::Position(#fidx) },
+                    ])
+                })
+                .collect();
+            (
+                quote! {
+                    #delta_type_name( #(#field_names),* ) => {
+                        Self( #(#field_assignments),* )
+                    },
                 },
-            }
-        },
-        StructVariant::UnitStruct => quote! {
-            #delta_type_name => Self,
+                quote! {
+                    #delta_type_name( #(#field_names),* ) => {
+                        #(#field_validations)*
+                    },
+                },
+            )
         },
+        StructVariant::UnitStruct => (
+            quote! { #delta_type_name => Self, },
+            quote! { #delta_type_name => {}, },
+        ),
     };
     Ok(quote! {
         impl<#(#type_param_decls),*> deltoid::FromDelta
-            for #type_name<#type_params>
+            for #type_name<#type_args>
             #where_clause
         {
             #[allow(unused)]
@@ -638,6 +1051,16 @@ pub(crate) fn define_FromDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                     #match_body
                 })
             }
+
+            #[allow(unused)]
+            fn validate_from_delta(
+                delta: &Self::Delta,
+                errors: &mut Vec<deltoid::DeltaError>,
+            ) {
+                match delta {
+                    #validation_body
+                }
+            }
         }
     })
 }
@@ -650,35 +1073,23 @@ pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStre
     let fields: &[FieldDesc] = input.fields()?;
     let in_type_param_decls: &Punctuated<GenericParam, Comma> =
         input.type_param_decls()?;
-    let type_params: &Punctuated<Ident2, Comma> = input.type_params()?;
-    let in_where_clause: &WhereClause = input.where_clause()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
     let type_param_decls: Vec<TokenStream2> = in_type_param_decls.iter()
         .map(|type_param_decl| match type_param_decl {
             GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
             GenericParam::Const(const_param)     => quote! { #const_param  },
-            GenericParam::Type(type_param) => {
-                let T: &Ident2 = &type_param.ident;
-                // NOTE: `bounds` defines trait bounds on the corresponding
-                // type parameter `T` in `InputType::Struct#type_param`:
-                let bounds: Vec<TokenStream2> = type_param.bounds.iter()
-                    .map(|trait_bound| quote! { #trait_bound })
-                    .collect();
+            GenericParam::Type(type_param) => input.type_param_decl(
+                type_param,
                 quote! {
-                    #T:
-                    std::clone::Clone
-                        + std::fmt::Debug
-                        + std::cmp::PartialEq
-                        + deltoid::IntoDelta
-                        + for<'de> serde::Deserialize<'de>
-                        + serde::Serialize
-                        #(+ #bounds)* // Copy user-specified type/lifetime bounds
-                }
-            },
+                    std::clone::Clone + std::fmt::Debug + std::cmp::PartialEq
+                    + deltoid::IntoDelta + for<'de> serde::Deserialize<'de>
+                    + serde::Serialize
+                },
+            ),
         })
         .collect();
-    let predicates: Vec<TokenStream2> = in_where_clause.predicates.iter()
-        .map(|where_predicate| quote! { #where_predicate })
-        .collect();
+    let predicates: Vec<TokenStream2> =
+        input.where_predicates(quote! { deltoid::IntoDelta })?;
     let where_clause = quote! { where #(#predicates),* };
     let mut match_body = TokenStream2::new();
     match_body.extend(match struct_variant {
@@ -691,8 +1102,14 @@ pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                     let fname = field.name_ref()?;
                     Ok(if field.ignore_field() {
                         quote! { #fname: std::marker::PhantomData }
+                    } else if field.is_flatten() {
+                        let into_delta =
+                            field.delta_from_value_tokens(quote! { #fname });
+                        quote! { #fname: #into_delta? }
                     } else {
-                        quote! { #fname: Some(#fname.into_delta()?) }
+                        let into_delta =
+                            field.delta_from_value_tokens(quote! { #fname });
+                        quote! { #fname: Some(#into_delta?) }
                     })
                 })
                 .collect::<DeriveResult<_>>()?;
@@ -714,7 +1131,9 @@ pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStre
                     Ok(if field.ignore_field() {
                         quote! { std::marker::PhantomData }
                     } else {
-                        quote! { Some(#fname.into_delta()?) }
+                        let into_delta =
+                            field.delta_from_value_tokens(quote! { #fname });
+                        quote! { Some(#into_delta?) }
                     })
                 })
                 .collect::<DeriveResult<_>>()?;
@@ -730,7 +1149,7 @@ pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStre
     });
     Ok(quote! {
         impl<#(#type_param_decls),*> deltoid::IntoDelta
-            for #type_name<#type_params>
+            for #type_name<#type_args>
             #where_clause
         {
             #[allow(unused)]
@@ -743,3 +1162,59 @@ pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStre
         }
     })
 }
+
+pub(crate) fn define_ToDynDelta_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    if !input.is_struct() { return bug_detected!() }
+    let struct_variant: &StructVariant = input.struct_variant()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let fields: &[FieldDesc] = input.fields()?;
+    let type_args: Punctuated<GenericArgument, Comma> =
+        input.delta_type_args()?;
+    let type_param_decls: Vec<TokenStream2> =
+        input.delta_type_param_decls(quote! { deltoid::Core })?;
+    let predicates: Vec<TokenStream2> = input.dyn_delta_predicates()?;
+    let where_clause = quote! { where #(#predicates),* };
+    let field_changes: Vec<TokenStream2> = match struct_variant {
+        StructVariant::NamedStruct => fields.iter()
+            .filter(|field: &&FieldDesc| !field.ignore_field())
+            .map(|field: &FieldDesc| {
+                let fname = field.name_ref()?;
+                Ok(if field.is_flatten() {
+                    // NOTE: merge the changes of a flattened delta
+                    quote! { self.#fname.to_dyn_delta() }
+                } else {
+                    quote! {
+                        deltoid::DynDelta::field(
+                            stringify!(#fname),
+                            self.#fname.to_dyn_delta()
+                        )
+                    }
+                })
+            })
+            .collect::<DeriveResult<_>>()?,
+        StructVariant::TupleStruct => fields.iter()
+            .filter(|field: &&FieldDesc| !field.ignore_field())
+            .map(|field: &FieldDesc| {
+                let fpos = field.pos_ref()?;
+                Ok(quote! {
+                    deltoid::DynDelta::field(
+                        stringify!(#fpos),
+                        self.#fpos.to_dyn_delta()
+                    )
+                })
+            })
+            .collect::<DeriveResult<_>>()?,
+        StructVariant::UnitStruct => vec![],
+    };
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::ToDynDelta
+            for #delta_type_name<#type_args>
+            #where_clause
+        {
+            fn to_dyn_delta(&self) -> deltoid::DynDelta {
+                #[allow(unused)] use deltoid::ToDynDelta;
+                deltoid::DynDelta::changes(vec![ #(#field_changes),* ])
+            }
+        }
+    })
+}
@@ -0,0 +1,266 @@
+//! Code generation for structs marked with `#[delta(transparent)]`
+#![allow(non_snake_case)]
+
+use crate::DeriveResult;
+use crate::gen::{FieldDesc, InputType, StructVariant};
+use proc_macro2::{Ident as Ident2, TokenStream as TokenStream2};
+use quote::quote;
+use syn::*;
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+
+/// The generic parameter declarations of the impls for a transparent
+/// struct, where `inferred` is the `deltoid` trait that the impl requires.
+fn type_param_decls(input: &InputType, inferred: TokenStream2)
+                    -> DeriveResult<Vec<TokenStream2>> {
+    let in_type_param_decls: &Punctuated<GenericParam, Comma> =
+        input.type_param_decls()?;
+    Ok(in_type_param_decls.iter()
+        .map(|type_param_decl| match type_param_decl {
+            GenericParam::Lifetime(lifetime_def) => quote! { #lifetime_def },
+            GenericParam::Const(const_param)     => quote! { #const_param  },
+            GenericParam::Type(type_param) => input.type_param_decl(
+                type_param,
+                quote! {
+                    std::clone::Clone + std::fmt::Debug + std::cmp::PartialEq
+                    + #inferred + for<'de> serde::Deserialize<'de>
+                    + serde::Serialize
+                },
+            ),
+        })
+        .collect())
+}
+
+/// The where-clause of the impls for a transparent struct.
+fn where_clause(input: &InputType, inferred: TokenStream2)
+                -> DeriveResult<TokenStream2> {
+    let predicates: Vec<TokenStream2> = input.where_predicates(inferred)?;
+    Ok(quote! { where #(#predicates),* })
+}
+
+/// The only field of the transparent struct that isn't ignored.
+fn field(input: &InputType) -> DeriveResult<&FieldDesc> {
+    match input.transparent_field()? {
+        Some(field) => Ok(field),
+        None => bug_detected!(),
+    }
+}
+
+/// The name or position of a `field`, for accessing it.
+fn member(field: &FieldDesc) -> DeriveResult<TokenStream2> {
+    Ok(if field.is_named() {
+        let fname: &Ident2 = field.name_ref()?;
+        quote! { #fname }
+    } else {
+        let fpos = field.pos_ref()?;
+        quote! { #fpos }
+    })
+}
+
+/// An expression that constructs the transparent struct from the `value`
+/// of its field, and the `ignored` value of each of its ignored fields.
+fn construct(
+    input: &InputType,
+    value: TokenStream2,
+    ignored: &dyn Fn(&FieldDesc) -> DeriveResult<TokenStream2>,
+) -> DeriveResult<TokenStream2> {
+    let fields: &[FieldDesc] = input.fields()?;
+    let values: Vec<TokenStream2> = fields.iter()
+        .map(|field: &FieldDesc| if field.ignore_field() {
+            ignored(field)
+        } else {
+            Ok(value.clone())
+        })
+        .collect::<DeriveResult<_>>()?;
+    Ok(match input.struct_variant()? {
+        StructVariant::NamedStruct => {
+            let field_names: Vec<&Ident2> = fields.iter()
+                .map(|field: &FieldDesc| field.name_ref())
+                .collect::<DeriveResult<_>>()?;
+            quote! { Self { #( #field_names: #values ),* } }
+        },
+        StructVariant::TupleStruct => quote! { Self( #(#values),* ) },
+        StructVariant::UnitStruct => return bug_detected!(),
+    })
+}
+
+pub(crate) fn define_delta_type(input: &InputType) -> DeriveResult<TokenStream2> {
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let delta_vis: &Visibility = input.delta_vis()?;
+    let type_args: Punctuated<GenericArgument, Comma> =
+        input.delta_type_args()?;
+    let delta_type: TokenStream2 =
+        input.erase_lifetimes(field(input)?.delta_type())?;
+    Ok(quote! {
+        #delta_vis type #delta_type_name<#type_args> = #delta_type;
+    })
+}
+
+pub(crate) fn define_Debug_impl(_input: &InputType) -> DeriveResult<TokenStream2> {
+    // NOTE: the delta type of the field already implements `Debug`
+    Ok(quote! {})
+}
+
+pub(crate) fn define_Core_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    let type_name: &Ident2 = input.type_name()?;
+    let delta_type_name: &Ident2 = input.delta_type_name()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
+    let delta_type_args: Punctuated<GenericArgument, Comma> =
+        input.delta_type_args()?;
+    let type_param_decls: Vec<TokenStream2> =
+        type_param_decls(input, quote! { deltoid::Core })?;
+    let where_clause: TokenStream2 =
+        where_clause(input, quote! { deltoid::Core })?;
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::Core
+            for #type_name<#type_args>
+            #where_clause
+        {
+            type Delta = #delta_type_name<#delta_type_args>;
+        }
+    })
+}
+
+pub(crate) fn define_Apply_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    let type_name: &Ident2 = input.type_name()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
+    let type_param_decls: Vec<TokenStream2> =
+        type_param_decls(input, quote! { deltoid::Apply })?;
+    let where_clause: TokenStream2 =
+        where_clause(input, quote! { deltoid::Apply })?;
+    let field: &FieldDesc = field(input)?;
+    let member: TokenStream2 = member(field)?;
+    let apply: TokenStream2 =
+        field.apply_tokens(quote! { self.#member }, quote! { delta });
+    let validate: TokenStream2 =
+        field.validate_tokens(quote! { self.#member }, quote! { delta });
+    let construct: TokenStream2 = construct(
+        input,
+        quote! { #apply? },
+        &|ignored| {
+            let ignored_member: TokenStream2 = self::member(ignored)?;
+            Ok(quote! { self.#ignored_member.clone() })
+        },
+    )?;
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::Apply
+            for #type_name<#type_args>
+            #where_clause
+        {
+            #[allow(unused)]
+            fn apply(&self, delta: Self::Delta)
+                     -> deltoid::DeltaResult<Self>
+            {
+                self.apply_with(delta, &deltoid::ApplyPolicy::default())
+            }
+
+            #[allow(unused)]
+            fn apply_with(
+                &self,
+                delta: Self::Delta,
+                policy: &deltoid::ApplyPolicy,
+            ) -> deltoid::DeltaResult<Self> {
+                #[allow(unused)] use deltoid::Apply;
+                Ok(#construct)
+            }
+
+            #[allow(unused)]
+            fn validate_into(
+                &self,
+                delta: &Self::Delta,
+                errors: &mut Vec<deltoid::DeltaError>,
+            ) {
+                #[allow(unused)] use deltoid::Apply;
+                #validate
+            }
+        }
+    })
+}
+
+pub(crate) fn define_Delta_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    let type_name: &Ident2 = input.type_name()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
+    let type_param_decls: Vec<TokenStream2> =
+        type_param_decls(input, quote! { deltoid::Delta })?;
+    let where_clause: TokenStream2 =
+        where_clause(input, quote! { deltoid::Delta })?;
+    let field: &FieldDesc = field(input)?;
+    let member: TokenStream2 = member(field)?;
+    let delta: TokenStream2 =
+        field.delta_tokens(quote! { self.#member }, quote! { rhs.#member });
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::Delta
+            for #type_name<#type_args>
+            #where_clause
+        {
+            fn delta(&self, rhs: &Self) -> deltoid::DeltaResult<Self::Delta> {
+                #[allow(unused)] use deltoid::Delta;
+                #delta
+            }
+        }
+    })
+}
+
+pub(crate) fn define_FromDelta_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    let type_name: &Ident2 = input.type_name()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
+    let type_param_decls: Vec<TokenStream2> =
+        type_param_decls(input, quote! { deltoid::FromDelta })?;
+    let where_clause: TokenStream2 =
+        where_clause(input, quote! { deltoid::FromDelta })?;
+    let from_delta: TokenStream2 = field(input)?.value_from_delta_path();
+    let validate_from_delta: TokenStream2 =
+        field(input)?.validate_value_from_delta_path();
+    let construct: TokenStream2 = construct(
+        input,
+        quote! { #from_delta(delta)? },
+        &|_| Ok(quote! { Default::default() }),
+    )?;
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::FromDelta
+            for #type_name<#type_args>
+            #where_clause
+        {
+            fn from_delta(delta: Self::Delta) -> deltoid::DeltaResult<Self> {
+                #[allow(unused)] use deltoid::FromDelta;
+                Ok(#construct)
+            }
+
+            fn validate_from_delta(
+                delta: &Self::Delta,
+                errors: &mut Vec<deltoid::DeltaError>,
+            ) {
+                #validate_from_delta(delta, errors)
+            }
+        }
+    })
+}
+
+pub(crate) fn define_IntoDelta_impl(input: &InputType) -> DeriveResult<TokenStream2> {
+    let type_name: &Ident2 = input.type_name()?;
+    let type_args: &Punctuated<GenericArgument, Comma> = input.type_args()?;
+    let type_param_decls: Vec<TokenStream2> =
+        type_param_decls(input, quote! { deltoid::IntoDelta })?;
+    let where_clause: TokenStream2 =
+        where_clause(input, quote! { deltoid::IntoDelta })?;
+    let field: &FieldDesc = field(input)?;
+    let member: TokenStream2 = member(field)?;
+    let into_delta: TokenStream2 =
+        field.delta_from_value_tokens(quote! { self.#member });
+    Ok(quote! {
+        impl<#(#type_param_decls),*> deltoid::IntoDelta
+            for #type_name<#type_args>
+            #where_clause
+        {
+            fn into_delta(self) -> deltoid::DeltaResult<Self::Delta> {
+                #[allow(unused)] use deltoid::IntoDelta;
+                #into_delta
+            }
+        }
+    })
+}
+
+pub(crate) fn define_ToDynDelta_impl(_input: &InputType) -> DeriveResult<TokenStream2> {
+    // NOTE: the delta type of the field already implements `ToDynDelta`
+    Ok(quote! {})
+}
@@ -19,13 +19,107 @@ use std::path::{Path, PathBuf};
 use syn::{parse_macro_input, DeriveInput};
 
 
+/// Derive `deltoid::{Core, Apply, Delta, FromDelta, IntoDelta}` for a
+/// `struct` or `enum`, along with a `{TypeName}Delta` type.
+///
+/// For a struct with named fields, the delta type also gets a getter per
+/// field (e.g. `delta.volume() -> Option<&U8Delta>`), a `Default` impl
+/// that changes nothing, and a builder:
+/// `FooDelta::builder().volume(5).build()`.  A builder setter converts
+/// the value with `IntoDelta`, so for a collection it holds the delta that
+/// adds every element, which appends to a non-empty collection rather than
+/// replacing it.  The `*_delta` setters take a field delta as is, e.g. one
+/// calculated with `Delta::delta()`, which does replace the elements.
+/// `build()` fails if a setter failed, or if a field was set twice.
+/// Since their accessors would clash, a struct can't have fields named
+/// `builder` or `build`, nor both a field `foo` and a field `foo_delta`.
+///
+/// The delta type doesn't have the lifetime parameters of the type, since
+/// it owns its data, so a delta can outlive the values it was calculated
+/// from.  A field that borrows must therefore be a `Cow<'a, B>` whose
+/// `B::Owned` is owned, e.g. `Cow<'a, str>`, so that its delta doesn't
+/// borrow, or use `#[delta(with = "...")]`.
+/// The type parameters that such a field uses must be `'static`.
+///
+/// The generated code can be tweaked with `#[delta(...)]` attributes.
+///
+/// On the type:
+/// - `#[delta(bound = "T: Trait, ...")]` replaces the bounds that are
+///   otherwise inferred for the delta type and the generated impls.
+///   It is forwarded to serde as `#[serde(bound = "...")]`.
+/// - `#[delta(atomic)]` makes the delta type an alias of
+///   `deltoid::AtomicDelta<Self>`, which replaces a value as a whole
+///   rather than diffing its fields.  The fields then don't need to
+///   implement any `deltoid` traits.
+/// - `#[delta(remote = "path::to::Type")]` marks the type as a mirror of
+///   a type from another crate, which can't implement the `deltoid`
+///   traits itself.  The mirror must have the same fields as the remote
+///   type, which must all be public.  Besides the usual items, this
+///   generates `From` conversions between the two types and the functions
+///   of a `with` module (see below) as associated functions of `Mirror`,
+///   which fields of the remote type can use via `#[delta(remote = "...")]`.
+/// - `#[delta(transparent)]` on a struct with exactly one field that isn't
+///   ignored, e.g. a newtype, makes the delta type an alias of the delta
+///   type of that field.
+/// - `#[delta(name = "FooChange")]` names the delta type, instead of
+///   `{TypeName}Delta`.
+/// - `#[delta(vis = "pub(crate)")]` sets the visibility of the delta type,
+///   which is `pub` otherwise.
+/// - `#[delta(derive(Eq, Hash, ...))]` adds derives to the delta type.
+///   `Eq` and `Hash` are implemented whenever the deltas of all fields
+///   implement them, including for generic types.
+///   `Clone`, `Debug`, `PartialEq` and serde's traits are implemented
+///   regardless, so listing them has no effect.
+/// - `#[delta(serde(...))]` adds a `#[serde(...)]` attribute to the delta
+///   type, e.g. `#[delta(serde(rename_all = "camelCase"))]`.
+/// - `#[delta(skip_unchanged)]` leaves unchanged named fields out when a
+///   delta is serialized, and a delta without them deserializes with those
+///   fields unchanged.  This makes the deltas of sparse changes a lot
+///   smaller in self-describing formats like JSON, but breaks formats that
+///   rely on the position of fields, like bincode, which is why it's off
+///   by default.
+///
+/// `derive`, `serde` and `skip_unchanged` can't be combined with `atomic` or `transparent`,
+/// whose delta types aren't generated.
+///
+/// On a field:
+/// - `#[delta(ignore_field)]` leaves the field out of deltas.
+/// - `#[delta(atomic)]` replaces the field value as a whole, using a
+///   `deltoid::AtomicDelta` of the field type, which then doesn't need
+///   to implement any `deltoid` traits.
+/// - `#[delta(flatten)]` on a named field of a struct merges the fields of
+///   the field's delta into the delta of the struct, both when serialized
+///   and in its `DynDelta`.  The field's delta is then always present, so
+///   its type must be a delta with named fields e.g. a derived one.
+/// - `#[delta(remote = "Mirror")]` uses the delta logic of `Mirror`, a
+///   type marked with `#[delta(remote = "...")]`, for a field of the
+///   remote type.  The field's delta type is then `MirrorDelta`.
+/// - `#[delta(with = "module")]` uses custom delta logic for the field of
+///   type `F`.  `module` must define a `Delta` type that implements
+///   `Clone`, `Debug`, `PartialEq`, `ToDynDelta` and serde's traits, and:
+///   ```ignore
+///   fn apply(value: &F, delta: Delta) -> DeltaResult<F>;
+///   fn apply_with(value: &F, delta: Delta, policy: &ApplyPolicy)
+///                 -> DeltaResult<F>;
+///   fn delta(lhs: &F, rhs: &F) -> DeltaResult<Delta>;
+///   fn from_delta(delta: Delta) -> DeltaResult<F>;
+///   fn into_delta(value: F) -> DeltaResult<Delta>;
+///   fn validate_into(value: &F, delta: &Delta, errors: &mut Vec<DeltaError>);
+///   fn validate_from_delta(delta: &Delta, errors: &mut Vec<DeltaError>);
+///   ```
+///   `apply_with` is what a derived `Apply::apply_with` calls, so it
+///   should honor the `ApplyPolicy` the same way.  The `validate_*`
+///   functions push every problem that `apply` or `from_delta` would run
+///   into onto `errors`, without building a value.
+/// - `#[delta(bound = "T: Trait, ...")]` replaces the bounds that are
+///   otherwise inferred for the field's type, and is likewise forwarded
+///   to serde.  Since serde copies the bounds into its `Deserialize` impl,
+///   use `serde::de::DeserializeOwned` rather than `for<'de> Deserialize<'de>`.
 #[proc_macro_derive(Delta, attributes(delta))]
 pub fn derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let output: TokenStream2 = derive_internal(input).unwrap(
-        // This is a HACK that allows more ergonomic code for the meat of
-        // the macro while still conforming to the required macro signature.
-    );
+    let output: TokenStream2 = derive_internal(input)
+        .unwrap_or_else(|err: DeriveError| err.to_compile_error());
     TokenStream::from(output)
 }
 
@@ -39,6 +133,8 @@ fn derive_internal(input: DeriveInput) -> DeriveResult<TokenStream2> {
     let impl_Delta            = input_type.define_Delta_impl()?;
     let impl_FromDelta        = input_type.define_FromDelta_impl()?;
     let impl_IntoDelta        = input_type.define_IntoDelta_impl()?;
+    let impl_ToDynDelta       = input_type.define_ToDynDelta_impl()?;
+    let impls_remote          = input_type.define_remote_impls()?;
     let output: TokenStream2 = quote! {
         #delta_type_definition
         #impl_Debug
@@ -47,6 +143,8 @@ fn derive_internal(input: DeriveInput) -> DeriveResult<TokenStream2> {
         #impl_Delta
         #impl_FromDelta
         #impl_IntoDelta
+        #impl_ToDynDelta
+        #impls_remote
     };
 
     #[cfg(feature = "print-expansions--unstable")]
@@ -58,6 +156,8 @@ fn derive_internal(input: DeriveInput) -> DeriveResult<TokenStream2> {
         &impl_Delta,
         &impl_FromDelta,
         &impl_IntoDelta,
+        &impl_ToDynDelta,
+        &impls_remote,
     );
 
     #[cfg(feature = "dump-expansions--unstable")]
@@ -70,6 +170,8 @@ fn derive_internal(input: DeriveInput) -> DeriveResult<TokenStream2> {
         &impl_Delta,
         &impl_FromDelta,
         &impl_IntoDelta,
+        &impl_ToDynDelta,
+        &impls_remote,
     );
 
     Ok(output)
@@ -85,6 +187,8 @@ fn print_generated_code(
     impl_Delta: &TokenStream2,
     impl_FromDelta: &TokenStream2,
     impl_IntoDelta: &TokenStream2,
+    impl_ToDynDelta: &TokenStream2,
+    impls_remote: &TokenStream2,
 ) {
     println!("{}\n", delta_type_definition);
     println!("{}\n", impl_Debug);
@@ -93,6 +197,8 @@ fn print_generated_code(
     println!("{}\n", impl_Delta);
     println!("{}\n", impl_FromDelta);
     println!("{}\n", impl_IntoDelta);
+    println!("{}\n", impl_ToDynDelta);
+    println!("{}\n", impls_remote);
     println!("\n\n\n\n");
 }
 
@@ -107,6 +213,8 @@ fn write_generated_code_to_file(
     impl_Delta: &TokenStream2,
     impl_FromDelta: &TokenStream2,
     impl_IntoDelta: &TokenStream2,
+    impl_ToDynDelta: &TokenStream2,
+    impls_remote: &TokenStream2,
 ) {
     let manifest_dir: &Path = Path::new(env!("CARGO_MANIFEST_DIR"));
     let expanded_dir: PathBuf = manifest_dir.join("expanded");
@@ -145,6 +253,14 @@ fn write_generated_code_to_file(
         .expect("Failed to write impl_IntoDelta");
     file.write_all("\n\n".as_bytes()).expect("Failed to write newlines");
 
+    file.write_all(format!("{}", impl_ToDynDelta).as_bytes())
+        .expect("Failed to write impl_ToDynDelta");
+    file.write_all("\n\n".as_bytes()).expect("Failed to write newlines");
+
+    file.write_all(format!("{}", impls_remote).as_bytes())
+        .expect("Failed to write impls_remote");
+    file.write_all("\n\n".as_bytes()).expect("Failed to write newlines");
+
     file.flush().expect(&format!("Failed to flush {}", filename.display()));
     std::process::Command::new("rustfmt")
         .args(&[
@@ -0,0 +1,9 @@
+use deltoid_derive::Delta;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Foo {
+    builder: u8,
+}
+
+fn main() {}
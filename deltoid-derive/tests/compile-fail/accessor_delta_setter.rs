@@ -0,0 +1,10 @@
+use deltoid_derive::Delta;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Foo {
+    volume: u8,
+    volume_delta: u8,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use deltoid_derive::Delta;
+use serde_derive::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Foo<'a> {
+    #[delta(atomic)]
+    name: Cow<'a, str>,
+}
+
+fn main() {}
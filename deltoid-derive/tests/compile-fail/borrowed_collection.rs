@@ -0,0 +1,10 @@
+use deltoid_derive::Delta;
+use serde_derive::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Foo<'a> {
+    names: Vec<Cow<'a, str>>,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use deltoid_derive::Delta;
+
+#[derive(Clone, Debug, PartialEq, Delta)]
+pub struct Foo<const N: usize> {
+    len: usize,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use deltoid_derive::Delta;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(atomic, derive(Eq))]
+pub struct Foo {
+    f0: u8,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use deltoid_derive::Delta;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Bar { f0: u8 }
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Foo(#[delta(flatten)] Bar);
+
+fn main() {}
@@ -0,0 +1,7 @@
+use deltoid_derive::Delta;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Foo(#[delta = "ignore_field"] u8);
+
+fn main() {}
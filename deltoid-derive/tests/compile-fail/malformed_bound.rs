@@ -0,0 +1,11 @@
+use deltoid_derive::Delta;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(bound = "T: +")]
+pub struct Foo<T> {
+    #[delta(bound = 42)]
+    items: Vec<T>,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use deltoid_derive::Delta;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Foo {
+    #[delta(atomic, remote = "BarDef")]
+    f0: f64,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use deltoid_derive::Delta;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(transparent)]
+pub struct Foo {
+    f0: u8,
+    f1: u8,
+}
+
+fn main() {}
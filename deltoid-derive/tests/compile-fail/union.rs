@@ -0,0 +1,9 @@
+use deltoid_derive::Delta;
+
+#[derive(Delta)]
+pub union Foo {
+    f0: u8,
+    f1: u16,
+}
+
+fn main() {}
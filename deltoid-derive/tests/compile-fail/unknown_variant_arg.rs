@@ -0,0 +1,11 @@
+use deltoid_derive::Delta;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub enum Foo {
+    Bar(u8),
+    #[delta(ignore_field)]
+    Baz { f0: u8 },
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+//! Tests for the errors reported by `#[derive(Delta)]` on invalid input.
+
+#[test]
+fn compile_fail() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/compile-fail/*.rs");
+}
@@ -7,6 +7,7 @@
 };
 use deltoid_derive::Delta;
 use serde_derive::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt::Debug;
 
 
@@ -55,6 +56,311 @@ pub struct Baz;
 #[derive(Clone, Debug, PartialEq, Default, Delta, Deserialize, Serialize)]
 pub struct Plow(std::borrow::Cow<'static, String>);
 
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Server { host: String, port: u16 }
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Config { servers: Vec<Server> }
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Inventory<T>
+where
+    T: std::str::FromStr,
+    <T as std::str::FromStr>::Err: Debug,
+    for<'a> &'a T: Debug,
+{
+    items: Vec<T>,
+    featured: Option<T>,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub enum Either<L, R> where L: Clone, R: Clone {
+    Left(Vec<L>),
+    Right { right: Option<R> },
+    Neither,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct View<'a> { name: Cow<'a, str>, items: Vec<Server> }
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Shelf<'a, T: Clone> { label: Cow<'a, str>, top: Cow<'a, T> }
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub enum Label<'a, T> {
+    Text(Cow<'a, str>),
+    Tagged { tag: Cow<'a, str>, value: T },
+    Blank,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Meters;
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(bound = "U: Clone + Debug + PartialEq")]
+pub struct Length<U> {
+    value: u32,
+    #[delta(ignore_field)]
+    unit: std::marker::PhantomData<U>,
+}
+
+/// Diff `f64`s with a tolerance.
+mod approx {
+    use deltoid::{ApplyPolicy, DeltaError, DeltaResult, DynDelta, ToDynDelta};
+    use serde_derive::{Deserialize, Serialize};
+
+    const EPSILON: f64 = 1e-6;
+
+    #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+    pub struct Delta(pub Option<f64>);
+
+    impl ToDynDelta for Delta {
+        fn to_dyn_delta(&self) -> DynDelta {
+            match &self.0 {
+                Some(value) => DynDelta::replace(value),
+                None => DynDelta::Unchanged,
+            }
+        }
+    }
+
+    pub fn apply(value: &f64, delta: Delta) -> DeltaResult<f64> {
+        Ok(delta.0.unwrap_or(*value))
+    }
+
+    pub fn apply_with(value: &f64, delta: Delta, _policy: &ApplyPolicy)
+                      -> DeltaResult<f64> {
+        apply(value, delta)
+    }
+
+    pub fn delta(lhs: &f64, rhs: &f64) -> DeltaResult<Delta> {
+        Ok(Delta(if (lhs - rhs).abs() < EPSILON { None } else { Some(*rhs) }))
+    }
+
+    pub fn from_delta(delta: Delta) -> DeltaResult<f64> {
+        Ok(delta.0.unwrap_or_default())
+    }
+
+    pub fn into_delta(value: f64) -> DeltaResult<Delta> {
+        Ok(Delta(Some(value)))
+    }
+
+    pub fn validate_into(_value: &f64, _delta: &Delta, _errors: &mut Vec<DeltaError>) {}
+
+    pub fn validate_from_delta(_delta: &Delta, _errors: &mut Vec<DeltaError>) {}
+}
+
+/// Diff a sorted `Vec` as a set.
+mod sorted_set {
+    use deltoid::{ApplyPolicy, DeltaError, DeltaResult, DynDelta, OnFailure};
+    use deltoid::ToDynDelta;
+    use serde_derive::{Deserialize, Serialize};
+
+    #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+    pub struct Delta { pub added: Vec<u32>, pub removed: Vec<u32> }
+
+    impl ToDynDelta for Delta {
+        fn to_dyn_delta(&self) -> DynDelta {
+            DynDelta::replace(self)
+        }
+    }
+
+    pub fn apply(value: &[u32], delta: Delta) -> DeltaResult<Vec<u32>> {
+        let mut value: Vec<u32> = value.iter()
+            .filter(|item| !delta.removed.contains(item))
+            .chain(delta.added.iter())
+            .copied()
+            .collect();
+        value.sort_unstable();
+        Ok(value)
+    }
+
+    pub fn apply_with(value: &[u32], delta: Delta, policy: &ApplyPolicy)
+                      -> DeltaResult<Vec<u32>> {
+        let missing: bool = delta.removed.iter()
+            .any(|item| !value.contains(item));
+        if missing && policy.missing_set_item == OnFailure::Error {
+            return Err(DeltaError::ExpectedValue {
+                type_name: "u32".to_string(),
+                file: file!().to_string(),
+                line: line!(),
+                column: column!(),
+            });
+        }
+        apply(value, delta)
+    }
+
+    pub fn delta(lhs: &[u32], rhs: &[u32]) -> DeltaResult<Delta> {
+        Ok(Delta {
+            added: rhs.iter().filter(|i| !lhs.contains(i)).copied().collect(),
+            removed: lhs.iter().filter(|i| !rhs.contains(i)).copied().collect(),
+        })
+    }
+
+    pub fn from_delta(delta: Delta) -> DeltaResult<Vec<u32>> {
+        apply(&[], delta)
+    }
+
+    pub fn into_delta(value: Vec<u32>) -> DeltaResult<Delta> {
+        delta(&[], &value)
+    }
+
+    pub fn validate_into(_value: &[u32], _delta: &Delta, _errors: &mut Vec<DeltaError>) {}
+
+    pub fn validate_from_delta(_delta: &Delta, _errors: &mut Vec<DeltaError>) {}
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Shape {
+    name: String,
+    #[delta(with = "approx")]
+    x: f64,
+    #[delta(with = "sorted_set")]
+    tags: Vec<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub enum Geometry {
+    Point(#[delta(with = "approx")] f64, #[delta(with = "approx")] f64),
+    Tagged { #[delta(with = "sorted_set")] tags: Vec<u32> },
+}
+
+/// Doesn't implement any of the `deltoid` traits.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Rgb(u8, u8, u8);
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(atomic)]
+pub enum Cell<T> {
+    Empty,
+    Full(T),
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Pixel {
+    x: u32,
+    #[delta(atomic)]
+    color: Rgb,
+    cell: Cell<Rgb>,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Swatch {
+    #[delta(atomic)]
+    color: Option<u8>,
+}
+
+/// Stands in for a crate that doesn't implement any of the `deltoid` traits.
+pub mod geo {
+    use serde_derive::{Deserialize, Serialize};
+
+    #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+    pub struct Point { pub x: u32, pub y: u32 }
+
+    #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+    pub struct Pair<T>(pub T, pub T);
+
+    #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+    pub enum Shape {
+        Empty,
+        Line(Point, Point),
+        Circle { center: Point, radius: u32 },
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(remote = "geo::Point")]
+pub struct PointDef { x: u32, y: u32 }
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(remote = "geo::Pair")]
+pub struct PairDef<T>(T, T);
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(remote = "geo::Shape")]
+pub enum ShapeDef {
+    Empty,
+    Line(
+        #[delta(remote = "PointDef")] geo::Point,
+        #[delta(remote = "PointDef")] geo::Point,
+    ),
+    Circle { #[delta(remote = "PointDef")] center: geo::Point, radius: u32 },
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Scene {
+    #[delta(remote = "PointDef")]
+    origin: geo::Point,
+    #[delta(remote = "PairDef<String>")]
+    labels: geo::Pair<String>,
+    #[delta(remote = "ShapeDef")]
+    shape: geo::Shape,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(transparent)]
+pub struct UserId(u64);
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(transparent)]
+pub struct Annotated<T> {
+    value: T,
+    #[delta(ignore_field)]
+    note: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(skip_unchanged)]
+pub struct Timestamps {
+    created: u32,
+    updated: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub struct Record {
+    id: UserId,
+    #[delta(flatten)]
+    times: Timestamps,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(name = "SettingsChange", vis = "pub(crate)", derive(Eq, Hash))]
+#[delta(serde(rename_all = "camelCase", deny_unknown_fields))]
+pub(crate) struct Settings {
+    max_conns: u32,
+    server_name: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+// NOTE: Only `Default` is derived, since the delta type already implements
+//       the other traits:
+#[delta(derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize))]
+pub struct Volume {
+    level: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(derive(Debug, Eq, Hash, serde::Serialize))]
+pub enum Toggle<T> {
+    Off,
+    On(T),
+}
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+#[delta(derive(Clone, Eq, Hash))]
+pub struct Marker;
+
+#[derive(Clone, Debug, PartialEq, Delta, Deserialize, Serialize)]
+pub enum List<T> {
+    Cons(
+        #[delta(bound = "")]
+        T,
+        #[delta(bound = "T: Clone + PartialEq + Debug \
+                         + serde::de::DeserializeOwned + serde::Serialize")]
+        Box<List<T>>,
+    ),
+    Nil,
+}
+
 
 
 
@@ -392,3 +698,557 @@ pub fn nested_data__apply() -> DeltaResult<()>  {
     assert_eq!(val1, expected, "{:#?} != {:#?}", val1, expected);
     Ok(())
 }
+
+#[test]
+pub fn nested_data__to_dyn_delta() -> DeltaResult<()> {
+    use deltoid::{DynDelta, ToDynDelta};
+    let val0: Corge<Corge<(), bool>, ()> = Corge::Grault(
+        42u8,
+        Corge::Floof { one: 100u8, two: (), three: true }
+    );
+    let val1: Corge<Corge<(), bool>, ()> = Corge::Grault(
+        42u8,
+        Corge::Floof { one: 72u8, two: (), three: true }
+    );
+    let delta = val0.delta(&val1)?;
+    let expected = DynDelta::variant("Grault", DynDelta::Changes(vec![
+        DynDelta::field(1, DynDelta::variant("Floof", DynDelta::Changes(vec![
+            DynDelta::field("one", DynDelta::replace(&72u8)),
+        ]))),
+    ]));
+    assert_eq!(delta.to_dyn_delta(), expected);
+    Ok(())
+}
+
+#[test]
+pub fn struct__to_dyn_delta() -> DeltaResult<()> {
+    use deltoid::{DynDelta, ToDynDelta};
+    let foo0: Foo0<u8> = Foo0 { f0: (), f1: 1, f2: String::from("foo") };
+    let foo1: Foo0<u8> = Foo0 { f0: (), f1: 1, f2: String::from("bar") };
+    let delta = foo0.delta(&foo1)?;
+    assert_eq!(delta.to_dyn_delta(), DynDelta::Changes(vec![
+        DynDelta::field("f2", DynDelta::replace("bar")),
+    ]));
+    assert_eq!(foo0.delta(&foo0)?.to_dyn_delta(), DynDelta::Unchanged);
+    Ok(())
+}
+
+#[test]
+pub fn nested_data__walk() -> DeltaResult<()> {
+    use deltoid::{ChangeKind, PathSegment, Walk};
+    use deltoid::visit::format_path;
+    let val0: Corge<Foo0<u8>, ()> = Corge::Floof {
+        one: 1,
+        two: Foo0 { f0: (), f1: 2, f2: String::from("foo") },
+        three: (),
+    };
+    let val1: Corge<Foo0<u8>, ()> = Corge::Floof {
+        one: 1,
+        two: Foo0 { f0: (), f1: 3, f2: String::from("bar") },
+        three: (),
+    };
+    let mut changes: Vec<String> = vec![];
+    val0.delta(&val1)?.walk(&mut |path: &[PathSegment], change: ChangeKind| {
+        changes.push(format!("{} {}", format_path(path), change));
+    });
+    assert_eq!(changes, vec![
+        "Floof.two.f1 changed to 3",
+        "Floof.two.f2 changed to \"bar\"",
+    ]);
+    Ok(())
+}
+
+#[test]
+pub fn enum__validate() -> DeltaResult<()> {
+    use deltoid::{EltDelta, VecDelta};
+    let val0: Corge<Vec<u8>, ()> = Corge::Grault(1, vec![1, 2]);
+    let val1: Corge<Vec<u8>, ()> = Corge::Grault(2, vec![3]);
+    assert_eq!(val0.validate(&val0.delta(&val1)?), Ok(()));
+    assert_eq!(val0.validate(&val0.delta(&Corge::Quux)?), Ok(()));
+    let delta = CorgeDelta::Grault(None, Some(VecDelta(vec![
+        EltDelta::Edit { index: 3, item: 4u8.into_delta()? },
+        EltDelta::Remove { count: 5 },
+    ])));
+    assert_eq!(val0.validate(&delta).unwrap_err().len(), 2);
+    assert!(val0.apply(delta).is_err());
+    Ok(())
+}
+
+#[test]
+pub fn enum__validate__other_variant() -> DeltaResult<()> {
+    use deltoid::{EltDelta, VecDelta};
+    let val0: Corge<Vec<u8>, ()> = Corge::Quux;
+    let delta = CorgeDelta::Grault(None, Some(VecDelta(vec![
+        EltDelta::Edit { index: 0, item: 4u8.into_delta()? },
+    ])));
+    let errors = val0.validate(&delta).unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(val0.apply(delta).is_err());
+    Ok(())
+}
+
+#[test]
+pub fn struct__with__validate() -> DeltaResult<()> {
+    let val0 = Shape { name: "a".into(), x: 1.0, tags: vec![1, 2] };
+    let val1 = Shape { name: "b".into(), x: 2.0, tags: vec![2, 3] };
+    assert_eq!(val0.validate(&val0.delta(&val1)?), Ok(()));
+    let mut errors = vec![];
+    Shape::validate_from_delta(&val0.delta(&val1)?, &mut errors);
+    assert_eq!(errors.len(), 0);
+    Ok(())
+}
+
+#[test]
+pub fn enum__apply_with() -> DeltaResult<()> {
+    use deltoid::ApplyPolicy;
+    let stale: Corge<Vec<u8>, ()> = Corge::Grault(1, vec![1, 2]);
+    let delta = stale.delta(&Corge::Grault(2, vec![1, 3]))?;
+    let current: Corge<Vec<u8>, ()> = Corge::Grault(1, vec![1]);
+    assert!(current.apply(delta.clone()).is_err());
+    assert_eq!(
+        current.apply_with(delta.clone(), &ApplyPolicy::lenient())?,
+        Corge::Grault(2, vec![1])
+    );
+    assert_eq!(
+        current.apply_with(delta, &ApplyPolicy::upsert())?,
+        Corge::Grault(2, vec![1, 3])
+    );
+    Ok(())
+}
+
+#[test]
+pub fn struct__apply__error_path() -> DeltaResult<()> {
+    use deltoid::{EltDelta, U16Delta, VecDelta};
+    use deltoid::visit::format_path;
+    let server = Server { host: String::from("localhost"), port: 80 };
+    let config = Config { servers: vec![server.clone(), server] };
+    let delta = ConfigDelta {
+        servers: Some(VecDelta(vec![EltDelta::Edit {
+            index: 1,
+            item: ServerDelta { host: None, port: Some(U16Delta(None)) },
+        }])),
+    };
+    let error = config.apply(delta.clone()).unwrap_err();
+    assert_eq!(format_path(error.path()), "servers[1].port");
+    assert!(matches!(
+        error.innermost(),
+        deltoid::DeltaError::ExpectedValue { .. }
+    ));
+    let errors = config.validate(&delta).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(format_path(errors[0].path()), "servers[1].port");
+    Ok(())
+}
+
+#[test]
+pub fn struct__where_clause__roundtrip() -> DeltaResult<()> {
+    let val0: Inventory<u8> = Inventory { items: vec![1, 2], featured: None };
+    let val1: Inventory<u8> = Inventory { items: vec![2], featured: Some(2) };
+    let delta = val0.delta(&val1)?;
+    assert_eq!(val0.apply(delta)?, val1);
+    assert_eq!(Inventory::from_delta(val1.clone().into_delta()?)?, val1);
+    Ok(())
+}
+
+#[test]
+pub fn enum__where_clause__roundtrip() -> DeltaResult<()> {
+    let val0: Either<String, u8> = Either::Left(vec![String::from("a")]);
+    let val1: Either<String, u8> = Either::Right { right: Some(3) };
+    let delta = val0.delta(&val1)?;
+    assert_eq!(val0.apply(delta)?, val1);
+    let val2: Either<String, u8> = Either::Left(vec![]);
+    let delta = val0.delta(&val2)?;
+    assert_eq!(val0.apply(delta)?, val2);
+    Ok(())
+}
+
+#[test]
+pub fn struct__lifetime_param__delta_outlives_source() -> DeltaResult<()> {
+    let server = Server { host: String::from("localhost"), port: 80 };
+    let delta: ViewDelta = {
+        let name0 = String::from("before");
+        let name1 = String::from("after");
+        let view0 = View { name: Cow::Borrowed(&name0), items: vec![] };
+        let view1 = View { name: Cow::Borrowed(&name1), items: vec![server.clone()] };
+        view0.delta(&view1)?
+    };
+    let view0: View<'static> = View { name: Cow::Borrowed("before"), items: vec![] };
+    let view1 = view0.apply(delta)?;
+    assert_eq!(view1, View { name: Cow::Borrowed("after"), items: vec![server] });
+    let delta: ViewDelta = {
+        let name = String::from("renamed");
+        ViewDelta::builder().name(Cow::Borrowed(&name)).build()?
+    };
+    assert_eq!(view0.apply(delta)?.name, "renamed");
+    Ok(())
+}
+
+#[test]
+pub fn struct__lifetime_and_type_param__delta_outlives_source() -> DeltaResult<()> {
+    let delta: ShelfDelta<String> = {
+        let (top0, top1) = (String::from("books"), String::from("plants"));
+        let shelf0 = Shelf { label: Cow::Borrowed("top"), top: Cow::Borrowed(&top0) };
+        let shelf1 = Shelf { label: Cow::Borrowed("top"), top: Cow::Borrowed(&top1) };
+        shelf0.delta(&shelf1)?
+    };
+    let shelf0: Shelf<String> = Shelf {
+        label: Cow::Owned(String::from("top")),
+        top: Cow::Owned(String::from("books")),
+    };
+    assert_eq!(*shelf0.apply(delta)?.top, "plants");
+    let delta: ShelfDelta<String> = {
+        let top = String::from("games");
+        ShelfDelta::builder().top(Cow::Borrowed(&top)).build()?
+    };
+    assert_eq!(*shelf0.apply(delta)?.top, "games");
+    Ok(())
+}
+
+#[test]
+pub fn enum__lifetime_param__roundtrip() -> DeltaResult<()> {
+    let tag = String::from("tag");
+    let val0: Label<u8> = Label::Text(Cow::Borrowed("text"));
+    let val1: Label<u8> = Label::Tagged { tag: Cow::Borrowed(&tag), value: 1 };
+    let delta = val0.delta(&val1)?;
+    assert_eq!(val0.apply(delta)?, val1);
+    let val2: Label<u8> = Label::Blank;
+    assert_eq!(Label::from_delta(val2.clone().into_delta()?)?, val2);
+    Ok(())
+}
+
+#[test]
+pub fn struct__type_bound() -> DeltaResult<()> {
+    use std::marker::PhantomData;
+    let val0: Length<Meters> = Length { value: 1, unit: PhantomData };
+    let val1: Length<Meters> = Length { value: 2, unit: PhantomData };
+    let delta = val0.delta(&val1)?;
+    assert_eq!(delta, LengthDelta { value: Some(2u32.into_delta()?), unit: PhantomData });
+    assert_eq!(val0.apply(delta)?, val1);
+    Ok(())
+}
+
+#[test]
+pub fn enum__field_bound__recursive() -> DeltaResult<()> {
+    let val0: List<u8> = List::Cons(1, Box::new(List::Nil));
+    let val1: List<u8> = List::Cons(1, Box::new(List::Cons(2, Box::new(List::Nil))));
+    let delta = val0.delta(&val1)?;
+    assert_eq!(val0.apply(delta)?, val1);
+    Ok(())
+}
+
+#[test]
+pub fn struct__field_with() -> DeltaResult<()> {
+    let val0 = Shape { name: String::from("s"), x: 1.0, tags: vec![1, 2, 3] };
+    let val1 = Shape { name: String::from("s"), x: 1.0000001, tags: vec![2, 3, 4] };
+    let delta = val0.delta(&val1)?;
+    assert_eq!(delta, ShapeDelta {
+        name: None,
+        x: Some(approx::Delta(None)),
+        tags: Some(sorted_set::Delta { added: vec![4], removed: vec![1] }),
+    });
+    let val2 = val0.apply(delta)?;
+    assert_eq!(val2, Shape { name: String::from("s"), x: 1.0, tags: vec![2, 3, 4] });
+    assert_eq!(Shape::from_delta(val1.clone().into_delta()?)?, val1);
+    Ok(())
+}
+
+#[test]
+pub fn struct__field_with__apply_with() -> DeltaResult<()> {
+    use deltoid::ApplyPolicy;
+    let val0 = Shape { name: String::from("s"), x: 1.0, tags: vec![2, 3] };
+    let delta = ShapeDelta {
+        name: None,
+        x: None,
+        tags: Some(sorted_set::Delta { added: vec![4], removed: vec![1] }),
+    };
+    assert!(val0.apply_with(delta.clone(), &ApplyPolicy::strict()).is_err());
+    let val1 = val0.apply_with(delta, &ApplyPolicy::lenient())?;
+    assert_eq!(val1, Shape { name: String::from("s"), x: 1.0, tags: vec![2, 3, 4] });
+    Ok(())
+}
+
+#[test]
+pub fn enum__field_with() -> DeltaResult<()> {
+    let val0 = Geometry::Point(1.0, 2.0);
+    let val1 = Geometry::Point(1.0, 2.5);
+    let delta = val0.delta(&val1)?;
+    assert_eq!(delta, GeometryDelta::Point(None, Some(approx::Delta(Some(2.5)))));
+    assert_eq!(val0.apply(delta)?, val1);
+    let val2 = Geometry::Tagged { tags: vec![3, 1] };
+    assert_eq!(val0.apply(val0.delta(&val2)?)?, Geometry::Tagged { tags: vec![1, 3] });
+    Ok(())
+}
+
+#[test]
+pub fn struct__field_atomic() -> DeltaResult<()> {
+    let val0 = Pixel { x: 1, color: Rgb(0, 0, 0), cell: Cell::Empty };
+    let val1 = Pixel { x: 1, color: Rgb(0, 0, 255), cell: Cell::Empty };
+    let delta = val0.delta(&val1)?;
+    assert_eq!(delta, PixelDelta {
+        x: None,
+        color: Some(deltoid::AtomicDelta::Replace(Rgb(0, 0, 255))),
+        cell: None,
+    });
+    let json_string = serde_json::to_string(&delta)
+        .expect("Could not serialize to json");
+    let delta1: PixelDelta = serde_json::from_str(&json_string)
+        .expect("Could not deserialize from json");
+    assert_eq!(val0.apply(delta1)?, val1);
+    assert_eq!(Pixel::from_delta(val1.clone().into_delta()?)?, val1);
+    Ok(())
+}
+
+#[test]
+pub fn struct__field_atomic__replace_with_none() -> DeltaResult<()> {
+    let val0 = Swatch { color: Some(3) };
+    let val1 = Swatch { color: None };
+    let delta = val0.delta(&val1)?;
+    let json_string = serde_json::to_string(&delta)
+        .expect("Could not serialize to json");
+    let delta1: SwatchDelta = serde_json::from_str(&json_string)
+        .expect("Could not deserialize from json");
+    assert_eq!(delta1, delta);
+    assert_eq!(val0.apply(delta1)?, val1);
+    Ok(())
+}
+
+#[test]
+pub fn enum__type_atomic() -> DeltaResult<()> {
+    let val0: Cell<Rgb> = Cell::Full(Rgb(1, 2, 3));
+    let val1: Cell<Rgb> = Cell::Full(Rgb(1, 2, 4));
+    let delta: CellDelta<Rgb> = val0.delta(&val1)?;
+    assert_eq!(delta, deltoid::AtomicDelta::Replace(val1.clone()));
+    assert_eq!(val0.apply(delta)?, val1);
+    assert_eq!(val0.delta(&val0)?, deltoid::AtomicDelta::Unchanged);
+    assert_eq!(val0.apply(deltoid::AtomicDelta::Unchanged)?, val0);
+    assert!(Cell::<Rgb>::from_delta(deltoid::AtomicDelta::Unchanged).is_err());
+    Ok(())
+}
+
+#[test]
+pub fn struct__field_remote() -> DeltaResult<()> {
+    let val0 = Scene {
+        origin: geo::Point { x: 0, y: 0 },
+        labels: geo::Pair(String::from("a"), String::from("b")),
+        shape: geo::Shape::Empty,
+    };
+    let val1 = Scene {
+        origin: geo::Point { x: 0, y: 5 },
+        labels: geo::Pair(String::from("a"), String::from("c")),
+        shape: geo::Shape::Circle { center: geo::Point { x: 1, y: 1 }, radius: 2 },
+    };
+    let delta: SceneDelta = val0.delta(&val1)?;
+    let json_string = serde_json::to_string(&delta)
+        .expect("Could not serialize to json");
+    let delta1: SceneDelta = serde_json::from_str(&json_string)
+        .expect("Could not deserialize from json");
+    assert_eq!(delta1, delta);
+    assert_eq!(val0.apply(delta)?, val1);
+    assert_eq!(Scene::from_delta(val1.clone().into_delta()?)?, val1);
+    Ok(())
+}
+
+#[test]
+pub fn enum__type_remote() -> DeltaResult<()> {
+    let val0 = geo::Shape::Line(geo::Point { x: 0, y: 0 }, geo::Point { x: 1, y: 1 });
+    let val1 = geo::Shape::Line(geo::Point { x: 0, y: 0 }, geo::Point { x: 1, y: 2 });
+    let delta = ShapeDef::delta(&val0, &val1)?;
+    assert_eq!(delta, ShapeDefDelta::Line(
+        None,
+        Some(PointDefDelta { x: None, y: Some(2u32.into_delta()?) }),
+    ));
+    assert_eq!(ShapeDef::apply(&val0, delta)?, val1);
+    assert_eq!(ShapeDef::from_delta(ShapeDef::into_delta(val1.clone())?)?, val1);
+    assert_eq!(geo::Shape::from(ShapeDef::from(&val1)), val1);
+    Ok(())
+}
+
+#[test]
+pub fn struct__type_transparent() -> DeltaResult<()> {
+    let val0 = UserId(1);
+    let val1 = UserId(2);
+    let delta: UserIdDelta = val0.delta(&val1)?;
+    assert_eq!(delta, 1u64.delta(&2u64)?);
+    assert_eq!(val0.apply(delta)?, val1);
+    assert_eq!(UserId::from_delta(val1.clone().into_delta()?)?, val1);
+
+    let val0 = Annotated { value: vec![1u8], note: String::from("a") };
+    let val1 = Annotated { value: vec![1u8, 2], note: String::from("b") };
+    let delta: AnnotatedDelta<Vec<u8>> = val0.delta(&val1)?;
+    assert_eq!(delta, vec![1u8].delta(&vec![1u8, 2])?);
+    let val2 = val0.apply(delta)?;
+    assert_eq!(val2, Annotated { value: vec![1u8, 2], note: String::from("a") });
+    Ok(())
+}
+
+#[test]
+pub fn struct__field_flatten() -> DeltaResult<()> {
+    use deltoid::{DynDelta, ToDynDelta};
+    let val0 = Record { id: UserId(1), times: Timestamps { created: 1, updated: 1 } };
+    let val1 = Record { id: UserId(1), times: Timestamps { created: 1, updated: 5 } };
+    let delta: RecordDelta = val0.delta(&val1)?;
+    let json_string = serde_json::to_string(&delta)
+        .expect("Could not serialize to json");
+    let json: serde_json::Value = serde_json::from_str(&json_string)
+        .expect("Could not deserialize from json");
+    assert!(json.get("updated").is_some(), "{}", json_string);
+    assert!(json.get("times").is_none(), "{}", json_string);
+    let delta1: RecordDelta = serde_json::from_str(&json_string)
+        .expect("Could not deserialize from json");
+    assert_eq!(delta1, delta);
+    assert_eq!(delta.to_dyn_delta(), DynDelta::changes(vec![
+        DynDelta::field("updated", 1u32.delta(&5u32)?.to_dyn_delta()),
+    ]));
+    assert_eq!(val0.apply(delta)?, val1);
+    assert_eq!(Record::from_delta(val1.clone().into_delta()?)?, val1);
+    Ok(())
+}
+
+#[test]
+pub fn struct__delta_name_vis_derive_serde() -> DeltaResult<()> {
+    use std::collections::HashSet;
+    let val0 = Settings { max_conns: 1, server_name: String::from("a") };
+    let val1 = Settings { max_conns: 2, server_name: String::from("b") };
+    let delta: SettingsChange = val0.delta(&val1)?;
+    let json_string = serde_json::to_string(&delta)
+        .expect("Could not serialize to json");
+    assert!(json_string.contains("\"maxConns\""), "{}", json_string);
+    assert!(json_string.contains("\"serverName\""), "{}", json_string);
+    let delta1: SettingsChange = serde_json::from_str(&json_string)
+        .expect("Could not deserialize from json");
+    assert_eq!(delta1, delta);
+    let unknown: Result<SettingsChange, _> =
+        serde_json::from_str(r#"{"maxConns":null,"serverName":null,"x":1}"#);
+    assert!(unknown.is_err());
+    let mut deltas: HashSet<SettingsChange> = HashSet::new();
+    deltas.insert(delta.clone());
+    assert!(deltas.contains(&delta));
+    assert_eq!(val0.apply(delta)?, val1);
+    Ok(())
+}
+
+#[test]
+pub fn enum__delta_derive() -> DeltaResult<()> {
+    use std::collections::HashSet;
+    let val0: Toggle<u8> = Toggle::Off;
+    let val1: Toggle<u8> = Toggle::On(3);
+    let mut deltas: HashSet<ToggleDelta<u8>> = HashSet::new();
+    deltas.insert(val0.delta(&val1)?);
+    deltas.insert(val0.delta(&val1)?);
+    assert_eq!(deltas.len(), 1);
+    assert_eq!(Marker.delta(&Marker)?, MarkerDelta);
+    Ok(())
+}
+
+#[test]
+pub fn struct__serialize__bincode_roundtrip() -> DeltaResult<()> {
+    let val0 = Settings { max_conns: 1, server_name: "local".to_string() };
+    let val1 = Settings { max_conns: 2, server_name: "local".to_string() };
+    let delta: SettingsChange = val0.delta(&val1)?;
+    let bytes: Vec<u8> = bincode::serialize(&delta)
+        .expect("Could not serialize to bincode");
+    let delta1: SettingsChange = bincode::deserialize(&bytes)
+        .expect("Could not deserialize from bincode");
+    assert_eq!(delta1, delta);
+    assert_eq!(val0.apply(delta1)?, val1);
+    Ok(())
+}
+
+#[test]
+pub fn struct__serialize__skips_unchanged_fields() -> DeltaResult<()> {
+    let val0 = Timestamps { created: 1, updated: 1 };
+    let val1 = Timestamps { created: 1, updated: 2 };
+    let delta: TimestampsDelta = val0.delta(&val1)?;
+    let json_string = serde_json::to_string(&delta)
+        .expect("Could not serialize to json");
+    assert!(!json_string.contains("created"), "{}", json_string);
+    assert!(!json_string.contains("null"), "{}", json_string);
+    let delta1: TimestampsDelta = serde_json::from_str(&json_string)
+        .expect("Could not deserialize from json");
+    assert_eq!(delta1, delta);
+    // NOTE: payloads that spell out unchanged fields still deserialize
+    let updated: serde_json::Value = serde_json::to_value(&delta)
+        .expect("Could not serialize to json")["updated"].clone();
+    let json_value = serde_json::json!({ "created": null, "updated": updated });
+    let delta2: TimestampsDelta = serde_json::from_value(json_value)
+        .expect("Could not deserialize from json");
+    assert_eq!(delta2, delta);
+    assert_eq!(val0.apply(delta)?, val1);
+    Ok(())
+}
+
+#[test]
+pub fn struct__getters() -> DeltaResult<()> {
+    let val0 = Record { id: UserId(1), times: Timestamps { created: 1, updated: 1 } };
+    let val1 = Record { id: UserId(1), times: Timestamps { created: 1, updated: 5 } };
+    let delta: RecordDelta = val0.delta(&val1)?;
+    assert_eq!(delta.id(), None);
+    assert_eq!(delta.times().created(), None);
+    assert_eq!(delta.times().updated(), Some(&5u32.into_delta()?));
+    Ok(())
+}
+
+#[test]
+pub fn struct__builder() -> DeltaResult<()> {
+    let val0 = Record { id: UserId(1), times: Timestamps { created: 1, updated: 1 } };
+    let val1 = Record { id: UserId(1), times: Timestamps { created: 1, updated: 5 } };
+    let times = TimestampsDelta::builder().updated(5).build()?;
+    let delta = RecordDelta::builder().times_delta(times).build()?;
+    assert_eq!(delta, val0.delta(&val1)?);
+    assert_eq!(val0.apply(delta)?, val1);
+    assert_eq!(RecordDelta::default(), val0.delta(&val0)?);
+    let delta = SettingsChange::builder()
+        .max_conns(10)
+        .server_name_delta("db".to_string().into_delta()?)
+        .build()?;
+    let val = Settings { max_conns: 1, server_name: "local".to_string() };
+    let expected = Settings { max_conns: 10, server_name: "db".to_string() };
+    assert_eq!(val.apply(delta)?, expected);
+    Ok(())
+}
+
+#[test]
+pub fn struct__builder__derive_default() -> DeltaResult<()> {
+    let val0 = Volume { level: 1 };
+    assert_eq!(VolumeDelta::default(), val0.delta(&val0)?);
+    let delta = VolumeDelta::builder().level(5).build()?;
+    assert_eq!(val0.apply(delta)?, Volume { level: 5 });
+    Ok(())
+}
+
+#[test]
+pub fn struct__builder__collection_field() -> DeltaResult<()> {
+    let a = Server { host: "a".to_string(), port: 1 };
+    let b = Server { host: "b".to_string(), port: 2 };
+    let val0 = Config { servers: vec![a.clone()] };
+    let val1 = Config { servers: vec![b.clone()] };
+    // NOTE: a value setter adds every element rather than replacing them
+    let delta = ConfigDelta::builder().servers(vec![b.clone()]).build()?;
+    assert_eq!(val0.apply(delta)?, Config { servers: vec![a, b] });
+    let servers = val0.servers.delta(&val1.servers)?;
+    let delta = ConfigDelta::builder().servers_delta(servers).build()?;
+    assert_eq!(val0.apply(delta)?, val1);
+    Ok(())
+}
+
+#[test]
+pub fn struct__builder__field_set_twice() -> DeltaResult<()> {
+    let result = TimestampsDelta::builder().created(1).created(2).build();
+    let error = result.unwrap_err();
+    assert!(matches!(
+        error.innermost(),
+        deltoid::DeltaError::FailedToEnsure { .. }
+    ));
+    let result = TimestampsDelta::builder()
+        .updated(1)
+        .updated_delta(2u32.into_delta()?)
+        .build();
+    assert!(result.is_err());
+    let times = TimestampsDelta::builder().updated(5).build()?;
+    let result = RecordDelta::builder()
+        .times_delta(times.clone())
+        .times_delta(times)
+        .build();
+    assert!(result.is_err());
+    Ok(())
+}
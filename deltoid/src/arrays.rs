@@ -1,6 +1,8 @@
 //!
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{ApplyPolicy, PathSegment};
+use crate::{DynDelta, ToDynDelta};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 use std::mem::{self, MaybeUninit};
@@ -19,6 +21,12 @@ where T: Apply + FromDelta
     + Serialize
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
         let mut new: [MaybeUninit<T>; LEN] =
             unsafe { MaybeUninit::uninit().assume_init() };
         let initialized: Vec<usize> = delta.0.iter()
@@ -26,7 +34,9 @@ where T: Apply + FromDelta
             .collect();
         // NOTE: initialize the delta `new[index]` cells:
         for Edit { delta: d, index } in delta.0 {
-            new[index] = MaybeUninit::new(self[index].apply(d)?);
+            let elt: T = self[index].apply_with(d, policy)
+                .map_err(|e| e.at(PathSegment::Index(index)))?;
+            new[index] = MaybeUninit::new(elt);
         }
         // NOTE: initialize the non-delta `new[index]` cells:
         for index in 0 .. LEN {
@@ -37,6 +47,17 @@ where T: Apply + FromDelta
         }
         Ok(unsafe { array_assume_init(new) })
     }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        for Edit { delta: d, index } in delta.0.iter() {
+            let start: usize = errors.len();
+            match ensure_lt![*index, LEN] {
+                Ok(()) => self[*index].validate_into(d, errors),
+                Err(error) => errors.push(error),
+            }
+            DeltaError::scope_all(errors, start, &PathSegment::Index(*index));
+        }
+    }
 }
 
 impl<T, const LEN: usize> Delta for [T; LEN]
@@ -71,7 +92,9 @@ where T: Clone + Debug + PartialEq + FromDelta
             .collect();
         // NOTE: initialize the delta `new[index]` cells:
         for Edit { delta: d, index } in delta.0 {
-            new[index] = MaybeUninit::new(<T>::from_delta(d)?);
+            let elt: T = <T>::from_delta(d)
+                .map_err(|e| e.at(PathSegment::Index(index)))?;
+            new[index] = MaybeUninit::new(elt);
         }
         // NOTE: initialize the non-delta `new[index]` cells:
         for index in 0 .. LEN {
@@ -114,6 +137,15 @@ pub struct Edit<T: Core> {
     index: usize,
 }
 
+impl<T: Core, const LEN: usize> ToDynDelta for ArrayDelta<T, LEN>
+where T::Delta: ToDynDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        DynDelta::changes(self.0.iter()
+            .map(|edit| DynDelta::index(edit.index, edit.delta.to_dyn_delta()))
+            .collect())
+    }
+}
+
 #[inline(never)]
 unsafe fn array_assume_init<T, const N: usize>(
     array: [MaybeUninit<T>; N]
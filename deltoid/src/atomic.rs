@@ -0,0 +1,136 @@
+//! Deltas that replace a value as a whole rather than diffing its parts.
+//!
+//! This is what `#[delta(atomic)]` uses: on a field, the field is routed
+//! through the functions in this module, and on a type, the type's delta
+//! is an [`AtomicDelta`] of the type itself.  The functions can also be
+//! used to hand-write the deltoid impls of a type that has no finer deltas.
+
+use crate::{ApplyPolicy, DeltaError, DeltaResult, DynDelta, ToDynDelta};
+
+
+/// A delta that either leaves a value unchanged or replaces it entirely.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub enum AtomicDelta<T> {
+    Unchanged,
+    Replace(T),
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for AtomicDelta<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            Self::Unchanged      => write!(f, "AtomicDelta::Unchanged"),
+            Self::Replace(value) => write!(f, "AtomicDelta::Replace({:#?})", value),
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> ToDynDelta for AtomicDelta<T> {
+    fn to_dyn_delta(&self) -> DynDelta {
+        match self {
+            Self::Unchanged      => DynDelta::Unchanged,
+            Self::Replace(value) => DynDelta::replace(value),
+        }
+    }
+}
+
+
+/// Apply `delta` to `value`, i.e. replace `value` if `delta` has a value.
+pub fn apply<T: Clone>(value: &T, delta: AtomicDelta<T>) -> DeltaResult<T> {
+    Ok(match delta {
+        AtomicDelta::Unchanged      => value.clone(),
+        AtomicDelta::Replace(value) => value,
+    })
+}
+
+/// Apply `delta` to `value` like [`apply`] does.  The `policy` doesn't
+/// come into play, since applying an [`AtomicDelta`] can't fail.
+pub fn apply_with<T: Clone>(
+    value: &T,
+    delta: AtomicDelta<T>,
+    _policy: &ApplyPolicy,
+) -> DeltaResult<T> {
+    apply(value, delta)
+}
+
+/// Calculate the delta that replaces `lhs` with `rhs`, if they differ.
+pub fn delta<T>(lhs: &T, rhs: &T) -> DeltaResult<AtomicDelta<T>>
+where T: Clone + PartialEq {
+    Ok(if lhs == rhs {
+        AtomicDelta::Unchanged
+    } else {
+        AtomicDelta::Replace(rhs.clone())
+    })
+}
+
+/// Convert `delta` to the value it replaces with.
+pub fn from_delta<T>(delta: AtomicDelta<T>) -> DeltaResult<T> {
+    match delta {
+        AtomicDelta::Unchanged      => Err(ExpectedValue!("AtomicDelta<T>")),
+        AtomicDelta::Replace(value) => Ok(value),
+    }
+}
+
+/// Convert `value` to a delta that replaces with it.
+pub fn into_delta<T>(value: T) -> DeltaResult<AtomicDelta<T>> {
+    Ok(AtomicDelta::Replace(value))
+}
+
+/// Push every problem with applying `delta` to `value` onto `errors`,
+/// of which there are none, since any value can be replaced.
+pub fn validate_into<T>(
+    _value: &T,
+    _delta: &AtomicDelta<T>,
+    _errors: &mut Vec<DeltaError>,
+) {
+}
+
+/// Push every problem with converting `delta` to a value onto `errors`.
+pub fn validate_from_delta<T>(
+    delta: &AtomicDelta<T>,
+    errors: &mut Vec<DeltaError>,
+) {
+    if let AtomicDelta::Unchanged = delta {
+        errors.push(ExpectedValue!("AtomicDelta<T>"));
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use super::*;
+
+    #[test]
+    fn AtomicDelta__roundtrip() -> DeltaResult<()> {
+        let lhs: (u8, u8) = (1, 2);
+        let rhs: (u8, u8) = (1, 3);
+        assert_eq!(delta(&lhs, &lhs)?, AtomicDelta::Unchanged);
+        let delta: AtomicDelta<(u8, u8)> = delta(&lhs, &rhs)?;
+        let json_string = serde_json::to_string(&delta)
+            .expect("Could not serialize to json");
+        assert_eq!(json_string, r#"{"Replace":[1,3]}"#);
+        let delta1: AtomicDelta<(u8, u8)> = serde_json::from_str(&json_string)
+            .expect("Could not deserialize from json");
+        assert_eq!(apply(&lhs, delta1)?, rhs);
+        assert!(from_delta::<(u8, u8)>(AtomicDelta::Unchanged).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn AtomicDelta__roundtrip__replace_with_none() -> DeltaResult<()> {
+        let lhs: Option<u8> = Some(3);
+        let rhs: Option<u8> = None;
+        let delta: AtomicDelta<Option<u8>> = delta(&lhs, &rhs)?;
+        assert_eq!(delta, AtomicDelta::Replace(None));
+        let json_string = serde_json::to_string(&delta)
+            .expect("Could not serialize to json");
+        let delta1: AtomicDelta<Option<u8>> = serde_json::from_str(&json_string)
+            .expect("Could not deserialize from json");
+        assert_eq!(delta1, delta);
+        assert_eq!(apply(&lhs, delta1)?, rhs);
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 //!
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{ApplyPolicy, DynDelta, ToDynDelta};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de;
 use serde::ser::SerializeMap;
@@ -14,7 +15,7 @@ where B: Clone + Debug + PartialEq + Core + ToOwned
     + for<'de> Deserialize<'de>
     + Serialize
 {
-    type Delta = CowDelta<'a, B>;
+    type Delta = CowDelta<B>;
 }
 
 impl<'a, B> Apply for Cow<'a, B>
@@ -24,13 +25,25 @@ where B: Apply + FromDelta + ToOwned
       <B as ToOwned>::Owned: Debug
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
         let lhs: &B = self.borrow();
         Ok(if let Some(delta) = delta.inner {
-            Cow::Owned(lhs.apply(delta)?.to_owned())
+            Cow::Owned(lhs.apply_with(delta, policy)?.to_owned())
         } else {
             self.clone()
         })
     }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if let Some(delta) = &delta.inner {
+            (self.borrow() as &B).validate_into(delta, errors);
+        }
+    }
 }
 
 impl<'a, B> Delta for Cow<'a, B>
@@ -59,9 +72,16 @@ where B: Clone + Debug + PartialEq + FromDelta
 {
     fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
         let delta: B::Delta = delta.inner
-            .ok_or_else(|| ExpectedValue!("CowDelta<'a, B>"))?;
+            .ok_or_else(|| ExpectedValue!("CowDelta<B>"))?;
         Ok(Cow::Owned(<B>::from_delta(delta)?.to_owned()))
     }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        match &delta.inner {
+            Some(delta) => <B>::validate_from_delta(delta, errors),
+            None => errors.push(ExpectedValue!("CowDelta<B>")),
+        }
+    }
 }
 
 impl<'a, B> IntoDelta for Cow<'a, B>
@@ -79,13 +99,15 @@ where B: Clone + Debug + PartialEq + IntoDelta
 
 
 
+/// The delta of a `Cow<'a, B>`, which owns its data, so that it can
+/// outlive the value that it was calculated from.
 #[derive(Clone, PartialEq)]
-pub struct CowDelta<'a, B: Core> {
+pub struct CowDelta<B: Core> {
     #[doc(hidden)] pub inner: Option<B::Delta>,
-    #[doc(hidden)] pub _phantom: PhantomData<&'a B>
+    #[doc(hidden)] pub _phantom: PhantomData<B>
 }
 
-impl<'a, B: Core> std::fmt::Debug for CowDelta<'a, B> {
+impl<B: Core> std::fmt::Debug for CowDelta<B> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         match &self.inner {
             Some(d) => write!(f, "CowDelta({:#?})", d),
@@ -94,7 +116,14 @@ impl<'a, B: Core> std::fmt::Debug for CowDelta<'a, B> {
     }
 }
 
-impl<'a, B> Serialize for CowDelta<'a, B>
+impl<B: Core> ToDynDelta for CowDelta<B>
+where B::Delta: ToDynDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        self.inner.to_dyn_delta()
+    }
+}
+
+impl<B> Serialize for CowDelta<B>
 where B: Core + for<'de> Deserialize<'de> + Serialize {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where S: Serializer {
@@ -108,15 +137,15 @@ where B: Core + for<'de> Deserialize<'de> + Serialize {
     }
 }
 
-impl<'de, 'a, B> Deserialize<'de> for CowDelta<'a, B>
+impl<'de, B> Deserialize<'de> for CowDelta<B>
 where B: Core + Deserialize<'de> + Serialize {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where D: Deserializer<'de> {
-        struct DeltaVisitor<'a2, B2>(PhantomData<&'a2 B2>);
+        struct DeltaVisitor<B2>(PhantomData<B2>);
 
-        impl<'de, 'a2, B2> de::Visitor<'de> for DeltaVisitor<'a2, B2>
+        impl<'de, B2> de::Visitor<'de> for DeltaVisitor<B2>
         where B2: Core + Deserialize<'de> + Serialize {
-            type Value = CowDelta<'a2, B2>;
+            type Value = CowDelta<B2>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                 formatter.write_str("a CowDelta")
@@ -3,7 +3,9 @@
 //!
 //! [`Box`]: https://doc.rust-lang.org/std/boxed/struct.Box.html
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::ApplyPolicy;
+use crate::{DynDelta, ToDynDelta};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de;
 use serde::ser::SerializeMap;
@@ -25,10 +27,22 @@ where T: Apply
     + Serialize
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
         let lhs: &T = self.as_ref();
         match delta.0 {
             None => Ok(self.clone()),
-            Some(delta) => Ok(Box::new(lhs.apply(*delta)?)),
+            Some(delta) => Ok(Box::new(lhs.apply_with(*delta, policy)?)),
+        }
+    }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if let Some(delta) = &delta.0 {
+            self.as_ref().validate_into(delta, errors);
         }
     }
 }
@@ -89,6 +103,13 @@ impl<T: Core> std::fmt::Debug for BoxDelta<T> {
     }
 }
 
+impl<T: Core> ToDynDelta for BoxDelta<T>
+where T::Delta: ToDynDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        self.0.to_dyn_delta()
+    }
+}
+
 impl<T> Serialize for BoxDelta<T>
 where T: Core
     + for<'de> Deserialize<'de>
@@ -4,6 +4,8 @@
 //! [`BtreeMap`]: https://doc.rust-lang.org/std/collections/struct.BTreeMap.html
 
 use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{ApplyPolicy, OnFailure, PathSegment};
+use crate::{DynDelta, ToDynDelta};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeSet, BTreeMap};
 use std::fmt::Debug;
@@ -29,20 +31,43 @@ where K: Clone + Debug + PartialEq + Ord + Apply
     + Serialize,
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
         let mut new: Self = self.clone();
         for change in delta.into_iter() { match change {
             EntryDelta::Edit { key, value } => {
-                let place: &mut V = &mut *new.get_mut(&key)
-                    .ok_or_else(|| ExpectedValue!("BTreeMapDelta<K, V>"))?;
-                *place = <V>::from_delta(value)?;
+                let at = |e: DeltaError| e.at(PathSegment::key(&key));
+                match new.get_mut(&key) {
+                    Some(place) =>
+                        *place = place.apply_with(value, policy).map_err(at)?,
+                    None => match policy.missing_map_key {
+                        OnFailure::Error =>
+                            return Err(at(ExpectedValue!("BTreeMapDelta<K, V>"))),
+                        OnFailure::Skip => {},
+                        OnFailure::Upsert => {
+                            let value: V = <V>::from_delta(value).map_err(at)?;
+                            new.insert(key, value);
+                        },
+                    },
+                }
             },
             EntryDelta::Add { key, value } => {
-                new.insert(key, <V>::from_delta(value)?);
+                let value: V = <V>::from_delta(value)
+                    .map_err(|e| e.at(PathSegment::key(&key)))?;
+                new.insert(key, value);
             },
             EntryDelta::Remove { key } =>  { new.remove(&key); },
         }}
         Ok(new)
     }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        delta.validate_into(|key| self.get(key), errors);
+    }
 }
 
 impl<K, V> Delta for BTreeMap<K, V>
@@ -96,14 +121,21 @@ where K: Clone + Debug + PartialEq + Ord + FromDelta
         if let Some(delta) = delta.0 {
             for (index, element) in delta.into_iter().enumerate() {
                 match element {
-                    EntryDelta::Add { key, value } =>
-                        map.insert(key, <V>::from_delta(value)?),
+                    EntryDelta::Add { key, value } => {
+                        let value: V = <V>::from_delta(value)
+                            .map_err(|e| e.at(PathSegment::key(&key)))?;
+                        map.insert(key, value)
+                    },
                     _ => return Err(DeltaError::IllegalDelta { index })?,
                 };
             }
         }
         Ok(map)
     }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        delta.validate_from_delta(errors);
+    }
 }
 
 impl<K, V> IntoDelta for BTreeMap<K, V>
@@ -127,6 +159,70 @@ where K: Clone + Debug + PartialEq + Ord + IntoDelta
     }
 }
 
+impl<K, V> BTreeMapDelta<K, V>
+where K: Core + Debug + PartialEq,
+      V: Apply + FromDelta
+{
+    /// Push every problem with applying `self` to a map onto `errors`, for
+    /// `BTreeMap<K, V>` and the other maps that use a `BTreeMapDelta<K, V>`.
+    /// `get` returns the value of a key in the original map.
+    pub(crate) fn validate_into<'v>(
+        &self,
+        get: impl Fn(&K) -> Option<&'v V>,
+        errors: &mut Vec<DeltaError>,
+    ) where V: 'v {
+        let changes: &[EntryDelta<K, V>] = self.0.as_deref().unwrap_or(&[]);
+        for (index, change) in changes.iter().enumerate() { match change {
+            EntryDelta::Edit { key, value } => {
+                // NOTE: the key must be present at that point of `apply()`.
+                //       A value that an earlier change replaced is only
+                //       known by applying the delta, so edits of it only
+                //       check the key:
+                let start: usize = errors.len();
+                match changes[..index].iter().rev().find(|change| match change {
+                    EntryDelta::Add { key: k, .. }
+                        | EntryDelta::Edit { key: k, .. }
+                        | EntryDelta::Remove { key: k } => k == key,
+                }) {
+                    Some(EntryDelta::Remove { .. }) =>
+                        errors.push(ExpectedValue!("BTreeMapDelta<K, V>")),
+                    Some(_) => {},
+                    None => match get(key) {
+                        Some(place) => place.validate_into(value, errors),
+                        None => errors.push(ExpectedValue!("BTreeMapDelta<K, V>")),
+                    },
+                }
+                DeltaError::scope_all(errors, start, &PathSegment::key(key));
+            },
+            EntryDelta::Add { key, value } => {
+                let start: usize = errors.len();
+                <V>::validate_from_delta(value, errors);
+                DeltaError::scope_all(errors, start, &PathSegment::key(key));
+            },
+            EntryDelta::Remove { .. } => {},
+        }}
+    }
+}
+
+impl<K, V> BTreeMapDelta<K, V>
+where K: Core + Debug + PartialEq,
+      V: FromDelta
+{
+    /// Push every problem with converting `self` to a map onto `errors`,
+    /// for `BTreeMap<K, V>` and the other maps that use a `BTreeMapDelta<K, V>`.
+    pub(crate) fn validate_from_delta(&self, errors: &mut Vec<DeltaError>) {
+        let changes: &[EntryDelta<K, V>] = self.0.as_deref().unwrap_or(&[]);
+        for (index, change) in changes.iter().enumerate() { match change {
+            EntryDelta::Add { key, value } => {
+                let start: usize = errors.len();
+                <V>::validate_from_delta(value, errors);
+                DeltaError::scope_all(errors, start, &PathSegment::key(key));
+            },
+            _ => errors.push(DeltaError::IllegalDelta { index }),
+        }}
+    }
+}
+
 
 
 
@@ -183,6 +279,18 @@ where K: std::fmt::Debug + Core,
     }
 }
 
+impl<K, V> ToDynDelta for BTreeMapDelta<K, V>
+where K: std::fmt::Debug + Core,
+      V: Core,
+      V::Delta: ToDynDelta,
+{
+    fn to_dyn_delta(&self) -> DynDelta {
+        DynDelta::changes(self.0.iter().flatten()
+            .map(ToDynDelta::to_dyn_delta)
+            .collect())
+    }
+}
+
 
 
 #[derive(Clone, PartialEq)]
@@ -217,6 +325,22 @@ where K: std::fmt::Debug,
     }
 }
 
+impl<K, V> ToDynDelta for EntryDelta<K, V>
+where K: std::fmt::Debug,
+      V: Core,
+      V::Delta: ToDynDelta,
+{
+    fn to_dyn_delta(&self) -> DynDelta {
+        match &self {
+            Self::Edit { key, value } =>
+                DynDelta::key(key, value.to_dyn_delta()),
+            Self::Add { key, value } =>
+                DynDelta::insert(key, value.to_dyn_delta()),
+            Self::Remove { key } => DynDelta::key(key, DynDelta::Remove),
+        }
+    }
+}
+
 
 
 
@@ -348,4 +472,36 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn BTreeMap__apply__compound_values() -> DeltaResult<()> {
+        let map0: BTreeMap<String, (u8, String)> = map! {
+            "foo".into() => (1, "one".into()),
+            "bar".into() => (2, "two".into()),
+        };
+        let map1: BTreeMap<String, (u8, String)> = map! {
+            "foo".into() => (10, "one".into()),
+            "bar".into() => (2, "two".into()),
+        };
+        let delta = map0.delta(&map1)?;
+        assert_eq!(map0.validate(&delta), Ok(()));
+        assert_eq!(map0.apply(delta)?, map1);
+        Ok(())
+    }
+
+    #[test]
+    fn BTreeMap__validate() -> DeltaResult<()> {
+        let map0: BTreeMap<String, usize> = map! { "foo".into() => 42 };
+        let map1: BTreeMap<String, usize> = map! { "bar".into() => 1 };
+        let delta = map0.delta(&map1)?;
+        assert_eq!(map0.validate(&delta), Ok(()));
+        let delta = BTreeMapDelta(Some(vec![
+            EntryDelta::Remove { key: "foo".into() },
+            EntryDelta::Edit { key: "foo".into(), value: 1usize.into_delta()? },
+            EntryDelta::Edit { key: "baz".into(), value: 2usize.into_delta()? },
+        ]));
+        assert_eq!(map0.validate(&delta).unwrap_err().len(), 2);
+        assert!(map0.apply(delta).is_err());
+        Ok(())
+    }
+
 }
@@ -3,7 +3,9 @@
 //!
 //! [`BTreeSet`]: https://doc.rust-lang.org/std/collections/struct.BTreeSet.html
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{ApplyPolicy, OnFailure};
+use crate::{DynDelta, ToDynDelta};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
 use std::fmt::Debug;
@@ -23,6 +25,12 @@ where T: Clone + Debug + PartialEq + Ord + Apply + FromDelta
     + Serialize,
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
         match delta.0 {
             None => Ok(self.clone()),
             Some(entry_deltas) => {
@@ -32,13 +40,23 @@ where T: Clone + Debug + PartialEq + Ord + Apply + FromDelta
                         new.insert(<T>::from_delta(item)?);
                     },
                     EntryDelta::Remove { item } => {
-                        new.remove(&(<T>::from_delta(item)?));
+                        let removed = new.remove(&(<T>::from_delta(item)?));
+                        if !removed && policy.missing_set_item == OnFailure::Error {
+                            return Err(ExpectedValue!("BTreeSetDelta<T>"));
+                        }
                     },
                 }}
                 Ok(new)
             },
         }
     }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        for entry_delta in delta.0.iter().flatten() { match entry_delta {
+            EntryDelta::Add { item } | EntryDelta::Remove { item } =>
+                <T>::validate_from_delta(item, errors),
+        }}
+    }
 }
 
 impl<T> Delta for BTreeSet<T>
@@ -155,6 +173,15 @@ where T: std::fmt::Debug + Core {
     }
 }
 
+impl<T: Core> ToDynDelta for BTreeSetDelta<T>
+where T::Delta: ToDynDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        DynDelta::changes(self.0.iter().flatten()
+            .map(ToDynDelta::to_dyn_delta)
+            .collect())
+    }
+}
+
 
 
 #[derive(Clone, PartialEq)]
@@ -178,6 +205,17 @@ where T: std::fmt::Debug + Core {
     }
 }
 
+impl<T: Core> ToDynDelta for EntryDelta<T>
+where T::Delta: ToDynDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        match &self {
+            Self::Add { item } => DynDelta::SetAdd(Box::new(item.to_dyn_delta())),
+            Self::Remove { item } =>
+                DynDelta::SetRemove(Box::new(item.to_dyn_delta())),
+        }
+    }
+}
+
 
 
 #[allow(non_snake_case)]
@@ -3,7 +3,9 @@
 //!
 //! [`HashSet`]: https://doc.rust-lang.org/std/collections/struct.HashSet.html
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{ApplyPolicy, OnFailure};
+use crate::{DynDelta, ToDynDelta};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fmt::Debug;
@@ -24,6 +26,12 @@ where T: Clone + Debug + PartialEq + Ord + Hash + Apply + FromDelta
     + Serialize,
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
         match delta.0 {
             None => Ok(self.clone()),
             Some(entry_deltas) => {
@@ -33,13 +41,23 @@ where T: Clone + Debug + PartialEq + Ord + Hash + Apply + FromDelta
                         new.insert(<T>::from_delta(item)?);
                     },
                     EntryDelta::Remove { item } => {
-                        new.remove(&(<T>::from_delta(item)?));
+                        let removed = new.remove(&(<T>::from_delta(item)?));
+                        if !removed && policy.missing_set_item == OnFailure::Error {
+                            return Err(ExpectedValue!("HashSetDelta<T>"));
+                        }
                     },
                 }}
                 Ok(new)
             },
         }
     }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        for entry_delta in delta.0.iter().flatten() { match entry_delta {
+            EntryDelta::Add { item } | EntryDelta::Remove { item } =>
+                <T>::validate_from_delta(item, errors),
+        }}
+    }
 }
 
 impl<T> Delta for HashSet<T>
@@ -156,6 +174,15 @@ where T: std::fmt::Debug + Core {
     }
 }
 
+impl<T: Core> ToDynDelta for HashSetDelta<T>
+where T::Delta: ToDynDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        DynDelta::changes(self.0.iter().flatten()
+            .map(ToDynDelta::to_dyn_delta)
+            .collect())
+    }
+}
+
 
 
 #[derive(Clone, PartialEq)]
@@ -179,6 +206,17 @@ where T: std::fmt::Debug + Core {
     }
 }
 
+impl<T: Core> ToDynDelta for EntryDelta<T>
+where T::Delta: ToDynDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        match &self {
+            Self::Add { item } => DynDelta::SetAdd(Box::new(item.to_dyn_delta())),
+            Self::Remove { item } =>
+                DynDelta::SetRemove(Box::new(item.to_dyn_delta())),
+        }
+    }
+}
+
 
 
 
@@ -304,4 +342,16 @@ mod tests {
         assert_eq!(set1, set2);
         Ok(())
     }
+
+    #[test]
+    fn HashSet__apply_with__missing_set_item() -> DeltaResult<()> {
+        let set0: HashSet<u8> = set! { 1 };
+        let delta = HashSetDelta(Some(vec![
+            EntryDelta::Remove { item: 2u8.into_delta()? },
+        ]));
+        assert_eq!(set0.apply(delta.clone())?, set0);
+        assert!(set0.apply_with(delta.clone(), &ApplyPolicy::strict()).is_err());
+        assert_eq!(set0.apply_with(delta, &ApplyPolicy::upsert())?, set0);
+        Ok(())
+    }
 }
@@ -1,8 +1,8 @@
 //!
 
-mod btreemap;
+pub(crate) mod btreemap;
 mod btreeset;
-mod hashmap;
+pub(crate) mod hashmap;
 mod hashset;
 mod vecdeque;
 
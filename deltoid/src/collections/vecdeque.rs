@@ -4,6 +4,9 @@
 //! [`VecDeque`]: https://doc.rust-lang.org/std/collections/struct.VecDeque.html
 
 use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{ApplyPolicy, PathSegment};
+use crate::{DynDelta, ToDynDelta};
+use crate::vec::{self, VecDelta};
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::fmt::Debug;
@@ -24,21 +27,60 @@ where T: Clone + Debug + PartialEq + Ord + Apply + FromDelta
     + Serialize,
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
-        let mut new: Self = self.clone();
-        for change in delta.into_iter() { match change {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
+        // NOTE: the changes are those of a `VecDelta<T>` in all but name:
+        let delta: VecDelta<T> = VecDelta(delta.0.into_iter()
+            .map(|change| match change {
+                EltDelta::Edit { index, item } =>
+                    vec::EltDelta::Edit { index, item },
+                EltDelta::Add(item) => vec::EltDelta::Add(item),
+                EltDelta::Remove { count } => vec::EltDelta::Remove { count },
+            })
+            .collect());
+        delta.apply_to(
+            self,
+            policy,
+            |deque| deque.len(),
+            |deque, index| &deque[index],
+            |deque, index, item| deque[index] = item,
+            |deque, item| { deque.push_back(item); Ok(()) },
+            |deque| deque.pop_back().is_some(),
+        )
+    }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        let mut len: usize = self.len();
+        for change in delta.iter() { match change {
             EltDelta::Edit { index, item } => {
-                // NOTE: If self.len() == 0, the Edit should have been an Add:
-                ensure_gt![self.len(), 0]?;
-                // NOTE: Ensure index is within bounds:
-                ensure_lt![index, self.len()]?;
-                new[index] = self[index].apply(item)?;
+                // NOTE: `apply()` edits the element of `self` at `index`,
+                //       which must also still be present at that point:
+                let start: usize = errors.len();
+                match ensure_lt![*index, usize::min(self.len(), len)] {
+                    Ok(()) => self[*index].validate_into(item, errors),
+                    Err(error) => errors.push(error),
+                }
+                let segment = PathSegment::Index(*index);
+                DeltaError::scope_all(errors, start, &segment);
             },
-            EltDelta::Add(delta) =>  new.push_back(<T>::from_delta(delta)?),
-            EltDelta::Remove { count } =>  for _ in 0 .. count {
-                new.pop_back().ok_or_else(|| ExpectedValue!("VecDelta<T>"))?;
+            EltDelta::Add(item) => {
+                let start: usize = errors.len();
+                <T>::validate_from_delta(item, errors);
+                let segment = PathSegment::Index(len);
+                DeltaError::scope_all(errors, start, &segment);
+                len += 1;
+            },
+            EltDelta::Remove { count } => {
+                if *count > len {
+                    errors.push(ExpectedValue!("VecDelta<T>"));
+                }
+                len = len.saturating_sub(*count);
             },
         }}
-        Ok(new)
     }
 }
 
@@ -79,12 +121,27 @@ where T: Clone + Debug + PartialEq + Ord + FromDelta
         let mut changes: VecDeque<T> = VecDeque::new();
         for (index, element) in delta.0.into_iter().enumerate() {
             match element {
-                EltDelta::Add(elt) => changes.push_back(<T>::from_delta(elt)?),
+                EltDelta::Add(elt) => changes.push_back(
+                    <T>::from_delta(elt)
+                        .map_err(|e| e.at(PathSegment::Index(index)))?
+                ),
                 _ => return Err(DeltaError::IllegalDelta { index })?,
             }
         }
         Ok(changes)
     }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        for (index, element) in delta.iter().enumerate() { match element {
+            EltDelta::Add(elt) => {
+                let start: usize = errors.len();
+                <T>::validate_from_delta(elt, errors);
+                let segment = PathSegment::Index(index);
+                DeltaError::scope_all(errors, start, &segment);
+            },
+            _ => errors.push(DeltaError::IllegalDelta { index }),
+        }}
+    }
 }
 
 impl<T> IntoDelta for VecDeque<T>
@@ -138,6 +195,13 @@ where T: std::fmt::Debug + Core {
     }
 }
 
+impl<T: Core> ToDynDelta for VecDequeDelta<T>
+where T::Delta: ToDynDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        DynDelta::changes(self.0.iter().map(ToDynDelta::to_dyn_delta).collect())
+    }
+}
+
 
 
 #[derive(Clone, PartialEq)]
@@ -171,6 +235,18 @@ impl<T: Core> std::fmt::Debug for EltDelta<T> {
     }
 }
 
+impl<T: Core> ToDynDelta for EltDelta<T>
+where T::Delta: ToDynDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        match &self {
+            Self::Edit { index, item } =>
+                DynDelta::index(*index, item.to_dyn_delta()),
+            Self::Remove { count } => DynDelta::Truncate { count: *count },
+            Self::Add(delta) => DynDelta::Push(Box::new(delta.to_dyn_delta())),
+        }
+    }
+}
+
 
 
 
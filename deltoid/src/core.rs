@@ -1,6 +1,8 @@
 //! Core definitions
 
-use crate::error::DeltaResult;
+use crate::dyn_delta::{DynDelta, ToDynDelta};
+use crate::error::{DeltaError, DeltaResult};
+use crate::policy::{ApplyPolicy, OnFailure};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
@@ -17,6 +19,38 @@ pub trait Apply: Core + Clone + Debug + PartialEq {
     /// i.e. calculate `self --[delta]--> other`.
     ///                                   ^^^^^
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self>;
+
+    /// Like `apply()`, but handle recoverable failures, such as an `Edit`
+    /// of a `Vec` element that is out of range, according to `policy`.
+    ///
+    /// The default impl ignores `policy`.  Impls that can fail in one of
+    /// the ways covered by `ApplyPolicy`, or that contain other values,
+    /// should override it.
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
+        let _ = policy;
+        self.apply(delta)
+    }
+
+    /// Check whether `delta` can be applied to `self` without actually
+    /// applying it, and collect all problems rather than just the first.
+    fn validate(&self, delta: &Self::Delta) -> Result<(), Vec<DeltaError>> {
+        let mut errors: Vec<DeltaError> = vec![];
+        self.validate_into(delta, &mut errors);
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+
+    /// Push every problem with applying `delta` to `self` onto `errors`.
+    ///
+    /// The default impl applies a clone of `delta` and so reports at most
+    /// one problem.  Impls should override it to report every problem
+    /// without building the result.
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if let Err(error) = self.apply(delta.clone()) {
+            errors.push(error);
+        }
+    }
 }
 
 pub trait Delta: Core + Clone + Debug + PartialEq {
@@ -29,6 +63,16 @@ pub trait Delta: Core + Clone + Debug + PartialEq {
 pub trait FromDelta: Core + Sized {
     /// Convert `Self::Delta` to `Self`.
     fn from_delta(delta: Self::Delta) -> DeltaResult<Self>;
+
+    /// Push every problem with converting `delta` to `Self` onto `errors`.
+    ///
+    /// The default impl converts a clone of `delta` and so reports at most
+    /// one problem.
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if let Err(error) = Self::from_delta(delta.clone()) {
+            errors.push(error);
+        }
+    }
 }
 
 /// Conversion from type * to type *Delta
@@ -54,6 +98,18 @@ macro_rules! impl_delta_trait_for_primitive_types {
                 fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
                     Self::from_delta(delta)
                 }
+
+                fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                              -> DeltaResult<Self>
+                {
+                    match (delta.0, policy.empty_delta) {
+                        (Some(value), _) => Ok(value),
+                        (None, OnFailure::Error) =>
+                            Err(ExpectedValue!(stringify!($delta))),
+                        (None, OnFailure::Skip) => Ok(self.clone()),
+                        (None, OnFailure::Upsert) => Ok(Self::default()),
+                    }
+                }
             }
 
             impl Delta for $type {
@@ -66,12 +122,8 @@ macro_rules! impl_delta_trait_for_primitive_types {
             impl FromDelta for $type {
                 #[inline(always)]
                 fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
-                    // TODO: perhaps turn the option to choose either returning
-                    //       an error or a `Default` value into a policy?
-                    // match delta.0 {
-                    //     Some(value) => Ok(value),
-                    //     None => Ok(Self::default()),
-                    // }
+                    // NOTE: `Apply::apply_with()` can fall back to a `Default`
+                    //       value instead, see `ApplyPolicy::empty_delta`.
                     delta.0.ok_or_else(|| ExpectedValue!(stringify!($delta)))
                 }
             }
@@ -95,6 +147,15 @@ macro_rules! impl_delta_trait_for_primitive_types {
                     }
                 }
             }
+
+            impl ToDynDelta for $delta {
+                fn to_dyn_delta(&self) -> DynDelta {
+                    match &self.0 {
+                        None => DynDelta::Unchanged,
+                        Some(prim) => DynDelta::replace(prim),
+                    }
+                }
+            }
         )*
     };
 }
@@ -0,0 +1,245 @@
+//! A type-erased representation of deltas.
+//!
+//! The delta types of `deltoid`, as well as derived ones, implement
+//! [`ToDynDelta`] whenever the deltas they contain do.  It converts a delta
+//! into a tree of [`DynDelta`] nodes, which makes it possible to inspect a
+//! delta without knowing its concrete type, e.g. for logging or rendering.
+//!
+//! Implementing it is opt-in for hand-written delta types, since
+//! [`Core::Delta`] doesn't require it; without it, those deltas and the
+//! deltas that contain them just can't be inspected this way.
+//!
+//! [`Core::Delta`]: ../trait.Core.html#associatedtype.Delta
+
+use serde_derive::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+
+/// A node in the type-erased tree representation of a delta.
+///
+/// Values, i.e. replacements, map keys and set items, are represented by
+/// their `Debug` representation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum DynDelta {
+    /// The value is unchanged.
+    Unchanged,
+    /// The value is replaced by a new value.
+    Replace(String),
+    /// The value is removed, e.g. an entry of a map.
+    Remove,
+    /// A change to a named or positional field of a struct or tuple.
+    Field { name: String, delta: Box<DynDelta> },
+    /// A change to the element at an `index` of a sequence.
+    Index { index: usize, delta: Box<DynDelta> },
+    /// A change to the entry with a given `key` of a map.
+    Key { key: String, delta: Box<DynDelta> },
+    /// An entry with a given `key` is added to a map, with the value that
+    /// `delta` describes.
+    Insert { key: String, delta: Box<DynDelta> },
+    /// A change to an enum value that is, or becomes, the variant `name`.
+    Variant { name: String, delta: Box<DynDelta> },
+    /// An element is appended to the end of a sequence.
+    Push(Box<DynDelta>),
+    /// `count` elements are removed from the end of a sequence.
+    Truncate { count: usize },
+    /// An item is added to a set.
+    SetAdd(Box<DynDelta>),
+    /// An item is removed from a set.
+    SetRemove(Box<DynDelta>),
+    /// Multiple changes to the parts of a value.
+    Changes(Vec<DynDelta>),
+}
+
+impl DynDelta {
+    /// Create a `Replace` node for a `value`.
+    pub fn replace<T: Debug + ?Sized>(value: &T) -> Self {
+        DynDelta::Replace(format!("{:?}", value))
+    }
+
+    /// Create a node for a number of `changes`.  This is `Unchanged` if there
+    /// are no changes, and otherwise a `Changes` node.  Nested `Changes`
+    /// nodes are merged into it, e.g. those of a flattened field.
+    pub fn changes(changes: Vec<DynDelta>) -> Self {
+        let changes: Vec<DynDelta> = changes.into_iter()
+            .flat_map(|change| match change {
+                DynDelta::Changes(changes) => changes,
+                change => vec![change],
+            })
+            .filter(|change| !change.is_unchanged())
+            .collect();
+        if changes.is_empty() {
+            DynDelta::Unchanged
+        } else {
+            DynDelta::Changes(changes)
+        }
+    }
+
+    pub fn field<N: ToString>(name: N, delta: DynDelta) -> Self {
+        DynDelta::Field { name: name.to_string(), delta: Box::new(delta) }
+    }
+
+    pub fn index(index: usize, delta: DynDelta) -> Self {
+        DynDelta::Index { index, delta: Box::new(delta) }
+    }
+
+    pub fn key<K: Debug + ?Sized>(key: &K, delta: DynDelta) -> Self {
+        DynDelta::Key { key: format!("{:?}", key), delta: Box::new(delta) }
+    }
+
+    pub fn insert<K: Debug + ?Sized>(key: &K, delta: DynDelta) -> Self {
+        DynDelta::Insert { key: format!("{:?}", key), delta: Box::new(delta) }
+    }
+
+    pub fn variant<N: ToString>(name: N, delta: DynDelta) -> Self {
+        DynDelta::Variant { name: name.to_string(), delta: Box::new(delta) }
+    }
+
+    /// Returns `true` if `self` doesn't describe any change.
+    pub fn is_unchanged(&self) -> bool {
+        match self {
+            DynDelta::Unchanged => true,
+            DynDelta::Field { delta, .. }
+                | DynDelta::Index { delta, .. }
+                | DynDelta::Key { delta, .. } => delta.is_unchanged(),
+            DynDelta::Changes(changes) =>
+                changes.iter().all(DynDelta::is_unchanged),
+            _ => false,
+        }
+    }
+
+    /// Iterate over the child nodes of `self`.
+    pub fn children(&self) -> Box<dyn Iterator<Item = &DynDelta> + '_> {
+        match self {
+            DynDelta::Unchanged
+                | DynDelta::Replace(_)
+                | DynDelta::Remove
+                | DynDelta::Truncate { .. } => Box::new(std::iter::empty()),
+            DynDelta::Field { delta, .. }
+                | DynDelta::Index { delta, .. }
+                | DynDelta::Key { delta, .. }
+                | DynDelta::Insert { delta, .. }
+                | DynDelta::Variant { delta, .. }
+                | DynDelta::Push(delta)
+                | DynDelta::SetAdd(delta)
+                | DynDelta::SetRemove(delta) => Box::new(std::iter::once(&**delta)),
+            DynDelta::Changes(changes) => Box::new(changes.iter()),
+        }
+    }
+}
+
+
+/// Conversion from a delta type to a [`DynDelta`].
+pub trait ToDynDelta {
+    fn to_dyn_delta(&self) -> DynDelta;
+}
+
+impl ToDynDelta for DynDelta {
+    fn to_dyn_delta(&self) -> DynDelta { self.clone() }
+}
+
+impl<D: ToDynDelta> ToDynDelta for Box<D> {
+    fn to_dyn_delta(&self) -> DynDelta { (**self).to_dyn_delta() }
+}
+
+/// An absent delta means that the value is unchanged.
+impl<D: ToDynDelta> ToDynDelta for Option<D> {
+    fn to_dyn_delta(&self) -> DynDelta {
+        match self {
+            Some(delta) => delta.to_dyn_delta(),
+            None => DynDelta::Unchanged,
+        }
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use crate::{Delta, DeltaResult};
+    use std::collections::HashMap;
+    use super::*;
+
+    #[test]
+    fn DynDelta__changes() {
+        assert_eq!(DynDelta::changes(vec![]), DynDelta::Unchanged);
+        assert_eq!(
+            DynDelta::changes(vec![DynDelta::Unchanged, DynDelta::Remove]),
+            DynDelta::Changes(vec![DynDelta::Remove]),
+        );
+    }
+
+    #[test]
+    fn Vec__to_dyn_delta() -> DeltaResult<()> {
+        let vec0: Vec<u8> = vec![1, 2, 3];
+        let vec1: Vec<u8> = vec![1, 4];
+        let delta = vec0.delta(&vec1)?;
+        assert_eq!(delta.to_dyn_delta(), DynDelta::Changes(vec![
+            DynDelta::index(1, DynDelta::replace(&4u8)),
+            DynDelta::Truncate { count: 1 },
+        ]));
+        assert!(vec0.delta(&vec0)?.to_dyn_delta().is_unchanged());
+        Ok(())
+    }
+
+    #[test]
+    fn HashMap__to_dyn_delta() -> DeltaResult<()> {
+        let mut map0: HashMap<String, (u8, bool)> = HashMap::new();
+        map0.insert("foo".into(), (1, true));
+        let mut map1 = map0.clone();
+        map1.insert("foo".into(), (1, false));
+        let delta = map0.delta(&map1)?;
+        assert_eq!(delta.to_dyn_delta(), DynDelta::Changes(vec![
+            DynDelta::key("foo", DynDelta::Changes(vec![
+                DynDelta::field(1, DynDelta::replace(&false)),
+            ])),
+        ]));
+        let mut map2 = map0.clone();
+        map2.insert("bar".into(), (2, true));
+        let delta = map0.delta(&map2)?;
+        assert_eq!(delta.to_dyn_delta(), DynDelta::Changes(vec![
+            DynDelta::insert("bar", DynDelta::Changes(vec![
+                DynDelta::field(0, DynDelta::replace(&2u8)),
+                DynDelta::field(1, DynDelta::replace(&true)),
+            ])),
+        ]));
+        Ok(())
+    }
+
+    #[test]
+    fn Core__delta_without_to_dyn_delta() -> DeltaResult<()> {
+        use crate::{Apply, Core, FromDelta};
+        use serde_derive::{Deserialize, Serialize};
+
+        #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+        struct Opaque(u8);
+
+        #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+        struct OpaqueDelta(u8);
+
+        impl Core for Opaque {
+            type Delta = OpaqueDelta;
+        }
+
+        impl Apply for Opaque {
+            fn apply(&self, delta: OpaqueDelta) -> DeltaResult<Self> {
+                Ok(Opaque(delta.0))
+            }
+        }
+
+        impl FromDelta for Opaque {
+            fn from_delta(delta: OpaqueDelta) -> DeltaResult<Self> {
+                Ok(Opaque(delta.0))
+            }
+        }
+
+        // NOTE: `OpaqueDelta` doesn't implement `ToDynDelta`, yet it can
+        //       still be used as a delta, including by other deltas:
+        let vec0: Vec<Opaque> = vec![Opaque(1)];
+        let delta = crate::VecDelta(vec![
+            crate::EltDelta::Edit { index: 0, item: OpaqueDelta(2) },
+        ]);
+        assert_eq!(vec0.apply(delta)?, vec![Opaque(2)]);
+        Ok(())
+    }
+}
@@ -1,8 +1,11 @@
 //!
 
-use crate::{Core, Apply, Delta, FromDelta, IntoDelta};
+use crate::{Core, Apply, Delta, FromDelta, IntoDelta, ToDynDelta};
+use crate::visit::{format_path, walk_against, ChangeKind, PathSegment, Walk};
 use serde_derive::{Deserialize, Serialize};
-use std::sync::TryLockError;
+use std::error::Error;
+use std::fmt;
+use std::sync::{RwLockReadGuard, RwLockWriteGuard, TryLockError};
 
 
 #[macro_export]
@@ -89,6 +92,31 @@ macro_rules! ensure_le {
     }};
 }
 
+/// Asserts that two values are equal, like `assert_eq!`.  On failure, only
+/// the delta from `left` to `right` is printed, one line per changed path.
+#[macro_export]
+macro_rules! assert_delta_eq {
+    ($left:expr, $right:expr $(,)?) => {{
+        match (&$left, &$right) {
+            (left, right) => if !(*left == *right) {
+                panic!("{}", $crate::error::delta_mismatch_message(
+                    stringify!($left), stringify!($right), left, right, None
+                ));
+            }
+        }
+    }};
+    ($left:expr, $right:expr, $($args:tt)+) => {{
+        match (&$left, &$right) {
+            (left, right) => if !(*left == *right) {
+                panic!("{}", $crate::error::delta_mismatch_message(
+                    stringify!($left), stringify!($right), left, right,
+                    Some(format!($($args)+))
+                ));
+            }
+        }
+    }};
+}
+
 #[macro_export]
 macro_rules! ensure {
     ($predicate:expr $(, $fmt:expr $(, $args:expr)*)? ) => {
@@ -142,6 +170,46 @@ macro_rules! ExpectedValue {
 
 pub type DeltaResult<T> = Result<T, DeltaError>;
 
+/// Build the failure message of `assert_delta_eq!`.
+#[doc(hidden)]
+pub fn delta_mismatch_message<T: Delta + IntoDelta>(
+    lstr: &str,
+    rstr: &str,
+    left: &T,
+    right: &T,
+    msg: Option<String>,
+) -> String
+where T::Delta: ToDynDelta {
+    let mut buf = format!("assertion failed: `{} == {}`", lstr, rstr);
+    if let Some(msg) = msg {
+        buf.push_str(&format!(": {}", msg));
+    }
+    match left.delta(right) {
+        Ok(delta) => {
+            buf.push_str("\ndelta from left to right:");
+            let mut changes: Vec<String> = vec![];
+            let mut visitor = |path: &[PathSegment], change: ChangeKind| {
+                let path: String = format_path(path);
+                let path: &str = if path.is_empty() { "<root>" } else { &path };
+                changes.push(format!("\n  {}: {}", path, change));
+            };
+            // NOTE: If `left` can't be converted to a delta, the changes
+            //       are listed without the values they replace:
+            if walk_against(left, &delta, &mut visitor).is_err() {
+                delta.walk(&mut visitor);
+            }
+            if changes.is_empty() {
+                buf.push_str("\n  <no changes>");
+            }
+            buf.extend(changes);
+        },
+        Err(err) => buf.push_str(&format!(
+            "\nfailed to compute the delta from left to right: {:?}", err
+        )),
+    }
+    buf
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[derive(Deserialize, Serialize)]
 pub enum DeltaError {
@@ -167,11 +235,124 @@ pub enum DeltaError {
     FailedToApplyDelta { reason: String },
     FailedToConvertFromDelta { reason: String },
     IllegalDelta { index: usize },
+    #[deprecated(note = "an `RwLock` failing to lock is a `DeltaError::RwLock`")]
     RwLockAccessWouldBlock,
-    RwLockPoisoned(String)
+    #[deprecated(note = "an `RwLock` failing to lock is a `DeltaError::RwLock`")]
+    RwLockPoisoned(String),
+    RwLock(LockError),
+    /// `error` occurred at `path` in the data.
+    AtPath {
+        path: Vec<PathSegment>,
+        error: Box<DeltaError>,
+    },
 }
 
+impl DeltaError {
+    /// The error of a failed attempt to lock an `RwLock` for reading.
+    pub fn rwlock_read<T>(err: TryLockError<RwLockReadGuard<'_, T>>) -> Self {
+        Self::RwLock(LockError::new(LockAccess::Read, &err))
+    }
+
+    /// The error of a failed attempt to lock an `RwLock` for writing.
+    pub fn rwlock_write<T>(err: TryLockError<RwLockWriteGuard<'_, T>>) -> Self {
+        Self::RwLock(LockError::new(LockAccess::Write, &err))
+    }
+
+    /// Scope `self` to `segment`, i.e. prepend `segment` to its data path.
+    pub fn at(self, segment: PathSegment) -> Self {
+        match self {
+            Self::AtPath { mut path, error } => {
+                path.insert(0, segment);
+                Self::AtPath { path, error }
+            },
+            error =>
+                Self::AtPath { path: vec![segment], error: Box::new(error) },
+        }
+    }
+
+    /// Scope the `errors` from index `start` onwards to `segment`, i.e. the
+    /// errors that were pushed while validating the part at `segment`.
+    pub fn scope_all(
+        errors: &mut Vec<DeltaError>,
+        start: usize,
+        segment: &PathSegment,
+    ) {
+        let scoped: Vec<DeltaError> = errors.split_off(start);
+        errors.extend(scoped.into_iter().map(|error| error.at(segment.clone())));
+    }
+
+    /// The path in the data at which `self` occurred.
+    /// It's empty if `self` isn't scoped.
+    pub fn path(&self) -> &[PathSegment] {
+        match self {
+            Self::AtPath { path, .. } => path,
+            _ => &[],
+        }
+    }
+
+    /// The innermost error, without its data path.  Unlike `source()`,
+    /// this is `self` if `self` isn't scoped.
+    pub fn innermost(&self) -> &DeltaError {
+        match self {
+            Self::AtPath { error, .. } => error,
+            error => error,
+        }
+    }
+}
+
+impl fmt::Display for DeltaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::BugDetected { msg, file, line, column } => {
+                write!(f, "bug detected at {}:{}:{}", file, line, column)?;
+                if !msg.is_empty() { write!(f, ": {}", msg)?; }
+                Ok(())
+            },
+            Self::ExpectedValue { type_name, file, line, column } => write!(
+                f, "expected a value for {} (at {}:{}:{})",
+                type_name, file, line, column
+            ),
+            Self::FailedToEnsure { predicate, msg, file, line, column } => {
+                write!(f, "failed to ensure `{}` at {}:{}:{}",
+                       predicate, file, line, column)?;
+                if !msg.is_empty() { write!(f, ": {}", msg)?; }
+                Ok(())
+            },
+            Self::FailedToApplyDelta { reason } =>
+                write!(f, "failed to apply delta: {}", reason),
+            Self::FailedToConvertFromDelta { reason } =>
+                write!(f, "failed to convert from delta: {}", reason),
+            Self::IllegalDelta { index } =>
+                write!(f, "illegal delta at change #{}", index),
+            #[allow(deprecated)]
+            Self::RwLockAccessWouldBlock =>
+                write!(f, "failed to lock an RwLock: it is held elsewhere"),
+            #[allow(deprecated)]
+            Self::RwLockPoisoned(msg) =>
+                write!(f, "failed to lock an RwLock: {}", msg),
+            Self::RwLock(err) =>
+                write!(f, "failed to lock an RwLock for {}", err.access),
+            Self::AtPath { path, .. } =>
+                write!(f, "failed at `{}`", format_path(path)),
+        }
+    }
+}
+
+impl Error for DeltaError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::RwLock(err) => Some(err),
+            Self::AtPath { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Kept for compatibility: this doesn't know whether the lock was meant
+/// for reading or for writing, so it produces the deprecated variants.
+/// Use [`DeltaError::rwlock_read`] or [`DeltaError::rwlock_write`] instead.
 impl<T> From<TryLockError<T>> for DeltaError {
+    #[allow(deprecated)]
     fn from(err: TryLockError<T>) -> DeltaError {
         match err {
             TryLockError::WouldBlock =>
@@ -183,9 +364,73 @@ impl<T> From<TryLockError<T>> for DeltaError {
 }
 
 
+/// Why an attempt to lock an `RwLock` failed.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Deserialize, Serialize)]
+pub struct LockError {
+    pub access: LockAccess,
+    pub kind: LockErrorKind,
+}
+
+impl LockError {
+    fn new<G>(access: LockAccess, err: &TryLockError<G>) -> Self {
+        let kind = match err {
+            TryLockError::WouldBlock => LockErrorKind::WouldBlock,
+            TryLockError::Poisoned(_) => LockErrorKind::Poisoned,
+        };
+        Self { access, kind }
+    }
+}
+
+impl fmt::Display for LockError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            LockErrorKind::WouldBlock =>
+                write!(f, "the lock is held elsewhere"),
+            LockErrorKind::Poisoned =>
+                write!(f, "the lock is poisoned by a panic in another thread"),
+        }
+    }
+}
+
+impl Error for LockError {}
+
+/// Whether an `RwLock` was locked for reading or for writing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Deserialize, Serialize)]
+pub enum LockAccess { Read, Write }
+
+impl fmt::Display for LockAccess {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Read  => write!(f, "reading"),
+            Self::Write => write!(f, "writing"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Deserialize, Serialize)]
+pub enum LockErrorKind {
+    /// The lock is held elsewhere.
+    WouldBlock,
+    /// Another thread panicked while holding the lock.
+    Poisoned,
+}
+
+
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct DeltaErrorDelta(Option<DeltaError>);
 
+impl crate::ToDynDelta for DeltaErrorDelta {
+    fn to_dyn_delta(&self) -> crate::DynDelta {
+        match &self.0 {
+            Some(derr) => crate::DynDelta::replace(derr),
+            None => crate::DynDelta::Unchanged,
+        }
+    }
+}
+
 impl Core for DeltaError {
     type Delta = DeltaErrorDelta;
 }
@@ -221,3 +466,116 @@ impl IntoDelta for DeltaError {
         Ok(DeltaErrorDelta(Some(self)))
     }
 }
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn assert_delta_eq__equal_values() {
+        assert_delta_eq!(vec![(1u8, true)], vec![(1u8, true)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `map0 == map1`: for 42
+delta from left to right:
+  [\"foo\"].1: changed from true to false
+  [\"qux\"]: removed")]
+    fn assert_delta_eq__different_values() {
+        let mut map0: BTreeMap<String, (u8, bool)> = BTreeMap::new();
+        map0.insert("foo".into(), (1, true));
+        map0.insert("qux".into(), (2, true));
+        let mut map1 = map0.clone();
+        map1.insert("foo".into(), (1, false));
+        map1.remove("qux");
+        assert_delta_eq!(map0, map1, "for {}", 42);
+    }
+
+    #[test]
+    #[should_panic(expected = "delta from left to right:\n  <root>: changed from 1 to 2")]
+    fn assert_delta_eq__primitive_values() {
+        assert_delta_eq!(1u8, 2u8);
+    }
+
+    #[test]
+    fn DeltaError__at() {
+        use crate::DeltaError;
+        use crate::visit::{format_path, PathSegment};
+        let error = DeltaError::IllegalDelta { index: 2 }
+            .at(PathSegment::Position(0))
+            .at(PathSegment::key("alice"))
+            .at(PathSegment::Field("scores".into()));
+        assert_eq!(format_path(error.path()), "scores[\"alice\"].0");
+        assert_eq!(error.innermost(), &DeltaError::IllegalDelta { index: 2 });
+        assert_eq!(DeltaError::IllegalDelta { index: 2 }.path(), &[]);
+    }
+
+    #[test]
+    fn BTreeMap__apply__error_path() {
+        use crate::{Apply, BTreeMapDelta, DeltaError, U8Delta, VecDelta};
+        use crate::collections::btreemap::EntryDelta;
+        use crate::vec::EltDelta;
+        use crate::visit::format_path;
+        let mut map0: BTreeMap<String, Vec<(u8, u8)>> = BTreeMap::new();
+        map0.insert("foo".into(), vec![(1, 1)]);
+        let delta = BTreeMapDelta(Some(vec![EntryDelta::Edit {
+            key: "foo".into(),
+            value: VecDelta(vec![EltDelta::Add((Some(U8Delta(Some(2))), None))]),
+        }]));
+        let error = map0.apply(delta).unwrap_err();
+        assert_eq!(format_path(error.path()), "[\"foo\"][1].1");
+        assert!(matches!(error.innermost(), DeltaError::ExpectedValue { .. }));
+    }
+
+    #[test]
+    fn Option__apply__error_path() {
+        use crate::{Apply, BTreeMapDelta, DeltaError, OptionDelta, VecDelta};
+        use crate::collections::btreemap::EntryDelta;
+        use crate::vec::EltDelta;
+        use crate::visit::format_path;
+        let mut map0: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+        map0.insert("foo".into(), vec![1]);
+        let opt0: Option<BTreeMap<String, Vec<u8>>> = Some(map0);
+        let delta = OptionDelta::Some(BTreeMapDelta(Some(vec![EntryDelta::Edit {
+            key: "foo".into(),
+            value: VecDelta(vec![EltDelta::Remove { count: 2 }]),
+        }])));
+        let error = opt0.apply(delta).unwrap_err();
+        assert_eq!(format_path(error.path()), "[\"foo\"]");
+        assert!(matches!(error.innermost(), DeltaError::ExpectedValue { .. }));
+    }
+
+    #[test]
+    fn DeltaError__display() {
+        use crate::DeltaError;
+        use crate::visit::PathSegment;
+        use std::error::Error;
+        let error = DeltaError::IllegalDelta { index: 2 }
+            .at(PathSegment::Index(3))
+            .at(PathSegment::Field("servers".into()));
+        assert_eq!(error.to_string(), "failed at `servers[3]`");
+        let source = error.source().expect("expected a source");
+        assert_eq!(source.to_string(), "illegal delta at change #2");
+        assert!(source.source().is_none());
+        let boxed: Box<dyn Error + Send + Sync> = Box::new(error);
+        assert_eq!(boxed.to_string(), "failed at `servers[3]`");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn DeltaError__from_try_lock_error() {
+        use crate::{DeltaError, LockAccess, LockError, LockErrorKind};
+        let lock = std::sync::RwLock::new(0u8);
+        let _guard = lock.write().unwrap();
+        let err = lock.try_read().unwrap_err();
+        assert_eq!(DeltaError::rwlock_read(err), DeltaError::RwLock(LockError {
+            access: LockAccess::Read,
+            kind: LockErrorKind::WouldBlock,
+        }));
+        let err = lock.try_write().unwrap_err();
+        assert_eq!(DeltaError::from(err), DeltaError::RwLockAccessWouldBlock);
+    }
+}
@@ -0,0 +1,220 @@
+//! Deltoid impls for [`ArrayVec`].
+//!
+//! An `ArrayVec` uses the same delta encoding as a `Vec`, i.e. [`VecDelta`].
+//! Since an `ArrayVec` has a fixed capacity, applying a delta that would
+//! grow it beyond that capacity results in an error rather than a panic.
+//!
+//! [`ArrayVec`]: https://docs.rs/arrayvec/latest/arrayvec/struct.ArrayVec.html
+//! [`VecDelta`]: ../../struct.VecDelta.html
+
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{ApplyPolicy, PathSegment};
+use crate::vec::{EltDelta, VecDelta};
+use ::arrayvec::ArrayVec;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+
+impl<T, const CAP: usize> Core for ArrayVec<T, CAP>
+where T: Clone + Debug + PartialEq + Core
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    type Delta = VecDelta<T>;
+}
+
+impl<T, const CAP: usize> Apply for ArrayVec<T, CAP>
+where T: Clone + Debug + PartialEq + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
+        delta.apply_to(
+            self,
+            policy,
+            |vec| vec.len(),
+            |vec, index| &vec[index],
+            |vec, index, item| vec[index] = item,
+            try_push,
+            |vec| vec.pop().is_some(),
+        )
+    }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        delta.validate_into(self.len(), |index| &self[index], errors);
+        validate_capacity::<T, CAP>(self.len(), delta, errors, |reason| {
+            DeltaError::FailedToApplyDelta { reason }
+        });
+    }
+}
+
+impl<T, const CAP: usize> Delta for ArrayVec<T, CAP>
+where T: Clone + Debug + PartialEq + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+        let (lhs_len, rhs_len) = (self.len(), rhs.len());
+        let max_len = usize::max(lhs_len, rhs_len);
+        let mut changes: Vec<EltDelta<T>> = vec![];
+        for index in 0 .. max_len { match (self.get(index), rhs.get(index)) {
+            (None, None) => return bug_detected!(),
+            (Some(lhs), Some(rhs)) if lhs == rhs => {/*NOP*/},
+            (Some(lhs), Some(rhs)) =>
+                changes.push(EltDelta::Edit { index, item: lhs.delta(rhs)? }),
+            (None, Some(rhs)) =>
+                changes.push(EltDelta::Add(rhs.clone().into_delta()?)),
+            (Some(_),   None) => match changes.last_mut() {
+                Some(EltDelta::Remove { ref mut count }) => *count += 1,
+                _ => changes.push(EltDelta::Remove { count: 1 }),
+            },
+        }}
+        Ok(VecDelta(changes))
+    }
+}
+
+impl<T, const CAP: usize> FromDelta for ArrayVec<T, CAP>
+where T: Clone + Debug + PartialEq + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn from_delta(delta: <Self as Core>::Delta) -> DeltaResult<Self> {
+        let mut vec: Self = ArrayVec::new();
+        for (index, element) in delta.0.into_iter().enumerate() {
+            match element {
+                EltDelta::Add(elt) => vec.try_push(
+                    <T>::from_delta(elt)
+                        .map_err(|e| e.at(PathSegment::Index(index)))?
+                )
+                    .map_err(|_| DeltaError::FailedToConvertFromDelta {
+                        reason: capacity_exceeded::<CAP>(),
+                    })?,
+                _ => return Err(DeltaError::IllegalDelta { index }),
+            }
+        }
+        Ok(vec)
+    }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        delta.validate_from_delta(errors);
+        validate_capacity::<T, CAP>(0, delta, errors, |reason| {
+            DeltaError::FailedToConvertFromDelta { reason }
+        });
+    }
+}
+
+impl<T, const CAP: usize> IntoDelta for ArrayVec<T, CAP>
+where T: Clone + Debug + PartialEq + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn into_delta(self) -> DeltaResult<<Self as Core>::Delta> {
+        let mut changes: Vec<EltDelta<T>> = vec![];
+        for elt in self {
+            changes.push(EltDelta::Add(elt.into_delta()?));
+        }
+        Ok(VecDelta(changes))
+    }
+}
+
+/// Push `item` onto `vec`, or fail if `vec` is full.
+fn try_push<T, const CAP: usize>(vec: &mut ArrayVec<T, CAP>, item: T)
+                                 -> DeltaResult<()> {
+    vec.try_push(item).map_err(|_| DeltaError::FailedToApplyDelta {
+        reason: capacity_exceeded::<CAP>(),
+    })
+}
+
+/// Push an error onto `errors` if `delta` grows an `ArrayVec` of length `len`
+/// beyond its capacity.
+fn validate_capacity<T: Core, const CAP: usize>(
+    mut len: usize,
+    delta: &VecDelta<T>,
+    errors: &mut Vec<DeltaError>,
+    error: impl Fn(String) -> DeltaError,
+) {
+    for change in delta.iter() { match change {
+        EltDelta::Edit { .. } => {/*NOP*/},
+        EltDelta::Add(_) if len >= CAP => {
+            errors.push(error(capacity_exceeded::<CAP>()));
+            return;
+        },
+        EltDelta::Add(_) => len += 1,
+        EltDelta::Remove { count } => len = len.saturating_sub(*count),
+    }}
+}
+
+fn capacity_exceeded<const CAP: usize>() -> String {
+    format!("VecDelta<T> exceeds the ArrayVec capacity of {}", CAP)
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn array_vec<const CAP: usize>(items: &[i32]) -> ArrayVec<i32, CAP> {
+        items.iter().cloned().collect()
+    }
+
+    #[test]
+    fn ArrayVec__apply__different_values() -> DeltaResult<()> {
+        let vec0: ArrayVec<i32, 4> = array_vec(&[1, 3]);
+        let vec1: ArrayVec<i32, 4> = array_vec(&[1, 4, 10, 30]);
+        let delta = vec0.delta(&vec1)?;
+        assert_eq!(delta, VecDelta(vec![
+            EltDelta::Edit { index: 1, item: 4i32.into_delta()? },
+            EltDelta::Add(10i32.into_delta()?),
+            EltDelta::Add(30i32.into_delta()?),
+        ]));
+        let vec2 = vec0.apply(delta)?;
+        assert_eq!(vec1, vec2);
+        Ok(())
+    }
+
+    #[test]
+    fn ArrayVec__apply__capacity_exceeded() -> DeltaResult<()> {
+        let vec0: ArrayVec<i32, 2> = array_vec(&[1, 3]);
+        let delta = vec![1, 3, 10].delta(&vec![1, 3, 10, 30])?;
+        let result = vec0.apply(delta);
+        assert!(matches!(
+            result,
+            Err(DeltaError::FailedToApplyDelta { .. })
+        ));
+        let delta = vec![1, 3, 10].into_delta()?;
+        let result = ArrayVec::<i32, 2>::from_delta(delta);
+        assert!(matches!(
+            result,
+            Err(DeltaError::FailedToConvertFromDelta { .. })
+        ));
+        Ok(())
+    }
+    #[test]
+    fn ArrayVec__validate__capacity_exceeded() -> DeltaResult<()> {
+        let vec0: ArrayVec<i32, 2> = array_vec(&[1, 3]);
+        let delta = vec![1, 3].delta(&vec![1, 4])?;
+        assert_eq!(vec0.validate(&delta), Ok(()));
+        let delta = vec![1, 3, 10].delta(&vec![1, 4, 10, 30])?;
+        let errors = vec0.validate(&delta).unwrap_err();
+        assert!(matches!(
+            errors.as_slice(),
+            [DeltaError::FailedToApplyDelta { .. }]
+        ));
+        let delta = vec![1, 3, 10].into_delta()?;
+        let mut errors = vec![];
+        ArrayVec::<i32, 2>::validate_from_delta(&delta, &mut errors);
+        assert!(matches!(
+            errors.as_slice(),
+            [DeltaError::FailedToConvertFromDelta { .. }]
+        ));
+        Ok(())
+    }
+}
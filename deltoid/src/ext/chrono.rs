@@ -0,0 +1,532 @@
+//! Deltoid impls for the date and time types of the [`chrono`] crate.
+//!
+//! Timestamps, i.e. [`DateTime`], [`NaiveDateTime`] and [`NaiveDate`], are
+//! encoded as an offset relative to the old value whenever that offset can be
+//! represented as an `i64`.  Such an offset is always smaller than the full
+//! value, both in JSON and in binary formats.  When it cannot be represented,
+//! the delta falls back to the full value.
+//!
+//! [`chrono`]: https://docs.rs/chrono
+//! [`DateTime`]: https://docs.rs/chrono/latest/chrono/struct.DateTime.html
+//! [`NaiveDateTime`]: https://docs.rs/chrono/latest/chrono/naive/struct.NaiveDateTime.html
+//! [`NaiveDate`]: https://docs.rs/chrono/latest/chrono/naive/struct.NaiveDate.html
+
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{DynDelta, ToDynDelta};
+use ::chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use ::chrono::{Offset, TimeZone};
+use serde::{Deserialize, Serialize};
+
+
+impl<Tz> Core for DateTime<Tz>
+where Tz: TimeZone,
+      DateTime<Tz>: for<'de> Deserialize<'de> + Serialize
+{
+    type Delta = DateTimeDelta<Tz>;
+}
+
+impl<Tz> Apply for DateTime<Tz>
+where Tz: TimeZone,
+      DateTime<Tz>: for<'de> Deserialize<'de> + Serialize
+{
+    fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        match delta {
+            DateTimeDelta::Offset(nanos) => self.clone()
+                .checked_add_signed(Duration::nanoseconds(nanos))
+                .ok_or_else(|| offset_out_of_range("DateTimeDelta<Tz>", nanos)),
+            DateTimeDelta::Value(value) => Ok(value),
+        }
+    }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if let DateTimeDelta::Offset(nanos) = *delta {
+            let new = self.clone().checked_add_signed(Duration::nanoseconds(nanos));
+            if new.is_none() {
+                errors.push(offset_out_of_range("DateTimeDelta<Tz>", nanos));
+            }
+        }
+    }
+}
+
+impl<Tz> Delta for DateTime<Tz>
+where Tz: TimeZone,
+      DateTime<Tz>: for<'de> Deserialize<'de> + Serialize
+{
+    fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+        // NOTE: An offset only preserves the time zone offset of `self`,
+        //       so it is only usable when `rhs` has that same offset.
+        if self.offset().fix() != rhs.offset().fix() {
+            return rhs.clone().into_delta();
+        }
+        let offset = rhs.clone().signed_duration_since(self.clone());
+        match offset.num_nanoseconds() {
+            Some(nanos) => Ok(DateTimeDelta::Offset(nanos)),
+            None => rhs.clone().into_delta(),
+        }
+    }
+}
+
+impl<Tz> FromDelta for DateTime<Tz>
+where Tz: TimeZone,
+      DateTime<Tz>: for<'de> Deserialize<'de> + Serialize
+{
+    fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+        match delta {
+            DateTimeDelta::Value(value) => Ok(value),
+            DateTimeDelta::Offset(_) => Err(offset_without_base("DateTimeDelta<Tz>")),
+        }
+    }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if let DateTimeDelta::Offset(_) = delta {
+            errors.push(offset_without_base("DateTimeDelta<Tz>"));
+        }
+    }
+}
+
+impl<Tz> IntoDelta for DateTime<Tz>
+where Tz: TimeZone,
+      DateTime<Tz>: for<'de> Deserialize<'de> + Serialize
+{
+    fn into_delta(self) -> DeltaResult<Self::Delta> {
+        Ok(DateTimeDelta::Value(self))
+    }
+}
+
+
+#[derive(Clone)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+#[serde(bound(
+    serialize = "DateTime<Tz>: Serialize",
+    deserialize = "DateTime<Tz>: Deserialize<'de>",
+))]
+pub enum DateTimeDelta<Tz: TimeZone> {
+    /// The new value, as a number of nanoseconds relative to the old value.
+    Offset(i64),
+    /// The new value.
+    Value(DateTime<Tz>),
+}
+
+impl<Tz: TimeZone> PartialEq for DateTimeDelta<Tz> {
+    fn eq(&self, rhs: &Self) -> bool {
+        match (self, rhs) {
+            (Self::Offset(lhs), Self::Offset(rhs)) => lhs == rhs,
+            (Self::Value(lhs), Self::Value(rhs)) =>
+                lhs == rhs && lhs.offset().fix() == rhs.offset().fix(),
+            _ => false,
+        }
+    }
+}
+
+impl<Tz: TimeZone> std::fmt::Debug for DateTimeDelta<Tz> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match &self {
+            Self::Offset(nanos) => write!(f, "DateTimeDelta::Offset({})", nanos),
+            Self::Value(value)  => write!(f, "DateTimeDelta::Value({:?})", value),
+        }
+    }
+}
+
+impl<Tz: TimeZone> ToDynDelta for DateTimeDelta<Tz> {
+    fn to_dyn_delta(&self) -> DynDelta {
+        match &self {
+            Self::Offset(nanos) => DynDelta::Replace(format!("{:+} nanoseconds", nanos)),
+            Self::Value(value)  => DynDelta::replace(value),
+        }
+    }
+}
+
+
+
+macro_rules! impl_delta_trait_for_naive_types {
+    ( $(
+        $type:ty => $delta:ident, $unit:literal,
+        $to_offset:path, $from_offset:path
+    );* $(;)? ) => {
+        $(
+            impl Core for $type {
+                type Delta = $delta;
+            }
+
+            impl Apply for $type {
+                fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+                    match delta {
+                        $delta::Offset(offset) => $from_offset(offset)
+                            .and_then(|offset| self.checked_add_signed(offset))
+                            .ok_or_else(|| {
+                                offset_out_of_range(stringify!($delta), offset)
+                            }),
+                        $delta::Value(value) => Ok(value),
+                    }
+                }
+
+                fn validate_into(
+                    &self,
+                    delta: &Self::Delta,
+                    errors: &mut Vec<DeltaError>,
+                ) {
+                    if let $delta::Value(_) = delta { return; }
+                    if let Err(error) = self.apply(*delta) {
+                        errors.push(error);
+                    }
+                }
+            }
+
+            impl Delta for $type {
+                fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+                    let offset: Duration = rhs.signed_duration_since(*self);
+                    match $to_offset(&offset) {
+                        Some(offset) => Ok($delta::Offset(offset)),
+                        None => rhs.into_delta(),
+                    }
+                }
+            }
+
+            impl FromDelta for $type {
+                fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+                    match delta {
+                        $delta::Value(value) => Ok(value),
+                        $delta::Offset(_) =>
+                            Err(offset_without_base(stringify!($delta))),
+                    }
+                }
+
+                fn validate_from_delta(
+                    delta: &Self::Delta,
+                    errors: &mut Vec<DeltaError>,
+                ) {
+                    if let $delta::Offset(_) = delta {
+                        errors.push(offset_without_base(stringify!($delta)));
+                    }
+                }
+            }
+
+            impl IntoDelta for $type {
+                fn into_delta(self) -> DeltaResult<Self::Delta> {
+                    Ok($delta::Value(self))
+                }
+            }
+
+
+            #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+            #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+            pub enum $delta {
+                #[doc = concat!(
+                    "The new value, as a number of ", $unit,
+                    " relative to the old value."
+                )]
+                Offset(i64),
+                /// The new value.
+                Value($type),
+            }
+
+            impl std::fmt::Debug for $delta {
+                fn fmt(&self, f: &mut std::fmt::Formatter)
+                       -> Result<(), std::fmt::Error>
+                {
+                    match &self {
+                        Self::Offset(offset) => write!(
+                            f, "{}::Offset({})", stringify!($delta), offset
+                        ),
+                        Self::Value(value) => write!(
+                            f, "{}::Value({:?})", stringify!($delta), value
+                        ),
+                    }
+                }
+            }
+
+            impl ToDynDelta for $delta {
+                fn to_dyn_delta(&self) -> DynDelta {
+                    match &self {
+                        Self::Offset(offset) =>
+                            DynDelta::Replace(format!("{:+} {}", offset, $unit)),
+                        Self::Value(value) => DynDelta::replace(value),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+fn num_days(duration: &Duration) -> Option<i64> { Some(duration.num_days()) }
+
+/// `Duration::days(days)`, or `None` if that would panic.
+fn checked_days(days: i64) -> Option<Duration> {
+    checked_seconds(days.checked_mul(24 * 60 * 60)?)
+}
+
+/// `Duration::nanoseconds(nanos)`, which can't panic.
+fn nanoseconds(nanos: i64) -> Option<Duration> {
+    Some(Duration::nanoseconds(nanos))
+}
+
+/// `Duration::seconds(secs)`, or `None` if that would panic.
+fn checked_seconds(secs: i64) -> Option<Duration> {
+    let max_secs: i64 = Duration::max_value().num_seconds();
+    if (-max_secs ..= max_secs).contains(&secs) {
+        Some(Duration::seconds(secs))
+    } else {
+        None
+    }
+}
+
+impl_delta_trait_for_naive_types! {
+    NaiveDate     => NaiveDateDelta,     "days",
+        num_days, checked_days;
+    NaiveDateTime => NaiveDateTimeDelta, "nanoseconds",
+        Duration::num_nanoseconds, nanoseconds;
+}
+
+
+
+impl Core for NaiveTime {
+    type Delta = NaiveTimeDelta;
+}
+
+impl Apply for NaiveTime {
+    fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        Self::from_delta(delta)
+    }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        Self::validate_from_delta(delta, errors)
+    }
+}
+
+impl Delta for NaiveTime {
+    fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+        rhs.into_delta()
+    }
+}
+
+impl FromDelta for NaiveTime {
+    fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+        delta.0.ok_or_else(|| ExpectedValue!("NaiveTimeDelta"))
+    }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if delta.0.is_none() {
+            errors.push(ExpectedValue!("NaiveTimeDelta"));
+        }
+    }
+}
+
+impl IntoDelta for NaiveTime {
+    fn into_delta(self) -> DeltaResult<Self::Delta> {
+        Ok(NaiveTimeDelta(Some(self)))
+    }
+}
+
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub struct NaiveTimeDelta(#[doc(hidden)] pub Option<NaiveTime>);
+
+impl std::fmt::Debug for NaiveTimeDelta {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match &self.0 {
+            Some(time) => write!(f, "NaiveTimeDelta({:?})", time),
+            None       => write!(f, "NaiveTimeDelta(None)"),
+        }
+    }
+}
+
+impl ToDynDelta for NaiveTimeDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        match &self.0 {
+            Some(time) => DynDelta::replace(time),
+            None       => DynDelta::Unchanged,
+        }
+    }
+}
+
+
+
+impl Core for Duration {
+    type Delta = DurationDelta;
+}
+
+impl Apply for Duration {
+    fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        Self::from_delta(delta)
+    }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        Self::validate_from_delta(delta, errors)
+    }
+}
+
+impl Delta for Duration {
+    fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+        rhs.into_delta()
+    }
+}
+
+impl FromDelta for Duration {
+    fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+        let (secs, nanos) = delta.0
+            .ok_or_else(|| ExpectedValue!("DurationDelta"))?;
+        checked_seconds(secs)
+            .and_then(|duration| {
+                duration.checked_add(&Duration::nanoseconds(nanos as i64))
+            })
+            .ok_or_else(|| DeltaError::FailedToConvertFromDelta {
+                reason: format!("DurationDelta({}s {}ns) is out of range", secs, nanos),
+            })
+    }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if let Err(error) = Self::from_delta(*delta) {
+            errors.push(error);
+        }
+    }
+}
+
+impl IntoDelta for Duration {
+    fn into_delta(self) -> DeltaResult<Self::Delta> {
+        let secs: i64 = self.num_seconds();
+        let subsec: Duration = self - Duration::seconds(secs);
+        let nanos = subsec.num_nanoseconds()
+            .ok_or_else(|| ExpectedValue!("DurationDelta"))?;
+        Ok(DurationDelta(Some((secs, nanos as i32))))
+    }
+}
+
+
+/// A [`Duration`] is encoded as a number of seconds and a number
+/// of subsecond nanoseconds, since it does not implement serde traits.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub struct DurationDelta(#[doc(hidden)] pub Option<(i64, i32)>);
+
+impl std::fmt::Debug for DurationDelta {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match &self.0 {
+            Some((secs, nanos)) => write!(f, "DurationDelta({}s {}ns)", secs, nanos),
+            None                => write!(f, "DurationDelta(None)"),
+        }
+    }
+}
+
+impl ToDynDelta for DurationDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        match &self.0 {
+            Some((secs, nanos)) => DynDelta::Replace(format!("{}s {}ns", secs, nanos)),
+            None                => DynDelta::Unchanged,
+        }
+    }
+}
+
+
+
+fn offset_out_of_range(type_name: &str, offset: i64) -> DeltaError {
+    DeltaError::FailedToApplyDelta {
+        reason: format!("{} offset {} is out of range", type_name, offset),
+    }
+}
+
+fn offset_without_base(type_name: &str) -> DeltaError {
+    DeltaError::FailedToConvertFromDelta {
+        reason: format!("{} holds an offset, which requires an old value", type_name),
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use ::chrono::{FixedOffset, Utc};
+    use serde_json;
+    use super::*;
+
+    #[test]
+    fn DateTime__delta__different_values() -> DeltaResult<()> {
+        let dt0: DateTime<Utc> = Utc.timestamp_opt(1_600_000_000, 0).unwrap();
+        let dt1: DateTime<Utc> = dt0 + Duration::milliseconds(1500);
+        let delta: <DateTime<Utc> as Core>::Delta = dt0.delta(&dt1)?;
+        assert_eq!(delta, DateTimeDelta::Offset(1_500_000_000));
+        let json_string = serde_json::to_string(&delta)
+            .expect("Could not serialize to json");
+        assert_eq!(json_string, "{\"Offset\":1500000000}");
+        let delta1: <DateTime<Utc> as Core>::Delta = serde_json::from_str(
+            &json_string
+        ).expect("Could not deserialize from json");
+        assert_eq!(delta, delta1);
+        Ok(())
+    }
+
+    #[test]
+    fn DateTime__apply__different_values() -> DeltaResult<()> {
+        let dt0: DateTime<Utc> = Utc.timestamp_opt(1_600_000_000, 0).unwrap();
+        let dt1: DateTime<Utc> = dt0 - Duration::days(3);
+        let delta: <DateTime<Utc> as Core>::Delta = dt0.delta(&dt1)?;
+        let dt2 = dt0.apply(delta)?;
+        assert_eq!(dt1, dt2);
+        Ok(())
+    }
+
+    #[test]
+    fn DateTime__apply__different_offsets() -> DeltaResult<()> {
+        let utc0 = Utc.timestamp_opt(1_600_000_000, 0).unwrap();
+        let dt0: DateTime<FixedOffset> =
+            utc0.with_timezone(&FixedOffset::east_opt(3600).unwrap());
+        let dt1: DateTime<FixedOffset> =
+            utc0.with_timezone(&FixedOffset::west_opt(7200).unwrap());
+        let delta: <DateTime<FixedOffset> as Core>::Delta = dt0.delta(&dt1)?;
+        assert_eq!(delta, DateTimeDelta::Value(dt1));
+        let dt2 = dt0.apply(delta)?;
+        assert_eq!(dt1.offset(), dt2.offset());
+        Ok(())
+    }
+
+    #[test]
+    fn NaiveDate__apply__different_values() -> DeltaResult<()> {
+        let d0 = NaiveDate::from_ymd_opt(2020, 2, 28).unwrap();
+        let d1 = NaiveDate::from_ymd_opt(2021, 3, 1).unwrap();
+        let delta: <NaiveDate as Core>::Delta = d0.delta(&d1)?;
+        assert_eq!(delta, NaiveDateDelta::Offset(367));
+        let d2 = d0.apply(delta)?;
+        assert_eq!(d1, d2);
+        Ok(())
+    }
+
+    #[test]
+    fn NaiveDateTime__apply__different_values() -> DeltaResult<()> {
+        let dt0 = NaiveDate::from_ymd_opt(2020, 2, 28).unwrap()
+            .and_hms_opt(12, 0, 0).unwrap();
+        let dt1 = dt0 + Duration::seconds(90);
+        let delta: <NaiveDateTime as Core>::Delta = dt0.delta(&dt1)?;
+        let dt2 = dt0.apply(delta)?;
+        assert_eq!(dt1, dt2);
+        assert!(NaiveDateTime::from_delta(delta).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn Duration__apply__different_values() -> DeltaResult<()> {
+        let d0 = Duration::milliseconds(-2500);
+        let d1 = Duration::weeks(3) + Duration::nanoseconds(17);
+        let delta: <Duration as Core>::Delta = d0.delta(&d1)?;
+        let d2 = d0.apply(delta)?;
+        assert_eq!(d1, d2);
+        let d3 = Duration::from_delta(d0.into_delta()?)?;
+        assert_eq!(d0, d3);
+        Ok(())
+    }
+
+    #[test]
+    fn NaiveDate__apply__offset_out_of_range() {
+        let d0 = NaiveDate::from_ymd_opt(2020, 2, 28).unwrap();
+        assert!(d0.apply(NaiveDateDelta::Offset(i64::MAX)).is_err());
+        assert!(d0.apply(NaiveDateDelta::Offset(i64::MIN)).is_err());
+    }
+
+    #[test]
+    fn Duration__from_delta__out_of_range() {
+        let delta = DurationDelta(Some((i64::MAX, 0)));
+        assert!(Duration::from_delta(delta).is_err());
+        let delta = DurationDelta(Some((i64::MIN, 0)));
+        assert!(Duration::from_delta(delta).is_err());
+    }
+}
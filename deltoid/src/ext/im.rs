@@ -0,0 +1,549 @@
+//! Deltoid impls for the persistent [`HashMap`], [`OrdMap`] and [`Vector`]
+//! types of the [`im`] crate.
+//!
+//! These types share structure between versions, and the delta calculations
+//! take advantage of that where `im`'s public API allows it:
+//! - an `OrdMap` is diffed using [`OrdMap::diff`], which doesn't visit nodes
+//!   that are shared by both maps,
+//! - a `Vector` skips leaf chunks that are shared by both vectors, although
+//!   it still walks the leaves one by one,
+//! - a `HashMap` that shares its root with the other map is equal without
+//!   visiting any entry.  Otherwise every entry of both maps is looked up
+//!   in the other map, i.e. the delta calculation is O(n) like that of a
+//!   `std` `HashMap`, since `im` doesn't expose the nodes of the map.
+//!   Values that are shared by both maps are merely equal without having
+//!   to be compared.
+//!
+//! The deltas use the same encoding as their `std` counterparts, i.e.
+//! [`HashMapDelta`], [`BTreeMapDelta`] and [`VecDelta`] respectively, so a
+//! delta computed for one type can be applied to its counterpart.
+//!
+//! [`im`]: https://docs.rs/im
+//! [`HashMap`]: https://docs.rs/im/latest/im/struct.HashMap.html
+//! [`OrdMap`]: https://docs.rs/im/latest/im/struct.OrdMap.html
+//! [`OrdMap::diff`]: https://docs.rs/im/latest/im/struct.OrdMap.html#method.diff
+//! [`Vector`]: https://docs.rs/im/latest/im/struct.Vector.html
+//! [`HashMapDelta`]: ../../struct.HashMapDelta.html
+//! [`BTreeMapDelta`]: ../../struct.BTreeMapDelta.html
+//! [`VecDelta`]: ../../struct.VecDelta.html
+
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{ApplyPolicy, OnFailure, PathSegment};
+use crate::collections::btreemap::{self, BTreeMapDelta};
+use crate::collections::hashmap::{self, HashMapDelta};
+use crate::vec::{EltDelta, VecDelta};
+use ::im::{HashMap, OrdMap, Vector};
+use ::im::ordmap::DiffItem;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+
+impl<K, V> Core for HashMap<K, V>
+where K: Clone + Debug + PartialEq + Eq + Hash + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    type Delta = HashMapDelta<K, V>;
+}
+
+impl<K, V> Apply for HashMap<K, V>
+where K: Clone + Debug + PartialEq + Eq + Hash + Apply
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
+        let mut new: Self = self.clone();
+        for change in delta.0.into_iter().flatten() { match change {
+            hashmap::EntryDelta::Edit { key, value } => {
+                let at = |e: DeltaError| e.at(PathSegment::key(&key));
+                match new.get_mut(&key) {
+                    Some(place) =>
+                        *place = place.apply_with(value, policy).map_err(at)?,
+                    None => match policy.missing_map_key {
+                        OnFailure::Error =>
+                            return Err(at(ExpectedValue!("HashMapDelta<K, V>"))),
+                        OnFailure::Skip => {},
+                        OnFailure::Upsert => {
+                            let value: V = <V>::from_delta(value).map_err(at)?;
+                            new.insert(key, value);
+                        },
+                    },
+                }
+            },
+            hashmap::EntryDelta::Add { key, value } => {
+                let value: V = <V>::from_delta(value)
+                    .map_err(|e| e.at(PathSegment::key(&key)))?;
+                new.insert(key, value);
+            },
+            hashmap::EntryDelta::Remove { key } => { new.remove(&key); },
+        }}
+        Ok(new)
+    }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        delta.validate_into(|key| self.get(key), errors);
+    }
+}
+
+impl<K, V> Delta for HashMap<K, V>
+where K: Clone + Debug + PartialEq + Eq + Hash + Delta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+        let mut changes: Vec<hashmap::EntryDelta<K, V>> = vec![];
+        if !self.ptr_eq(rhs) {
+            for (key, lhs_val) in self.iter() { match rhs.get(key) {
+                Some(rhs_val) if shared_or_eq(lhs_val, rhs_val) => {/*NOP*/},
+                Some(rhs_val) => changes.push(hashmap::EntryDelta::Edit {
+                    key: key.clone(),
+                    value: lhs_val.delta(rhs_val)?,
+                }),
+                None => changes.push(hashmap::EntryDelta::Remove {
+                    key: key.clone(),
+                }),
+            }}
+            for (key, rhs_val) in rhs.iter() {
+                if self.contains_key(key) { continue }
+                changes.push(hashmap::EntryDelta::Add {
+                    key: key.clone(),
+                    value: rhs_val.clone().into_delta()?,
+                });
+            }
+        }
+        Ok(HashMapDelta(if changes.is_empty() { None } else { Some(changes) }))
+    }
+}
+
+impl<K, V> FromDelta for HashMap<K, V>
+where K: Clone + Debug + PartialEq + Eq + Hash + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+        let mut map: Self = Self::new();
+        for (index, change) in delta.0.into_iter().flatten().enumerate() {
+            match change {
+                hashmap::EntryDelta::Add { key, value } => {
+                    let value: V = <V>::from_delta(value)
+                        .map_err(|e| e.at(PathSegment::key(&key)))?;
+                    map.insert(key, value)
+                },
+                _ => return Err(DeltaError::IllegalDelta { index }),
+            };
+        }
+        Ok(map)
+    }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        delta.validate_from_delta(errors);
+    }
+}
+
+impl<K, V> IntoDelta for HashMap<K, V>
+where K: Clone + Debug + PartialEq + Eq + Hash + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn into_delta(self) -> DeltaResult<Self::Delta> {
+        let mut changes: Vec<hashmap::EntryDelta<K, V>> = vec![];
+        for (key, value) in self {
+            changes.push(hashmap::EntryDelta::Add {
+                key,
+                value: value.into_delta()?,
+            });
+        }
+        Ok(HashMapDelta(if changes.is_empty() { None } else { Some(changes) }))
+    }
+}
+
+
+
+impl<K, V> Core for OrdMap<K, V>
+where K: Clone + Debug + PartialEq + Ord + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    type Delta = BTreeMapDelta<K, V>;
+}
+
+impl<K, V> Apply for OrdMap<K, V>
+where K: Clone + Debug + PartialEq + Ord + Apply
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
+        let mut new: Self = self.clone();
+        for change in delta.0.into_iter().flatten() { match change {
+            btreemap::EntryDelta::Edit { key, value } => {
+                let at = |e: DeltaError| e.at(PathSegment::key(&key));
+                match new.get_mut(&key) {
+                    Some(place) =>
+                        *place = place.apply_with(value, policy).map_err(at)?,
+                    None => match policy.missing_map_key {
+                        OnFailure::Error =>
+                            return Err(at(ExpectedValue!("BTreeMapDelta<K, V>"))),
+                        OnFailure::Skip => {},
+                        OnFailure::Upsert => {
+                            let value: V = <V>::from_delta(value).map_err(at)?;
+                            new.insert(key, value);
+                        },
+                    },
+                }
+            },
+            btreemap::EntryDelta::Add { key, value } => {
+                let value: V = <V>::from_delta(value)
+                    .map_err(|e| e.at(PathSegment::key(&key)))?;
+                new.insert(key, value);
+            },
+            btreemap::EntryDelta::Remove { key } => { new.remove(&key); },
+        }}
+        Ok(new)
+    }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        delta.validate_into(|key| self.get(key), errors);
+    }
+}
+
+impl<K, V> Delta for OrdMap<K, V>
+where K: Clone + Debug + PartialEq + Ord + Delta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+        let mut changes: Vec<btreemap::EntryDelta<K, V>> = vec![];
+        for item in self.diff(rhs) { match item {
+            DiffItem::Update { old: (key, lhs_val), new: (_, rhs_val) } =>
+                changes.push(btreemap::EntryDelta::Edit {
+                    key: key.clone(),
+                    value: lhs_val.delta(rhs_val)?,
+                }),
+            DiffItem::Add(key, rhs_val) =>
+                changes.push(btreemap::EntryDelta::Add {
+                    key: key.clone(),
+                    value: rhs_val.clone().into_delta()?,
+                }),
+            DiffItem::Remove(key, _) =>
+                changes.push(btreemap::EntryDelta::Remove { key: key.clone() }),
+        }}
+        Ok(BTreeMapDelta(if changes.is_empty() { None } else { Some(changes) }))
+    }
+}
+
+impl<K, V> FromDelta for OrdMap<K, V>
+where K: Clone + Debug + PartialEq + Ord + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+        let mut map: Self = Self::new();
+        for (index, change) in delta.0.into_iter().flatten().enumerate() {
+            match change {
+                btreemap::EntryDelta::Add { key, value } => {
+                    let value: V = <V>::from_delta(value)
+                        .map_err(|e| e.at(PathSegment::key(&key)))?;
+                    map.insert(key, value)
+                },
+                _ => return Err(DeltaError::IllegalDelta { index }),
+            };
+        }
+        Ok(map)
+    }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        delta.validate_from_delta(errors);
+    }
+}
+
+impl<K, V> IntoDelta for OrdMap<K, V>
+where K: Clone + Debug + PartialEq + Ord + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn into_delta(self) -> DeltaResult<Self::Delta> {
+        let mut changes: Vec<btreemap::EntryDelta<K, V>> = vec![];
+        for (key, value) in self {
+            changes.push(btreemap::EntryDelta::Add {
+                key,
+                value: value.into_delta()?,
+            });
+        }
+        Ok(BTreeMapDelta(if changes.is_empty() { None } else { Some(changes) }))
+    }
+}
+
+
+
+impl<T> Core for Vector<T>
+where T: Clone + Debug + PartialEq + Core
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    type Delta = VecDelta<T>;
+}
+
+impl<T> Apply for Vector<T>
+where T: Clone + Debug + PartialEq + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
+        delta.apply_to(
+            self,
+            policy,
+            |vec| vec.len(),
+            |vec, index| &vec[index],
+            |vec, index, item| { vec.set(index, item); },
+            |vec, item| { vec.push_back(item); Ok(()) },
+            |vec| vec.pop_back().is_some(),
+        )
+    }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        delta.validate_into(self.len(), |index| &self[index], errors);
+    }
+}
+
+impl<T> Delta for Vector<T>
+where T: Clone + Debug + PartialEq + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+        let mut changes: Vec<EltDelta<T>> = vec![];
+        if self.ptr_eq(rhs) { return Ok(VecDelta(changes)); }
+        let min_len = usize::min(self.len(), rhs.len());
+        let (mut lhs_leaves, mut rhs_leaves) = (self.leaves(), rhs.leaves());
+        let (mut lhs_leaf, mut rhs_leaf): (&[T], &[T]) = (&[], &[]);
+        let mut index = 0;
+        while index < min_len {
+            if lhs_leaf.is_empty() { match lhs_leaves.next() {
+                Some(leaf) => lhs_leaf = leaf,
+                None => return bug_detected!(),
+            }}
+            if rhs_leaf.is_empty() { match rhs_leaves.next() {
+                Some(leaf) => rhs_leaf = leaf,
+                None => return bug_detected!(),
+            }}
+            // NOTE: The remainders of both leaves are the same memory,
+            //       so they can be skipped as a whole:
+            if std::ptr::eq(lhs_leaf, rhs_leaf) {
+                index += lhs_leaf.len();
+                lhs_leaf = &[];
+                rhs_leaf = &[];
+                continue;
+            }
+            let (lhs, rhs) = (&lhs_leaf[0], &rhs_leaf[0]);
+            if lhs != rhs {
+                changes.push(EltDelta::Edit { index, item: lhs.delta(rhs)? });
+            }
+            lhs_leaf = &lhs_leaf[1..];
+            rhs_leaf = &rhs_leaf[1..];
+            index += 1;
+        }
+        for rhs in rhs.iter().skip(min_len) {
+            changes.push(EltDelta::Add(rhs.clone().into_delta()?));
+        }
+        if self.len() > min_len {
+            changes.push(EltDelta::Remove { count: self.len() - min_len });
+        }
+        Ok(VecDelta(changes))
+    }
+}
+
+impl<T> FromDelta for Vector<T>
+where T: Clone + Debug + PartialEq + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn from_delta(delta: <Self as Core>::Delta) -> DeltaResult<Self> {
+        let mut vec: Self = Vector::new();
+        for (index, element) in delta.0.into_iter().enumerate() {
+            match element {
+                EltDelta::Add(elt) => vec.push_back(
+                    <T>::from_delta(elt)
+                        .map_err(|e| e.at(PathSegment::Index(index)))?
+                ),
+                _ => return Err(DeltaError::IllegalDelta { index }),
+            }
+        }
+        Ok(vec)
+    }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        delta.validate_from_delta(errors);
+    }
+}
+
+impl<T> IntoDelta for Vector<T>
+where T: Clone + Debug + PartialEq + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn into_delta(self) -> DeltaResult<<Self as Core>::Delta> {
+        let mut changes: Vec<EltDelta<T>> = vec![];
+        for elt in self {
+            changes.push(EltDelta::Add(elt.into_delta()?));
+        }
+        Ok(VecDelta(changes))
+    }
+}
+
+/// Values that live at the same address are shared by both collections,
+/// and so they are equal without having to be compared.
+fn shared_or_eq<T: PartialEq>(lhs: &T, rhs: &T) -> bool {
+    std::ptr::eq(lhs, rhs) || lhs == rhs
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn HashMap__apply__different_values() -> DeltaResult<()> {
+        let map0: HashMap<String, u16> = (0 .. 100u16)
+            .map(|n| (n.to_string(), n))
+            .collect();
+        let mut map1 = map0.clone();
+        map1.insert("42".into(), 4242);
+        map1.insert("bar".into(), 300);
+        map1.remove("7");
+        let delta = map0.delta(&map1)?;
+        assert_eq!(delta.len(), 3);
+        let map2 = map0.apply(delta.clone())?;
+        assert_eq!(map1, map2);
+        // NOTE: The delta is compatible with that of a std `HashMap`:
+        let std_map0: std::collections::HashMap<String, u16> = map0.clone()
+            .into_iter()
+            .collect();
+        let std_map1: std::collections::HashMap<String, u16> = map1.clone()
+            .into_iter()
+            .collect();
+        assert_eq!(std_map0.apply(delta)?, std_map1);
+        Ok(())
+    }
+
+    #[test]
+    fn OrdMap__apply__different_values() -> DeltaResult<()> {
+        let map0: OrdMap<u16, String> = (0 .. 1000u16)
+            .map(|n| (n, n.to_string()))
+            .collect();
+        let map1 = map0.update(500, "five hundred".into()).without(&3);
+        let delta = map0.delta(&map1)?;
+        assert_eq!(delta, BTreeMapDelta(Some(vec![
+            btreemap::EntryDelta::Remove { key: 3 },
+            btreemap::EntryDelta::Edit {
+                key: 500,
+                value: "500".to_string().delta(&"five hundred".to_string())?,
+            },
+        ])));
+        let map2 = map0.apply(delta)?;
+        assert_eq!(map1, map2);
+        assert_eq!(map0.delta(&map0.clone())?, BTreeMapDelta(None));
+        Ok(())
+    }
+
+    #[test]
+    fn OrdMap__apply__std_compound_values() -> DeltaResult<()> {
+        use std::collections::BTreeMap;
+        let map0: OrdMap<String, (u8, u8)> = (0 .. 10u8)
+            .map(|n| (n.to_string(), (n, n)))
+            .collect();
+        let map1 = map0.update("5".into(), (5, 50)).without("3");
+        let std_map0: BTreeMap<String, (u8, u8)> = map0.clone()
+            .into_iter()
+            .collect();
+        let std_map1: BTreeMap<String, (u8, u8)> = map1.clone()
+            .into_iter()
+            .collect();
+        // NOTE: The deltas are compatible with those of a std `BTreeMap`,
+        //       in both directions:
+        assert_eq!(std_map0.apply(map0.delta(&map1)?)?, std_map1);
+        assert_eq!(map0.apply(std_map0.delta(&std_map1)?)?, map1);
+        Ok(())
+    }
+
+    #[test]
+    fn Vector__apply__different_values() -> DeltaResult<()> {
+        let vec0: Vector<u32> = (0 .. 1000).collect();
+        let mut vec1 = vec0.clone();
+        vec1.set(10, 1010);
+        vec1.set(900, 1900);
+        vec1.truncate(950);
+        let delta = vec0.delta(&vec1)?;
+        assert_eq!(delta, VecDelta(vec![
+            EltDelta::Edit { index:  10, item: 1010u32.into_delta()? },
+            EltDelta::Edit { index: 900, item: 1900u32.into_delta()? },
+            EltDelta::Remove { count: 50 },
+        ]));
+        let vec2 = vec0.apply(delta.clone())?;
+        assert_eq!(vec1, vec2);
+        // NOTE: The delta is compatible with that of a `Vec`:
+        let vec3 = vec0.iter().cloned().collect::<Vec<u32>>().apply(delta)?;
+        assert!(vec1.iter().eq(vec3.iter()));
+        Ok(())
+    }
+
+    #[test]
+    fn Vector__delta__unshared_values() -> DeltaResult<()> {
+        let vec0: Vector<u32> = (0 .. 100).collect();
+        let vec1: Vector<u32> = (0 .. 120).map(|n| n * 2).collect();
+        let delta = vec0.delta(&vec1)?;
+        let vec2 = vec0.apply(delta)?;
+        assert_eq!(vec1, vec2);
+        Ok(())
+    }
+}
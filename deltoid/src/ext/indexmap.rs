@@ -0,0 +1,826 @@
+//! Deltoid impls for [`IndexMap`] and [`IndexSet`].
+//!
+//! Unlike the deltas of `HashMap` and `HashSet`, the deltas of these types
+//! also capture the iteration order of the entries.  Entries that are added
+//! are appended, and entries that are removed are shifted out so that the
+//! order of the remaining entries is preserved.  If the resulting order still
+//! differs from the order of the new value, the delta includes the new order.
+//!
+//! [`IndexMap`]: https://docs.rs/indexmap/latest/indexmap/map/struct.IndexMap.html
+//! [`IndexSet`]: https://docs.rs/indexmap/latest/indexmap/set/struct.IndexSet.html
+
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{ApplyPolicy, OnFailure, PathSegment};
+use crate::{DynDelta, ToDynDelta};
+use ::indexmap::{IndexMap, IndexSet};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+
+impl<K, V> Core for IndexMap<K, V>
+where K: Clone + Debug + PartialEq + Eq + Hash + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    type Delta = IndexMapDelta<K, V>;
+}
+
+impl<K, V> Apply for IndexMap<K, V>
+where K: Clone + Debug + PartialEq + Eq + Hash + Apply
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
+        let mut new: Self = self.clone();
+        for change in delta.entries.into_iter().flatten() { match change {
+            EntryDelta::Edit { key, value } => {
+                let at = |e: DeltaError| e.at(PathSegment::key(&key));
+                match new.get_mut(&key) {
+                    Some(place) =>
+                        *place = place.apply_with(value, policy).map_err(at)?,
+                    None => match policy.missing_map_key {
+                        OnFailure::Error =>
+                            return Err(at(ExpectedValue!("IndexMapDelta<K, V>"))),
+                        OnFailure::Skip => {},
+                        OnFailure::Upsert => {
+                            let value: V = <V>::from_delta(value).map_err(at)?;
+                            new.insert(key, value);
+                        },
+                    },
+                }
+            },
+            EntryDelta::Add { key, value } => {
+                let value: V = <V>::from_delta(value)
+                    .map_err(|e| e.at(PathSegment::key(&key)))?;
+                new.insert(key, value);
+            },
+            EntryDelta::Remove { key } => { new.shift_remove(&key); },
+        }}
+        match delta.order {
+            None => Ok(new),
+            Some(order) => reorder_map(new, order, policy.missing_map_key),
+        }
+    }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        // NOTE: the entries that the delta touches, and their value if it's
+        //       still that of `self`; other values are only known by
+        //       applying the delta, so edits of them only check the key
+        let mut touched: HashMap<&K, Option<Option<&V>>> = HashMap::new();
+        for change in delta.iter() { match change {
+            EntryDelta::Edit { key, value } => {
+                let start: usize = errors.len();
+                let entry: Option<Option<&V>> = touched.get(key).cloned()
+                    .unwrap_or_else(|| self.get(key).map(Some));
+                match entry {
+                    Some(Some(place)) => place.validate_into(value, errors),
+                    Some(None) => {},
+                    None => errors.push(ExpectedValue!("IndexMapDelta<K, V>")),
+                }
+                DeltaError::scope_all(errors, start, &PathSegment::key(key));
+                if entry.is_some() { touched.insert(key, Some(None)); }
+            },
+            EntryDelta::Add { key, value } => {
+                let start: usize = errors.len();
+                <V>::validate_from_delta(value, errors);
+                DeltaError::scope_all(errors, start, &PathSegment::key(key));
+                touched.insert(key, Some(None));
+            },
+            EntryDelta::Remove { key } => { touched.insert(key, None); },
+        }}
+        if let Some(order) = &delta.order {
+            let is_present = |key: &K| match touched.get(key) {
+                Some(entry) => entry.is_some(),
+                None => self.contains_key(key),
+            };
+            let len: usize = self.keys()
+                .filter(|key| is_present(key))
+                .count()
+                + touched.iter()
+                    .filter(|(key, entry)| entry.is_some() && !self.contains_key(**key))
+                    .count();
+            validate_order(order, len, is_present, errors);
+        }
+    }
+}
+
+impl<K, V> Delta for IndexMap<K, V>
+where K: Clone + Debug + PartialEq + Eq + Hash + Delta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+        let mut changes: Vec<EntryDelta<K, V>> = vec![];
+        for (key, lhs_val) in self.iter() { match rhs.get(key) {
+            Some(rhs_val) if lhs_val == rhs_val => {/*NOP*/},
+            Some(rhs_val) => changes.push(EntryDelta::Edit {
+                key: key.clone(),
+                value: lhs_val.delta(rhs_val)?,
+            }),
+            None => changes.push(EntryDelta::Remove { key: key.clone() }),
+        }}
+        for (key, rhs_val) in rhs.iter() {
+            if self.contains_key(key) { continue }
+            changes.push(EntryDelta::Add {
+                key: key.clone(),
+                value: rhs_val.clone().into_delta()?,
+            });
+        }
+        // NOTE: This is the key order that applying `changes` results in:
+        let applied_order = self.keys()
+            .filter(|key| rhs.contains_key(*key))
+            .chain(rhs.keys().filter(|key| !self.contains_key(*key)));
+        let order = if applied_order.eq(rhs.keys()) {
+            None
+        } else {
+            Some(rhs.keys().cloned().collect())
+        };
+        Ok(IndexMapDelta {
+            entries: if changes.is_empty() { None } else { Some(changes) },
+            order,
+        })
+    }
+}
+
+impl<K, V> FromDelta for IndexMap<K, V>
+where K: Clone + Debug + PartialEq + Eq + Hash + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+        let mut map: Self = Self::new();
+        for (index, change) in delta.entries.into_iter().flatten().enumerate() {
+            match change {
+                EntryDelta::Add { key, value } => {
+                    let value: V = <V>::from_delta(value)
+                        .map_err(|e| e.at(PathSegment::key(&key)))?;
+                    map.insert(key, value)
+                },
+                _ => return Err(DeltaError::IllegalDelta { index }),
+            };
+        }
+        match delta.order {
+            None => Ok(map),
+            Some(order) => reorder_map(map, order, OnFailure::Error),
+        }
+    }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        let mut keys: HashSet<&K> = HashSet::new();
+        for (index, change) in delta.iter().enumerate() { match change {
+            EntryDelta::Add { key, value } => {
+                let start: usize = errors.len();
+                <V>::validate_from_delta(value, errors);
+                DeltaError::scope_all(errors, start, &PathSegment::key(key));
+                keys.insert(key);
+            },
+            _ => errors.push(DeltaError::IllegalDelta { index }),
+        }}
+        if let Some(order) = &delta.order {
+            validate_order(order, keys.len(), |key| keys.contains(key), errors);
+        }
+    }
+}
+
+impl<K, V> IntoDelta for IndexMap<K, V>
+where K: Clone + Debug + PartialEq + Eq + Hash + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+      V: Clone + Debug + PartialEq + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn into_delta(self) -> DeltaResult<Self::Delta> {
+        let mut changes: Vec<EntryDelta<K, V>> = vec![];
+        for (key, value) in self {
+            changes.push(EntryDelta::Add { key, value: value.into_delta()? });
+        }
+        Ok(IndexMapDelta {
+            entries: if changes.is_empty() { None } else { Some(changes) },
+            order: None,
+        })
+    }
+}
+
+/// Push every problem with `reorder_map()` ordering the keys of a map with
+/// `len` keys, for which `is_present` holds, as in `order`.
+fn validate_order<K>(
+    order: &[K],
+    len: usize,
+    is_present: impl Fn(&K) -> bool,
+    errors: &mut Vec<DeltaError>,
+) where K: Eq + Hash {
+    if let Err(error) = ensure_eq!(len, order.len()) {
+        errors.push(error);
+        return;
+    }
+    let mut seen: HashSet<&K> = HashSet::new();
+    for key in order {
+        if !is_present(key) || !seen.insert(key) {
+            errors.push(ExpectedValue!("IndexMapDelta<K, V>"));
+        }
+    }
+}
+
+/// Rearrange the entries of `map` so that its keys are ordered as in `order`.
+/// Unless `on_failure` is `OnFailure::Error`, the keys of `order` that `map`
+/// lacks are skipped, and those that `order` lacks are moved to the end.
+fn reorder_map<K, V>(
+    mut map: IndexMap<K, V>,
+    order: Vec<K>,
+    on_failure: OnFailure,
+) -> DeltaResult<IndexMap<K, V>>
+where K: Eq + Hash {
+    if on_failure != OnFailure::Error {
+        let rank = order_ranks(&order);
+        map.sort_by(|k0, _, k1, _| rank(k0).cmp(&rank(k1)));
+        return Ok(map);
+    }
+    ensure_eq!(map.len(), order.len())?;
+    let mut reordered = IndexMap::with_capacity(order.len());
+    for key in order {
+        let value = map.swap_remove(&key)
+            .ok_or_else(|| ExpectedValue!("IndexMapDelta<K, V>"))?;
+        reordered.insert(key, value);
+    }
+    Ok(reordered)
+}
+
+
+#[derive(Clone, PartialEq)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub struct IndexMapDelta<K: Core, V: Core> {
+    /// The entries that were edited, added or removed.
+    #[doc(hidden)] pub entries: Option<Vec<EntryDelta<K, V>>>,
+    /// The new key order, iff. applying `entries` doesn't already produce it.
+    #[doc(hidden)] pub order: Option<Vec<K>>,
+}
+
+impl<K, V> IndexMapDelta<K, V>
+where K: Core,
+      V: Core,
+{
+    pub fn iter<'d>(&'d self) -> Box<dyn Iterator<Item = &'d EntryDelta<K, V>> + 'd> {
+        match &self.entries {
+            Some(delta) => Box::new(delta.iter()),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.entries {
+            Some(delta) => delta.len(),
+            None => 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0 && self.order.is_none()
+    }
+
+    /// Returns the new key order, if the delta changes it.
+    pub fn order(&self) -> Option<&[K]> { self.order.as_deref() }
+}
+
+impl<K, V> std::fmt::Debug for IndexMapDelta<K, V>
+where K: std::fmt::Debug + Core,
+      V: std::fmt::Debug + Core
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        let mut buf = f.debug_struct("IndexMapDelta");
+        match &self.entries {
+            Some(entries) => buf.field("entries", entries),
+            None => buf.field("entries", &Vec::<EntryDelta<K, V>>::new()),
+        };
+        if let Some(order) = &self.order {
+            buf.field("order", order);
+        }
+        buf.finish()
+    }
+}
+
+
+impl<K, V> ToDynDelta for IndexMapDelta<K, V>
+where K: std::fmt::Debug + Core,
+      V: Core,
+      V::Delta: ToDynDelta,
+{
+    fn to_dyn_delta(&self) -> DynDelta {
+        let mut changes: Vec<DynDelta> = self.iter()
+            .map(ToDynDelta::to_dyn_delta)
+            .collect();
+        if let Some(order) = &self.order {
+            changes.push(DynDelta::field("order", DynDelta::replace(order)));
+        }
+        DynDelta::changes(changes)
+    }
+}
+
+
+#[derive(Clone, PartialEq)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub enum EntryDelta<K, V: Core> {
+    /// Edit a `value` of a given `key`
+    Edit { key: K, value: <V as Core>::Delta },
+    /// Append a given `key` and `value` entry.
+    Add { key: K, value: <V as Core>::Delta },
+    /// Remove the entry with a given `key` from the map,
+    /// preserving the order of the remaining entries.
+    Remove { key: K },
+}
+
+impl<K, V> std::fmt::Debug for EntryDelta<K, V>
+where K: std::fmt::Debug,
+      V: std::fmt::Debug + Core
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match &self {
+            Self::Edit { key, value } => f.debug_struct("Edit")
+                .field("key", key)
+                .field("value", value)
+                .finish(),
+            Self::Add { key, value } => f.debug_struct("Add")
+                .field("key", key)
+                .field("value", value)
+                .finish(),
+            Self::Remove { key } => f.debug_struct("Remove")
+                .field("key", key)
+                .finish(),
+        }
+    }
+}
+
+impl<K, V> ToDynDelta for EntryDelta<K, V>
+where K: std::fmt::Debug,
+      V: Core,
+      V::Delta: ToDynDelta,
+{
+    fn to_dyn_delta(&self) -> DynDelta {
+        match &self {
+            Self::Edit { key, value } =>
+                DynDelta::key(key, value.to_dyn_delta()),
+            Self::Add { key, value } =>
+                DynDelta::insert(key, value.to_dyn_delta()),
+            Self::Remove { key } => DynDelta::key(key, DynDelta::Remove),
+        }
+    }
+}
+
+
+
+impl<T> Core for IndexSet<T>
+where T: Clone + Debug + PartialEq + Eq + Hash + Core
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    type Delta = IndexSetDelta<T>;
+}
+
+impl<T> Apply for IndexSet<T>
+where T: Clone + Debug + PartialEq + Eq + Hash + Apply
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
+        let mut new: Self = self.clone();
+        for change in delta.items.into_iter().flatten() { match change {
+            ItemDelta::Add { item } => { new.insert(item); },
+            ItemDelta::Remove { item } => {
+                let removed: bool = new.shift_remove(&item);
+                if !removed && policy.missing_set_item == OnFailure::Error {
+                    return Err(ExpectedValue!("IndexSetDelta<T>"));
+                }
+            },
+        }}
+        match delta.order {
+            None => Ok(new),
+            Some(order) => reorder_set(new, order, policy.missing_set_item),
+        }
+    }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        // NOTE: only reordering the items can fail
+        let order: &[T] = match &delta.order {
+            Some(order) => order,
+            None => return,
+        };
+        let mut touched: HashMap<&T, bool> = HashMap::new();
+        for change in delta.iter() { match change {
+            ItemDelta::Add { item } => { touched.insert(item, true); },
+            ItemDelta::Remove { item } => { touched.insert(item, false); },
+        }}
+        let is_present = |item: &T| match touched.get(item) {
+            Some(present) => *present,
+            None => self.contains(item),
+        };
+        let len: usize = self.iter().filter(|item| is_present(item)).count()
+            + touched.iter()
+                .filter(|(item, present)| **present && !self.contains(**item))
+                .count();
+        validate_set_order(order, len, is_present, errors);
+    }
+}
+
+impl<T> Delta for IndexSet<T>
+where T: Clone + Debug + PartialEq + Eq + Hash + Delta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+        let mut changes: Vec<ItemDelta<T>> = vec![];
+        for removal in self.difference(rhs) {
+            changes.push(ItemDelta::Remove { item: removal.clone() });
+        }
+        for addition in rhs.difference(self) {
+            changes.push(ItemDelta::Add { item: addition.clone() });
+        }
+        // NOTE: This is the item order that applying `changes` results in:
+        let applied_order = self.intersection(rhs)
+            .chain(rhs.difference(self));
+        let order = if applied_order.eq(rhs.iter()) {
+            None
+        } else {
+            Some(rhs.iter().cloned().collect())
+        };
+        Ok(IndexSetDelta {
+            items: if changes.is_empty() { None } else { Some(changes) },
+            order,
+        })
+    }
+}
+
+impl<T> FromDelta for IndexSet<T>
+where T: Clone + Debug + PartialEq + Eq + Hash + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+        let mut set: Self = Self::new();
+        for (index, change) in delta.items.into_iter().flatten().enumerate() {
+            match change {
+                ItemDelta::Add { item } => set.insert(item),
+                _ => return Err(DeltaError::IllegalDelta { index }),
+            };
+        }
+        match delta.order {
+            None => Ok(set),
+            Some(order) => reorder_set(set, order, OnFailure::Error),
+        }
+    }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        let mut items: HashSet<&T> = HashSet::new();
+        for (index, change) in delta.iter().enumerate() { match change {
+            ItemDelta::Add { item } => { items.insert(item); },
+            _ => errors.push(DeltaError::IllegalDelta { index }),
+        }}
+        if let Some(order) = &delta.order {
+            validate_set_order(order, items.len(), |item| items.contains(item), errors);
+        }
+    }
+}
+
+impl<T> IntoDelta for IndexSet<T>
+where T: Clone + Debug + PartialEq + Eq + Hash + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize,
+{
+    fn into_delta(self) -> DeltaResult<Self::Delta> {
+        let changes: Vec<ItemDelta<T>> = self.into_iter()
+            .map(|item| ItemDelta::Add { item })
+            .collect();
+        Ok(IndexSetDelta {
+            items: if changes.is_empty() { None } else { Some(changes) },
+            order: None,
+        })
+    }
+}
+
+/// Push every problem with `reorder_set()` ordering the items of a set with
+/// `len` items, for which `is_present` holds, as in `order`.
+fn validate_set_order<T>(
+    order: &[T],
+    len: usize,
+    is_present: impl Fn(&T) -> bool,
+    errors: &mut Vec<DeltaError>,
+) where T: Eq + Hash {
+    if let Err(error) = ensure_eq!(len, order.len()) {
+        errors.push(error);
+        return;
+    }
+    if !order.iter().all(is_present) {
+        errors.push(ExpectedValue!("IndexSetDelta<T>"));
+    }
+}
+
+/// Rearrange the items of `set` so that they are ordered as in `order`.
+/// Unless `on_failure` is `OnFailure::Error`, the items of `order` that `set`
+/// lacks are skipped, and those that `order` lacks are moved to the end.
+fn reorder_set<T>(
+    mut set: IndexSet<T>,
+    order: Vec<T>,
+    on_failure: OnFailure,
+) -> DeltaResult<IndexSet<T>>
+where T: Eq + Hash {
+    if on_failure != OnFailure::Error {
+        let rank = order_ranks(&order);
+        set.sort_by(|t0, t1| rank(t0).cmp(&rank(t1)));
+        return Ok(set);
+    }
+    ensure_eq!(set.len(), order.len())?;
+    for item in order.iter() {
+        if !set.contains(item) {
+            return Err(ExpectedValue!("IndexSetDelta<T>"));
+        }
+    }
+    Ok(order.into_iter().collect())
+}
+
+/// Return the position of a key or item in `order`, where those that `order`
+/// lacks come last.  The sort that uses it is stable, so those keep their
+/// relative order.
+fn order_ranks<T: Eq + Hash>(order: &[T]) -> impl Fn(&T) -> usize + '_ {
+    let mut ranks: HashMap<&T, usize> = HashMap::with_capacity(order.len());
+    for (rank, t) in order.iter().enumerate() {
+        ranks.entry(t).or_insert(rank);
+    }
+    move |t: &T| ranks.get(t).copied().unwrap_or(usize::MAX)
+}
+
+
+#[derive(Clone, PartialEq)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub struct IndexSetDelta<T> {
+    /// The items that were added or removed.
+    #[doc(hidden)] pub items: Option<Vec<ItemDelta<T>>>,
+    /// The new item order, iff. applying `items` doesn't already produce it.
+    #[doc(hidden)] pub order: Option<Vec<T>>,
+}
+
+impl<T> IndexSetDelta<T> {
+    pub fn iter<'d>(&'d self) -> Box<dyn Iterator<Item = &'d ItemDelta<T>> + 'd> {
+        match &self.items {
+            Some(delta) => Box::new(delta.iter()),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.items {
+            Some(delta) => delta.len(),
+            None => 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0 && self.order.is_none()
+    }
+
+    /// Returns the new item order, if the delta changes it.
+    pub fn order(&self) -> Option<&[T]> { self.order.as_deref() }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for IndexSetDelta<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        let mut buf = f.debug_struct("IndexSetDelta");
+        match &self.items {
+            Some(items) => buf.field("items", items),
+            None => buf.field("items", &Vec::<ItemDelta<T>>::new()),
+        };
+        if let Some(order) = &self.order {
+            buf.field("order", order);
+        }
+        buf.finish()
+    }
+}
+
+
+/// Set items are hashed, so they are stored as-is rather than as deltas.
+#[derive(Clone, Debug, PartialEq)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub enum ItemDelta<T> {
+    /// Append a given `item`.
+    Add { item: T },
+    /// Remove a given `item`, preserving the order of the remaining items.
+    Remove { item: T },
+}
+
+impl<T: std::fmt::Debug> ToDynDelta for IndexSetDelta<T> {
+    fn to_dyn_delta(&self) -> DynDelta {
+        let mut changes: Vec<DynDelta> = self.iter()
+            .map(|item| match item {
+                ItemDelta::Add { item } =>
+                    DynDelta::SetAdd(Box::new(DynDelta::replace(item))),
+                ItemDelta::Remove { item } =>
+                    DynDelta::SetRemove(Box::new(DynDelta::replace(item))),
+            })
+            .collect();
+        if let Some(order) = &self.order {
+            changes.push(DynDelta::field("order", DynDelta::replace(order)));
+        }
+        DynDelta::changes(changes)
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use super::*;
+
+    macro_rules! map {
+        ($($key:expr => $val:expr),* $(,)?) => {{
+            let mut map = IndexMap::new();
+            $( map.insert($key, $val); )*
+                map
+        }}
+    }
+
+    #[test]
+    fn IndexMap__delta__same_values() -> DeltaResult<()> {
+        let map0: IndexMap<String, usize> = map! {
+            "bar".into() => 300usize,
+            "foo".into() =>  42usize,
+        };
+        let map1 = map0.clone();
+        let delta = map0.delta(&map1)?;
+        assert_eq!(delta, IndexMapDelta { entries: None, order: None });
+        let json_string = serde_json::to_string(&delta)
+            .expect("Could not serialize to json");
+        assert_eq!(json_string, "{\"entries\":null,\"order\":null}");
+        let map2 = map0.apply(delta)?;
+        assert_eq!(map1, map2);
+        Ok(())
+    }
+
+    #[test]
+    fn IndexMap__delta__different_values() -> DeltaResult<()> {
+        let map0: IndexMap<String, usize> = map! {
+            "bar".into()     => 300usize,
+            "foo".into()     =>  42usize,
+            "floozie".into() =>   0usize,
+        };
+        let map1: IndexMap<String, usize> = map! {
+            "bar".into() => 350usize,
+            "foo".into() =>  42usize,
+            "baz".into() => 9000usize,
+        };
+        let delta = map0.delta(&map1)?;
+        assert_eq!(delta, IndexMapDelta {
+            entries: Some(vec![
+                EntryDelta::Edit { key: "bar".into(), value: 350usize.into_delta()? },
+                EntryDelta::Remove { key: "floozie".into() },
+                EntryDelta::Add { key: "baz".into(), value: 9000usize.into_delta()? },
+            ]),
+            order: None,
+        });
+        let map2 = map0.apply(delta)?;
+        assert_eq!(map1, map2);
+        assert!(map1.keys().eq(map2.keys()));
+        Ok(())
+    }
+
+    #[test]
+    fn IndexMap__apply__reordered_values() -> DeltaResult<()> {
+        let map0: IndexMap<String, usize> = map! {
+            "bar".into() => 300usize,
+            "foo".into() =>  42usize,
+            "baz".into() =>   7usize,
+        };
+        let map1: IndexMap<String, usize> = map! {
+            "baz".into() =>   7usize,
+            "bar".into() => 300usize,
+            "foo".into() =>  42usize,
+        };
+        let delta = map0.delta(&map1)?;
+        assert_eq!(delta.entries, None);
+        assert_eq!(delta.order(), Some(&["baz".into(), "bar".into(), "foo".into()][..]));
+        let map2 = map0.apply(delta)?;
+        assert!(map1.keys().eq(map2.keys()));
+        Ok(())
+    }
+
+    #[test]
+    fn IndexMap__validate() -> DeltaResult<()> {
+        let map0: IndexMap<String, usize> = map! {
+            "bar".into() => 300usize,
+            "foo".into() =>  42usize,
+        };
+        let map1: IndexMap<String, usize> = map! {
+            "baz".into() =>   7usize,
+            "bar".into() => 350usize,
+        };
+        assert_eq!(map0.validate(&map0.delta(&map1)?), Ok(()));
+        let delta = IndexMapDelta {
+            entries: Some(vec![
+                EntryDelta::Edit { key: "qux".into(), value: 1usize.into_delta()? },
+                EntryDelta::Remove { key: "bar".into() },
+            ]),
+            order: Some(vec!["bar".into(), "foo".into()]),
+        };
+        assert_eq!(map0.validate(&delta).unwrap_err().len(), 2);
+        assert!(map0.apply(delta).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn IndexSet__apply__different_values() -> DeltaResult<()> {
+        let set0: IndexSet<u8> = vec![1, 2, 3, 4].into_iter().collect();
+        let set1: IndexSet<u8> = vec![4, 2, 5].into_iter().collect();
+        let delta = set0.delta(&set1)?;
+        let set2 = set0.apply(delta.clone())?;
+        assert!(set1.iter().eq(set2.iter()));
+        let set3 = IndexSet::from_delta(set1.clone().into_delta()?)?;
+        assert!(set1.iter().eq(set3.iter()));
+        Ok(())
+    }
+
+    #[test]
+    fn IndexMap__apply_with__missing_map_key() -> DeltaResult<()> {
+        let map0: IndexMap<String, usize> = map! { "foo".into() => 42usize };
+        let delta = IndexMapDelta {
+            entries: Some(vec![
+                EntryDelta::Edit { key: "qux".into(), value: 1usize.into_delta()? },
+            ]),
+            order: None,
+        };
+        assert!(map0.apply_with(delta.clone(), &ApplyPolicy::strict()).is_err());
+        assert_eq!(map0.apply_with(delta.clone(), &ApplyPolicy::lenient())?, map0);
+        let map1 = map0.apply_with(delta, &ApplyPolicy::upsert())?;
+        assert_eq!(map1, map! { "foo".into() => 42usize, "qux".into() => 1usize });
+        Ok(())
+    }
+
+    #[test]
+    fn IndexSet__apply_with__missing_set_item() -> DeltaResult<()> {
+        let set0: IndexSet<u8> = vec![1, 2].into_iter().collect();
+        let delta = IndexSetDelta {
+            items: Some(vec![ItemDelta::Remove { item: 3 }]),
+            order: None,
+        };
+        assert!(set0.apply_with(delta.clone(), &ApplyPolicy::strict()).is_err());
+        assert_eq!(set0.apply(delta)?, set0);
+        Ok(())
+    }
+
+    #[test]
+    fn IndexMap__apply_with__lenient_order() -> DeltaResult<()> {
+        let map0: IndexMap<String, usize> = map! {
+            "foo".into() => 1usize,
+            "bar".into() => 2usize,
+            "baz".into() => 3usize,
+        };
+        let delta = IndexMapDelta {
+            entries: Some(vec![
+                EntryDelta::Edit { key: "qux".into(), value: 4usize.into_delta()? },
+            ]),
+            order: Some(vec!["qux".into(), "bar".into(), "foo".into()]),
+        };
+        assert!(map0.apply_with(delta.clone(), &ApplyPolicy::strict()).is_err());
+        let map1 = map0.apply_with(delta, &ApplyPolicy::lenient())?;
+        assert_eq!(map1, map0);
+        assert_eq!(map1.keys().collect::<Vec<_>>(), vec!["bar", "foo", "baz"]);
+        Ok(())
+    }
+
+    #[test]
+    fn IndexSet__apply_with__lenient_order() -> DeltaResult<()> {
+        let set0: IndexSet<u8> = vec![1, 2, 4].into_iter().collect();
+        let delta = IndexSetDelta {
+            items: Some(vec![ItemDelta::Remove { item: 3 }]),
+            order: Some(vec![3, 2, 1]),
+        };
+        assert!(set0.apply_with(delta.clone(), &ApplyPolicy::strict()).is_err());
+        let set1 = set0.apply_with(delta, &ApplyPolicy::lenient())?;
+        assert_eq!(set1.into_iter().collect::<Vec<_>>(), vec![2, 1, 4]);
+        Ok(())
+    }
+}
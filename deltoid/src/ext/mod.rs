@@ -0,0 +1,15 @@
+//! Deltoid impls for types defined in third-party crates.
+//!
+//! Each submodule is gated behind a cargo feature with the same name as the
+//! crate it provides impls for.  The submodules are not glob-exported from
+//! the crate root because their delta type names may overlap, e.g. both
+//! `chrono` and `time` define a `Duration` type.
+
+#[cfg(feature = "arrayvec")]   pub mod arrayvec;
+#[cfg(feature = "chrono")]     pub mod chrono;
+#[cfg(feature = "im")]         pub mod im;
+#[cfg(feature = "indexmap")]   pub mod indexmap;
+#[cfg(feature = "rpds")]       pub mod rpds;
+#[cfg(feature = "serde_json")] pub mod serde_json;
+#[cfg(feature = "smallvec")]   pub mod smallvec;
+#[cfg(feature = "time")]       pub mod time;
@@ -0,0 +1,385 @@
+//! Deltoid impls for the persistent [`HashTrieMap`], [`RedBlackTreeMap`] and
+//! [`Vector`] types of the [`rpds`] crate, as well as their `*Sync` variants.
+//!
+//! These types share structure between versions, but `rpds` doesn't expose
+//! their nodes, so the delta calculations only take advantage of that in
+//! part:
+//! - maps that share their root are equal without visiting any entry,
+//! - entries and elements that are shared by both versions are equal
+//!   without having to be compared.
+//!
+//! Otherwise the delta calculations visit every entry or element of both
+//! versions, i.e. they are O(n) like those of their `std` counterparts,
+//! even if the versions differ in only a few places.
+//!
+//! The deltas use the same encoding as their `std` counterparts, i.e.
+//! [`HashMapDelta`], [`BTreeMapDelta`] and [`VecDelta`] respectively, so a
+//! delta computed for one type can be applied to its counterpart.
+//!
+//! [`rpds`]: https://docs.rs/rpds
+//! [`HashTrieMap`]: https://docs.rs/rpds/latest/rpds/map/hash_trie_map/struct.HashTrieMap.html
+//! [`RedBlackTreeMap`]: https://docs.rs/rpds/latest/rpds/map/red_black_tree_map/struct.RedBlackTreeMap.html
+//! [`Vector`]: https://docs.rs/rpds/latest/rpds/vector/struct.Vector.html
+//! [`HashMapDelta`]: ../../struct.HashMapDelta.html
+//! [`BTreeMapDelta`]: ../../struct.BTreeMapDelta.html
+//! [`VecDelta`]: ../../struct.VecDelta.html
+
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{ApplyPolicy, OnFailure, PathSegment};
+use crate::collections::btreemap::{self, BTreeMapDelta};
+use crate::collections::hashmap::{self, HashMapDelta};
+use crate::vec::{EltDelta, VecDelta};
+use ::rpds::{
+    HashTrieMap, HashTrieMapSync, RedBlackTreeMap, RedBlackTreeMapSync,
+    Vector, VectorSync,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+
+macro_rules! impl_delta_trait_for_map_types {
+    ( $(
+        $map:ident => $delta:ident, $entry:ident, [$($key_bound:tt)+]
+    );* $(;)? ) => {
+        $(
+            impl<K, V> Core for $map<K, V>
+            where K: Clone + Debug + PartialEq + $($key_bound)+ + Core
+                + for<'de> Deserialize<'de>
+                + Serialize,
+                  V: Clone + Debug + PartialEq + Core
+                + for<'de> Deserialize<'de>
+                + Serialize,
+            {
+                type Delta = $delta<K, V>;
+            }
+
+            impl<K, V> Apply for $map<K, V>
+            where K: Clone + Debug + PartialEq + $($key_bound)+ + Apply
+                + for<'de> Deserialize<'de>
+                + Serialize,
+                  V: Clone + Debug + PartialEq + Apply + FromDelta
+                + for<'de> Deserialize<'de>
+                + Serialize,
+            {
+                fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+                    self.apply_with(delta, &ApplyPolicy::default())
+                }
+
+                fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                              -> DeltaResult<Self>
+                {
+                    let mut new: Self = self.clone();
+                    for change in delta.0.into_iter().flatten() { match change {
+                        $entry::EntryDelta::Edit { key, value } => {
+                            let at = |e: DeltaError| e.at(PathSegment::key(&key));
+                            match new.get_mut(&key) {
+                                Some(place) => *place = place.apply_with(value, policy)
+                                    .map_err(at)?,
+                                None => match policy.missing_map_key {
+                                    OnFailure::Error =>
+                                        return Err(at(ExpectedValue!(stringify!($delta)))),
+                                    OnFailure::Skip => {},
+                                    OnFailure::Upsert => {
+                                        let value: V = <V>::from_delta(value)
+                                            .map_err(at)?;
+                                        new.insert_mut(key, value);
+                                    },
+                                },
+                            }
+                        },
+                        $entry::EntryDelta::Add { key, value } => {
+                            let value: V = <V>::from_delta(value)
+                                .map_err(|e| e.at(PathSegment::key(&key)))?;
+                            new.insert_mut(key, value);
+                        },
+                        $entry::EntryDelta::Remove { key } => {
+                            new.remove_mut(&key);
+                        },
+                    }}
+                    Ok(new)
+                }
+
+                fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+                    delta.validate_into(|key| self.get(key), errors);
+                }
+            }
+
+            impl<K, V> Delta for $map<K, V>
+            where K: Clone + Debug + PartialEq + $($key_bound)+ + Delta
+                + for<'de> Deserialize<'de>
+                + Serialize,
+                  V: Clone + Debug + PartialEq + Delta + IntoDelta
+                + for<'de> Deserialize<'de>
+                + Serialize,
+            {
+                fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+                    let mut changes: Vec<$entry::EntryDelta<K, V>> = vec![];
+                    if !self.ptr_eq(rhs) {
+                        for (key, lhs_val) in self.iter() { match rhs.get(key) {
+                            Some(rhs_val) if shared_or_eq(lhs_val, rhs_val) => {},
+                            Some(rhs_val) => changes.push($entry::EntryDelta::Edit {
+                                key: key.clone(),
+                                value: lhs_val.delta(rhs_val)?,
+                            }),
+                            None => changes.push($entry::EntryDelta::Remove {
+                                key: key.clone(),
+                            }),
+                        }}
+                        for (key, rhs_val) in rhs.iter() {
+                            if self.contains_key(key) { continue }
+                            changes.push($entry::EntryDelta::Add {
+                                key: key.clone(),
+                                value: rhs_val.clone().into_delta()?,
+                            });
+                        }
+                    }
+                    Ok($delta(if changes.is_empty() { None } else { Some(changes) }))
+                }
+            }
+
+            impl<K, V> FromDelta for $map<K, V>
+            where K: Clone + Debug + PartialEq + $($key_bound)+ + FromDelta
+                + for<'de> Deserialize<'de>
+                + Serialize,
+                  V: Clone + Debug + PartialEq + FromDelta
+                + for<'de> Deserialize<'de>
+                + Serialize,
+            {
+                fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+                    let mut map: Self = Self::default();
+                    for (index, change) in delta.0.into_iter().flatten().enumerate() {
+                        match change {
+                            $entry::EntryDelta::Add { key, value } => {
+                                let value: V = <V>::from_delta(value)
+                                    .map_err(|e| e.at(PathSegment::key(&key)))?;
+                                map.insert_mut(key, value)
+                            },
+                            _ => return Err(DeltaError::IllegalDelta { index }),
+                        };
+                    }
+                    Ok(map)
+                }
+
+                fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+                    delta.validate_from_delta(errors);
+                }
+            }
+
+            impl<K, V> IntoDelta for $map<K, V>
+            where K: Clone + Debug + PartialEq + $($key_bound)+ + IntoDelta
+                + for<'de> Deserialize<'de>
+                + Serialize,
+                  V: Clone + Debug + PartialEq + IntoDelta
+                + for<'de> Deserialize<'de>
+                + Serialize,
+            {
+                fn into_delta(self) -> DeltaResult<Self::Delta> {
+                    let mut changes: Vec<$entry::EntryDelta<K, V>> = vec![];
+                    for (key, value) in self.iter() {
+                        changes.push($entry::EntryDelta::Add {
+                            key: key.clone(),
+                            value: value.clone().into_delta()?,
+                        });
+                    }
+                    Ok($delta(if changes.is_empty() { None } else { Some(changes) }))
+                }
+            }
+        )*
+    };
+}
+
+impl_delta_trait_for_map_types! {
+    HashTrieMap         => HashMapDelta,  hashmap,  [Eq + Hash];
+    HashTrieMapSync     => HashMapDelta,  hashmap,  [Eq + Hash];
+    RedBlackTreeMap     => BTreeMapDelta, btreemap, [Ord];
+    RedBlackTreeMapSync => BTreeMapDelta, btreemap, [Ord];
+}
+
+
+
+macro_rules! impl_delta_trait_for_vector_types {
+    ( $($vector:ident),* $(,)? ) => {
+        $(
+            impl<T> Core for $vector<T>
+            where T: Clone + Debug + PartialEq + Core
+                + for<'de> Deserialize<'de>
+                + Serialize
+            {
+                type Delta = VecDelta<T>;
+            }
+
+            impl<T> Apply for $vector<T>
+            where T: Clone + Debug + PartialEq + Apply + FromDelta
+                + for<'de> Deserialize<'de>
+                + Serialize
+            {
+                fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+                    self.apply_with(delta, &ApplyPolicy::default())
+                }
+
+                fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                              -> DeltaResult<Self>
+                {
+                    delta.apply_to(
+                        self,
+                        policy,
+                        |vec| vec.len(),
+                        |vec, index| &vec[index],
+                        |vec, index, item| { vec.set_mut(index, item); },
+                        |vec, item| { vec.push_back_mut(item); Ok(()) },
+                        |vec| vec.drop_last_mut(),
+                    )
+                }
+
+                fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+                    delta.validate_into(self.len(), |index| &self[index], errors);
+                }
+            }
+
+            impl<T> Delta for $vector<T>
+            where T: Clone + Debug + PartialEq + Delta + IntoDelta
+                + for<'de> Deserialize<'de>
+                + Serialize
+            {
+                fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+                    let (lhs_len, rhs_len) = (self.len(), rhs.len());
+                    let max_len = usize::max(lhs_len, rhs_len);
+                    let mut changes: Vec<EltDelta<T>> = vec![];
+                    for index in 0 .. max_len { match (self.get(index), rhs.get(index)) {
+                        (None, None) => return bug_detected!(),
+                        (Some(lhs), Some(rhs)) if shared_or_eq(lhs, rhs) => {/*NOP*/},
+                        (Some(lhs), Some(rhs)) =>
+                            changes.push(EltDelta::Edit { index, item: lhs.delta(rhs)? }),
+                        (None, Some(rhs)) =>
+                            changes.push(EltDelta::Add(rhs.clone().into_delta()?)),
+                        (Some(_),   None) => match changes.last_mut() {
+                            Some(EltDelta::Remove { ref mut count }) => *count += 1,
+                            _ => changes.push(EltDelta::Remove { count: 1 }),
+                        },
+                    }}
+                    Ok(VecDelta(changes))
+                }
+            }
+
+            impl<T> FromDelta for $vector<T>
+            where T: Clone + Debug + PartialEq + FromDelta
+                + for<'de> Deserialize<'de>
+                + Serialize
+            {
+                fn from_delta(delta: <Self as Core>::Delta) -> DeltaResult<Self> {
+                    let mut vec: Self = Self::default();
+                    for (index, element) in delta.0.into_iter().enumerate() {
+                        match element {
+                            EltDelta::Add(elt) => vec.push_back_mut(
+                                <T>::from_delta(elt)
+                                    .map_err(|e| e.at(PathSegment::Index(index)))?
+                            ),
+                            _ => return Err(DeltaError::IllegalDelta { index }),
+                        }
+                    }
+                    Ok(vec)
+                }
+
+                fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+                    delta.validate_from_delta(errors);
+                }
+            }
+
+            impl<T> IntoDelta for $vector<T>
+            where T: Clone + Debug + PartialEq + IntoDelta
+                + for<'de> Deserialize<'de>
+                + Serialize
+            {
+                fn into_delta(self) -> DeltaResult<<Self as Core>::Delta> {
+                    let mut changes: Vec<EltDelta<T>> = vec![];
+                    for elt in self.iter() {
+                        changes.push(EltDelta::Add(elt.clone().into_delta()?));
+                    }
+                    Ok(VecDelta(changes))
+                }
+            }
+        )*
+    };
+}
+
+impl_delta_trait_for_vector_types! { Vector, VectorSync }
+
+/// Values that live at the same address are shared by both collections,
+/// and so they are equal without having to be compared.
+fn shared_or_eq<T: PartialEq>(lhs: &T, rhs: &T) -> bool {
+    std::ptr::eq(lhs, rhs) || lhs == rhs
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn HashTrieMap__apply__different_values() -> DeltaResult<()> {
+        let map0: HashTrieMap<String, u16> = (0 .. 100u16)
+            .map(|n| (n.to_string(), n))
+            .collect();
+        let map1 = map0.insert("42".into(), 4242)
+            .insert("bar".into(), 300)
+            .remove("7");
+        let delta = map0.delta(&map1)?;
+        assert_eq!(delta.len(), 3);
+        let map2 = map0.apply(delta)?;
+        assert_eq!(map1, map2);
+        assert_eq!(map0.delta(&map0.clone())?, HashMapDelta(None));
+        Ok(())
+    }
+
+    #[test]
+    fn RedBlackTreeMapSync__apply__different_values() -> DeltaResult<()> {
+        let map0: RedBlackTreeMapSync<u16, String> = (0 .. 100u16)
+            .map(|n| (n, n.to_string()))
+            .collect();
+        let map1 = map0.insert(50, "fifty".into()).remove(&3);
+        let delta = map0.delta(&map1)?;
+        let map2 = map0.apply(delta)?;
+        assert_eq!(map1, map2);
+        Ok(())
+    }
+
+    #[test]
+    fn HashTrieMap__apply__std_compound_values() -> DeltaResult<()> {
+        use std::collections::HashMap;
+        let map0: HashTrieMap<String, (u8, u8)> = (0 .. 10u8)
+            .map(|n| (n.to_string(), (n, n)))
+            .collect();
+        let map1 = map0.insert("5".into(), (5, 50)).remove("3");
+        let std_map0: HashMap<String, (u8, u8)> = map0.iter()
+            .map(|(k, v)| (k.clone(), *v))
+            .collect();
+        let std_map1: HashMap<String, (u8, u8)> = map1.iter()
+            .map(|(k, v)| (k.clone(), *v))
+            .collect();
+        // NOTE: The deltas are compatible with those of a std `HashMap`,
+        //       in both directions:
+        assert_eq!(std_map0.apply(map0.delta(&map1)?)?, std_map1);
+        assert_eq!(map0.apply(std_map0.delta(&std_map1)?)?, map1);
+        Ok(())
+    }
+
+    #[test]
+    fn Vector__apply__different_values() -> DeltaResult<()> {
+        let vec0: Vector<u32> = (0 .. 100).collect();
+        let vec1 = vec0.set(10, 1010).unwrap().drop_last().unwrap();
+        let delta = vec0.delta(&vec1)?;
+        assert_eq!(delta, VecDelta(vec![
+            EltDelta::Edit { index: 10, item: 1010u32.into_delta()? },
+            EltDelta::Remove { count: 1 },
+        ]));
+        let vec2 = vec0.apply(delta.clone())?;
+        assert_eq!(vec1, vec2);
+        // NOTE: The delta is compatible with that of a `Vec`:
+        let vec3 = vec0.iter().cloned().collect::<Vec<u32>>().apply(delta)?;
+        assert!(vec1.iter().eq(vec3.iter()));
+        Ok(())
+    }
+}
@@ -0,0 +1,744 @@
+//! Deltoid impls for [`serde_json::Value`].
+//!
+//! The delta of a `Value` is a list of [RFC 6902] JSON Patch operations,
+//! addressed using [RFC 6901] JSON Pointers.  This makes the conversion
+//! between a [`ValueDelta`] and a JSON Patch document lossless in both
+//! directions.  Calculating a delta only produces `add`, `remove` and
+//! `replace` operations:
+//! - object entries are added, removed or edited by key,
+//! - array elements are edited by index, and added or removed at the end,
+//! - any other change replaces the value as a whole.
+//!
+//! Applying a delta supports all operations, i.e. `move`, `copy` and `test`
+//! as well.  Validating a delta checks each operation against the original
+//! value, and only applies the delta when an operation depends on the
+//! outcome of an earlier one.
+//!
+//! In addition, the [`JsonDiff`] trait converts the delta of any type that
+//! can be serialized to JSON into a JSON Patch or an [RFC 7396] JSON Merge
+//! Patch document, and back, by diffing JSON representations.  Because of
+//! that, each conversion takes time proportional to the size of the value
+//! that the delta applies to, not to the size of the delta.
+//!
+//! [`serde_json::Value`]: https://docs.rs/serde_json/latest/serde_json/enum.Value.html
+//! [RFC 6902]: https://tools.ietf.org/html/rfc6902
+//! [RFC 6901]: https://tools.ietf.org/html/rfc6901
+//! [RFC 7396]: https://tools.ietf.org/html/rfc7396
+
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{DynDelta, ToDynDelta};
+use ::serde_json::{Map, Value};
+use serde::{Deserialize, Serialize};
+
+
+impl Core for Value {
+    type Delta = ValueDelta;
+}
+
+impl Apply for Value {
+    fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        let mut new: Self = self.clone();
+        for op in delta.0 { match op {
+            PatchOperation::Add { path, value } =>
+                add(&mut new, &path, value)?,
+            PatchOperation::Remove { path } => {
+                remove(&mut new, &path)?;
+            },
+            PatchOperation::Replace { path, value } =>
+                *pointer_mut(&mut new, &path)? = value,
+            PatchOperation::Move { from, path } => {
+                if path.starts_with(&format!("{}/", from)) {
+                    return Err(failed_to_apply(&path, "is a child of `from`"));
+                }
+                let value = remove(&mut new, &from)?;
+                add(&mut new, &path, value)?;
+            },
+            PatchOperation::Copy { from, path } => {
+                let value = pointer_mut(&mut new, &from)?.clone();
+                add(&mut new, &path, value)?;
+            },
+            PatchOperation::Test { path, value } => {
+                if *pointer_mut(&mut new, &path)? != value {
+                    return Err(failed_to_apply(&path, "failed the test"));
+                }
+            },
+        }}
+        Ok(new)
+    }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        let start: usize = errors.len();
+        // NOTE: The JSON Pointers written by the operations seen so far.
+        //       An operation that reads one that is affected by them
+        //       depends on the outcome of an earlier operation, and so
+        //       it can't be checked against `self`:
+        let mut written: Vec<&str> = vec![];
+        let mut has_dependent_ops = false;
+        for op in delta.iter() {
+            let (reads, writes): (Vec<&str>, Vec<&str>) = match op {
+                PatchOperation::Add { path, .. } => (vec![path], vec![path]),
+                PatchOperation::Remove { path }
+                    | PatchOperation::Replace { path, .. } =>
+                    (vec![path], vec![path]),
+                PatchOperation::Move { from, path } =>
+                    (vec![from, path], vec![from, path]),
+                PatchOperation::Copy { from, path } =>
+                    (vec![from, path], vec![path]),
+                PatchOperation::Test { path, .. } => (vec![path], vec![]),
+            };
+            let is_dependent = reads.iter()
+                .any(|read| written.iter().any(|write| affects(write, read)));
+            written.extend(writes);
+            if is_dependent {
+                has_dependent_ops = true;
+                continue;
+            }
+            if let Err(error) = validate_op(self, op) {
+                errors.push(error);
+            }
+        }
+        if has_dependent_ops && errors.len() == start {
+            if let Err(error) = self.apply(delta.clone()) {
+                errors.push(error);
+            }
+        }
+    }
+}
+
+impl Delta for Value {
+    fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+        let mut ops: Vec<PatchOperation> = vec![];
+        diff(self, rhs, &mut String::new(), &mut ops);
+        Ok(ValueDelta(ops))
+    }
+}
+
+impl FromDelta for Value {
+    fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+        Value::Null.apply(delta)
+    }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        Value::Null.validate_into(delta, errors)
+    }
+}
+
+impl IntoDelta for Value {
+    fn into_delta(self) -> DeltaResult<Self::Delta> {
+        Ok(ValueDelta(vec![
+            PatchOperation::Replace { path: String::new(), value: self }
+        ]))
+    }
+}
+
+/// Push the operations that turn `lhs` into `rhs` onto `ops`.
+/// The `path` points to `lhs` and `rhs`.
+fn diff(lhs: &Value, rhs: &Value, path: &mut String, ops: &mut Vec<PatchOperation>) {
+    let path_len = path.len();
+    match (lhs, rhs) {
+        (lhs, rhs) if lhs == rhs => {/*NOP*/},
+        (Value::Object(lhs), Value::Object(rhs)) => {
+            for (key, lhs_val) in lhs.iter() {
+                push_token(path, key);
+                match rhs.get(key) {
+                    Some(rhs_val) => diff(lhs_val, rhs_val, path, ops),
+                    None => ops.push(PatchOperation::Remove { path: path.clone() }),
+                }
+                path.truncate(path_len);
+            }
+            for (key, rhs_val) in rhs.iter() {
+                if lhs.contains_key(key) { continue }
+                push_token(path, key);
+                ops.push(PatchOperation::Add {
+                    path: path.clone(),
+                    value: rhs_val.clone(),
+                });
+                path.truncate(path_len);
+            }
+        },
+        (Value::Array(lhs), Value::Array(rhs)) => {
+            let min_len = usize::min(lhs.len(), rhs.len());
+            for index in 0 .. min_len {
+                push_token(path, &index.to_string());
+                diff(&lhs[index], &rhs[index], path, ops);
+                path.truncate(path_len);
+            }
+            for (index, rhs_val) in rhs.iter().enumerate().skip(min_len) {
+                push_token(path, &index.to_string());
+                ops.push(PatchOperation::Add {
+                    path: path.clone(),
+                    value: rhs_val.clone(),
+                });
+                path.truncate(path_len);
+            }
+            // NOTE: Remove from the end, so that the indices remain valid:
+            for index in (min_len .. lhs.len()).rev() {
+                push_token(path, &index.to_string());
+                ops.push(PatchOperation::Remove { path: path.clone() });
+                path.truncate(path_len);
+            }
+        },
+        (_, rhs) => ops.push(PatchOperation::Replace {
+            path: path.clone(),
+            value: rhs.clone(),
+        }),
+    }
+}
+
+/// Append a reference `token` to a JSON Pointer `path`, escaping it.
+fn push_token(path: &mut String, token: &str) {
+    path.push('/');
+    path.push_str(&token.replace('~', "~0").replace('/', "~1"));
+}
+
+/// Split a JSON Pointer `path` into its unescaped reference tokens.
+fn tokens(path: &str) -> DeltaResult<Vec<String>> {
+    if path.is_empty() { return Ok(vec![]); }
+    if !path.starts_with('/') {
+        return Err(failed_to_apply(path, "is not a valid JSON Pointer"));
+    }
+    Ok(path[1..].split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+/// Resolve an array index `token` for an array of length `len`.
+/// The index may be equal to `len` iff. `allow_end` is `true`.
+fn array_index(path: &str, token: &str, len: usize, allow_end: bool)
+               -> DeltaResult<usize> {
+    let index: usize = if token == "-" && allow_end {
+        len
+    } else if token == "0" || !token.starts_with('0') {
+        token.parse()
+            .map_err(|_| failed_to_apply(path, "has an invalid array index"))?
+    } else {
+        return Err(failed_to_apply(path, "has an invalid array index"));
+    };
+    if index < len || (allow_end && index == len) {
+        Ok(index)
+    } else {
+        Err(failed_to_apply(path, "has an out of bounds array index"))
+    }
+}
+
+fn pointer<'v>(value: &'v Value, path: &str) -> DeltaResult<&'v Value> {
+    let mut current: &Value = value;
+    for token in tokens(path)? {
+        current = match current {
+            Value::Object(map) => map.get(&token)
+                .ok_or_else(|| failed_to_apply(path, "does not exist"))?,
+            Value::Array(vec) => {
+                let index = array_index(path, &token, vec.len(), false)?;
+                &vec[index]
+            },
+            _ => return Err(failed_to_apply(path, "does not exist")),
+        };
+    }
+    Ok(current)
+}
+
+fn pointer_mut<'v>(value: &'v mut Value, path: &str) -> DeltaResult<&'v mut Value> {
+    let mut current: &mut Value = value;
+    for token in tokens(path)? {
+        current = match current {
+            Value::Object(map) => map.get_mut(&token)
+                .ok_or_else(|| failed_to_apply(path, "does not exist"))?,
+            Value::Array(vec) => {
+                let index = array_index(path, &token, vec.len(), false)?;
+                &mut vec[index]
+            },
+            _ => return Err(failed_to_apply(path, "does not exist")),
+        };
+    }
+    Ok(current)
+}
+
+/// Split `path` into the path of its parent and its last reference token.
+fn split_last(path: &str) -> DeltaResult<(&str, String)> {
+    match path.rfind('/') {
+        Some(idx) => Ok((
+            &path[.. idx],
+            path[idx + 1 ..].replace("~1", "/").replace("~0", "~"),
+        )),
+        None => Err(failed_to_apply(path, "is not a valid JSON Pointer")),
+    }
+}
+
+/// Returns `true` if writing to `write` may change the outcome of reading
+/// `read`.  Writing to an array element shifts the elements after it, so
+/// that affects its siblings as well.
+fn affects(write: &str, read: &str) -> bool {
+    let (parent, token) = write.rfind('/')
+        .map_or(("", ""), |idx| (&write[.. idx], &write[idx + 1 ..]));
+    let is_index = token == "-" || token.bytes().all(|b| b.is_ascii_digit());
+    is_prefix(write, read) || is_prefix(read, write)
+        || (is_index && is_prefix(parent, read))
+}
+
+/// Returns `true` if `prefix` points to `path` or to one of its ancestors.
+fn is_prefix(prefix: &str, path: &str) -> bool {
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Check whether `op` can be applied to `doc`, without applying it.
+fn validate_op(doc: &Value, op: &PatchOperation) -> DeltaResult<()> {
+    match op {
+        PatchOperation::Add { path, .. } => validate_add(doc, path),
+        PatchOperation::Remove { path }
+            | PatchOperation::Replace { path, .. } =>
+            pointer(doc, path).map(|_| ()),
+        PatchOperation::Move { from, path } => {
+            if path.starts_with(&format!("{}/", from)) {
+                return Err(failed_to_apply(path, "is a child of `from`"));
+            }
+            pointer(doc, from)?;
+            validate_add(doc, path)
+        },
+        PatchOperation::Copy { from, path } => {
+            pointer(doc, from)?;
+            validate_add(doc, path)
+        },
+        PatchOperation::Test { path, value } => {
+            if pointer(doc, path)? != value {
+                return Err(failed_to_apply(path, "failed the test"));
+            }
+            Ok(())
+        },
+    }
+}
+
+/// Check whether a value can be added to `doc` at `path`.
+fn validate_add(doc: &Value, path: &str) -> DeltaResult<()> {
+    if path.is_empty() { return Ok(()); }
+    let (parent, token) = split_last(path)?;
+    match pointer(doc, parent)? {
+        Value::Object(_) => Ok(()),
+        Value::Array(vec) => array_index(path, &token, vec.len(), true).map(|_| ()),
+        _ => Err(failed_to_apply(path, "has no container parent")),
+    }
+}
+
+fn add(doc: &mut Value, path: &str, value: Value) -> DeltaResult<()> {
+    if path.is_empty() {
+        *doc = value;
+        return Ok(());
+    }
+    let (parent, token) = split_last(path)?;
+    match pointer_mut(doc, parent)? {
+        Value::Object(map) => { map.insert(token, value); },
+        Value::Array(vec) => {
+            let index = array_index(path, &token, vec.len(), true)?;
+            vec.insert(index, value);
+        },
+        _ => return Err(failed_to_apply(path, "has no container parent")),
+    }
+    Ok(())
+}
+
+fn remove(doc: &mut Value, path: &str) -> DeltaResult<Value> {
+    if path.is_empty() {
+        return Ok(std::mem::replace(doc, Value::Null));
+    }
+    let (parent, token) = split_last(path)?;
+    match pointer_mut(doc, parent)? {
+        Value::Object(map) => map.remove(&token)
+            .ok_or_else(|| failed_to_apply(path, "does not exist")),
+        Value::Array(vec) => {
+            let index = array_index(path, &token, vec.len(), false)?;
+            Ok(vec.remove(index))
+        },
+        _ => Err(failed_to_apply(path, "does not exist")),
+    }
+}
+
+fn json_error(err: ::serde_json::Error) -> DeltaError {
+    DeltaError::FailedToConvertFromDelta { reason: err.to_string() }
+}
+
+fn failed_to_apply(path: &str, reason: &str) -> DeltaError {
+    DeltaError::FailedToApplyDelta {
+        reason: format!("JSON Pointer \"{}\" {}", path, reason),
+    }
+}
+
+
+
+/// A delta for a `serde_json::Value`, in the form of a JSON Patch document.
+#[derive(Clone, PartialEq)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub struct ValueDelta(#[doc(hidden)] pub Vec<PatchOperation>);
+
+impl ValueDelta {
+    #[inline(always)]
+    pub fn iter(&self) -> impl Iterator<Item = &PatchOperation> {
+        self.0.iter()
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize { self.0.len() }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+
+    /// Convert `self` to an RFC 6902 JSON Patch document.
+    pub fn to_json_patch(&self) -> DeltaResult<Value> {
+        ::serde_json::to_value(&self.0).map_err(json_error)
+    }
+
+    /// Convert an RFC 6902 JSON Patch document to a `ValueDelta`.
+    pub fn from_json_patch(patch: Value) -> DeltaResult<Self> {
+        ::serde_json::from_value(patch).map(ValueDelta).map_err(json_error)
+    }
+}
+
+impl From<Vec<PatchOperation>> for ValueDelta {
+    fn from(ops: Vec<PatchOperation>) -> Self { ValueDelta(ops) }
+}
+
+impl std::fmt::Debug for ValueDelta {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "ValueDelta ")?;
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+
+/// An RFC 6902 JSON Patch operation.
+/// Paths are RFC 6901 JSON Pointers.
+#[derive(Clone, Debug, PartialEq)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    /// Add a `value` at `path`, shifting array elements if necessary.
+    Add { path: String, value: Value },
+    /// Remove the value at `path`.
+    Remove { path: String },
+    /// Replace the value at `path` with `value`.
+    Replace { path: String, value: Value },
+    /// Remove the value at `from` and add it at `path`.
+    Move { from: String, path: String },
+    /// Add a copy of the value at `from` at `path`.
+    Copy { from: String, path: String },
+    /// Ensure that the value at `path` is equal to `value`.
+    Test { path: String, value: Value },
+}
+
+/// Each operation becomes a change to the entry with its JSON Pointer as key.
+/// `Test` operations don't change anything, and are left out.
+impl ToDynDelta for ValueDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        DynDelta::changes(self.iter().map(ToDynDelta::to_dyn_delta).collect())
+    }
+}
+
+impl ToDynDelta for PatchOperation {
+    fn to_dyn_delta(&self) -> DynDelta {
+        let (path, delta) = match self {
+            Self::Add { path, value } | Self::Replace { path, value } =>
+                (path, DynDelta::Replace(value.to_string())),
+            Self::Remove { path } => (path, DynDelta::Remove),
+            Self::Move { from, path } =>
+                (path, DynDelta::Replace(format!("<moved from {}>", from))),
+            Self::Copy { from, path } =>
+                (path, DynDelta::Replace(format!("<copied from {}>", from))),
+            Self::Test { .. } => return DynDelta::Unchanged,
+        };
+        DynDelta::Key { key: path.clone(), delta: Box::new(delta) }
+    }
+}
+
+
+
+/// Converts the deltas of a type to and from JSON Patch and JSON Merge Patch
+/// documents by re-diffing JSON, in O(size of the base value) rather than
+/// O(size of the delta).
+///
+/// The paths of the documents point into the JSON representation of the
+/// type.  Deltas don't always contain enough information to determine such
+/// paths, e.g. a `VecDelta` removes elements from the end without recording
+/// their indices, and serde attributes can rename or restructure fields.
+/// So each conversion takes place relative to a base value, i.e. the value
+/// that the delta applies to: it applies the delta or patch to the base
+/// value, and diffs the JSON representations of the base value and the
+/// result.
+///
+/// This trait is implemented for every type that can be serialized to and
+/// deserialized from JSON.
+pub trait JsonDiff: Apply + Delta + Serialize + for<'de> Deserialize<'de> {
+    /// Diff the JSON representations of `self` and of `self` with `delta`
+    /// applied, producing a JSON Patch document.
+    fn json_patch_for(&self, delta: Self::Delta) -> DeltaResult<ValueDelta> {
+        let new: Self = self.apply(delta)?;
+        to_json(self)?.delta(&to_json(&new)?)
+    }
+
+    /// Convert a JSON Patch document that applies to the JSON representation
+    /// of `self` to a delta.  This fails if the patched JSON representation
+    /// can't be deserialized, i.e. if the paths don't line up with `Self`.
+    fn delta_for_json_patch(&self, patch: ValueDelta) -> DeltaResult<Self::Delta> {
+        let new: Value = to_json(self)?.apply(patch)?;
+        self.delta(&::serde_json::from_value(new).map_err(json_error)?)
+    }
+
+    /// Diff the JSON representations of `self` and of `self` with `delta`
+    /// applied, producing a JSON Merge Patch document.  This requires `self`
+    /// to be represented as a JSON object, e.g. a struct.
+    ///
+    /// A Merge Patch removes the fields that it sets to `null`, so a field
+    /// that becomes `null` is set to `null` in the patch as well.  That
+    /// round-trips for e.g. an `Option` field, which serde deserializes as
+    /// `None` when it's missing, and otherwise this fails.
+    fn merge_patch_for(&self, delta: Self::Delta) -> DeltaResult<Value> {
+        let new: Self = self.apply(delta)?;
+        let (lhs, rhs) = match (to_json(self)?, to_json(&new)?) {
+            (Value::Object(lhs), Value::Object(rhs)) => (lhs, rhs),
+            _ => return Err(DeltaError::FailedToConvertFromDelta {
+                reason: "a JSON Merge Patch requires a JSON object".to_string(),
+            }),
+        };
+        let (patch, sets_null) = merge_patch(&lhs, &rhs);
+        let patch = Value::Object(patch);
+        if sets_null {
+            let mut merged = Value::Object(lhs);
+            merge(&mut merged, patch.clone());
+            let merged: Self = ::serde_json::from_value(merged)
+                .map_err(json_error)?;
+            if merged != new {
+                return Err(DeltaError::FailedToConvertFromDelta {
+                    reason: "a JSON Merge Patch can't set a field to null"
+                        .to_string(),
+                });
+            }
+        }
+        Ok(patch)
+    }
+
+    /// Convert a JSON Merge Patch document that applies to the JSON
+    /// representation of `self` to a delta.
+    fn delta_for_merge_patch(&self, patch: Value) -> DeltaResult<Self::Delta> {
+        let mut new: Value = to_json(self)?;
+        merge(&mut new, patch);
+        self.delta(&::serde_json::from_value(new).map_err(json_error)?)
+    }
+}
+
+impl<T> JsonDiff for T
+where T: Apply + Delta + Serialize + for<'de> Deserialize<'de> {}
+
+fn to_json<T: Serialize>(value: &T) -> DeltaResult<Value> {
+    ::serde_json::to_value(value).map_err(json_error)
+}
+
+/// Calculate the JSON Merge Patch that turns `lhs` into `rhs`, and whether
+/// it sets an entry that `rhs` has to `null`, which removes it instead.
+fn merge_patch(lhs: &Map<String, Value>, rhs: &Map<String, Value>)
+               -> (Map<String, Value>, bool) {
+    let mut patch = Map::new();
+    let mut sets_null = false;
+    for key in lhs.keys() {
+        if !rhs.contains_key(key) {
+            patch.insert(key.clone(), Value::Null);
+        }
+    }
+    for (key, rhs_val) in rhs.iter() { match (lhs.get(key), rhs_val) {
+        (Some(lhs_val), rhs_val) if lhs_val == rhs_val => {/*NOP*/},
+        (None, Value::Null) => sets_null = true,
+        (Some(_), Value::Null) => {
+            patch.insert(key.clone(), Value::Null);
+            sets_null = true;
+        },
+        (Some(Value::Object(lhs_val)), Value::Object(rhs_val)) => {
+            let (nested, nested_sets_null) = merge_patch(lhs_val, rhs_val);
+            patch.insert(key.clone(), Value::Object(nested));
+            sets_null |= nested_sets_null;
+        },
+        (_, rhs_val) => { patch.insert(key.clone(), rhs_val.clone()); },
+    }}
+    (patch, sets_null)
+}
+
+/// Apply a JSON Merge Patch to a `target` value.
+fn merge(target: &mut Value, patch: Value) {
+    match patch {
+        Value::Object(patch) => {
+            if !target.is_object() {
+                *target = Value::Object(Map::new());
+            }
+            if let Value::Object(map) = target {
+                for (key, value) in patch {
+                    if value.is_null() {
+                        map.remove(&key);
+                    } else {
+                        merge(map.entry(key).or_insert(Value::Null), value);
+                    }
+                }
+            }
+        },
+        patch => *target = patch,
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use ::serde_json::json;
+    use super::*;
+
+    #[test]
+    fn Value__delta__same_values() -> DeltaResult<()> {
+        let val0 = json!({ "foo": [1, 2, { "bar": null }] });
+        let val1 = val0.clone();
+        let delta = val0.delta(&val1)?;
+        assert_eq!(delta, ValueDelta(vec![]));
+        let val2 = val0.apply(delta)?;
+        assert_eq!(val1, val2);
+        Ok(())
+    }
+
+    #[test]
+    fn Value__delta__different_values() -> DeltaResult<()> {
+        let val0 = json!({
+            "name": "deltoid",
+            "tags": ["a", "b", "c"],
+            "owner": { "login": "jjpe", "id": 1 },
+            "a/b": 0,
+        });
+        let val1 = json!({
+            "name": "deltoid",
+            "tags": ["a", "z"],
+            "owner": { "login": "jjpe" },
+            "a/b": "zero",
+            "stars": 42,
+        });
+        let delta = val0.delta(&val1)?;
+        assert_eq!(delta.to_json_patch()?, json!([
+            { "op": "replace", "path": "/a~1b", "value": "zero" },
+            { "op": "remove",  "path": "/owner/id" },
+            { "op": "replace", "path": "/tags/1", "value": "z" },
+            { "op": "remove",  "path": "/tags/2" },
+            { "op": "add",     "path": "/stars", "value": 42 },
+        ]));
+        let val2 = val0.apply(delta)?;
+        assert_eq!(val1, val2);
+        Ok(())
+    }
+
+    #[test]
+    fn Value__apply__json_patch() -> DeltaResult<()> {
+        let patch = json!([
+            { "op": "test",  "path": "/foo", "value": [1, 2] },
+            { "op": "add",   "path": "/foo/1", "value": 3 },
+            { "op": "add",   "path": "/foo/-", "value": 4 },
+            { "op": "copy",  "from": "/foo", "path": "/bar" },
+            { "op": "move",  "from": "/foo/0", "path": "/baz" },
+        ]);
+        let delta = ValueDelta::from_json_patch(patch.clone())?;
+        assert_eq!(delta.to_json_patch()?, patch);
+        let val0 = json!({ "foo": [1, 2] });
+        let val1 = val0.apply(delta)?;
+        assert_eq!(val1, json!({ "foo": [3, 2, 4], "bar": [1, 3, 2, 4], "baz": 1 }));
+        Ok(())
+    }
+
+    #[test]
+    fn Value__apply__failed_test() -> DeltaResult<()> {
+        let delta = ValueDelta::from_json_patch(json!([
+            { "op": "test", "path": "/foo", "value": 1 },
+        ]))?;
+        let result = json!({ "foo": 2 }).apply(delta);
+        assert!(matches!(result, Err(DeltaError::FailedToApplyDelta { .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn Value__validate__json_patch() -> DeltaResult<()> {
+        let val0 = json!({ "foo": [1, 2], "bar": { "baz": true } });
+        let delta = ValueDelta::from_json_patch(json!([
+            { "op": "test",    "path": "/foo/0", "value": 1 },
+            { "op": "replace", "path": "/bar/baz", "value": false },
+            { "op": "add",     "path": "/foo/-", "value": 3 },
+        ]))?;
+        assert_eq!(val0.validate(&delta), Ok(()));
+        let delta = ValueDelta::from_json_patch(json!([
+            { "op": "test",    "path": "/foo/0", "value": 2 },
+            { "op": "remove",  "path": "/qux" },
+            { "op": "add",     "path": "/foo/5", "value": 3 },
+        ]))?;
+        assert_eq!(val0.validate(&delta).unwrap_err().len(), 3);
+        // NOTE: The `remove` depends on the `add`, so the delta is applied:
+        let delta = ValueDelta::from_json_patch(json!([
+            { "op": "add",     "path": "/qux", "value": 1 },
+            { "op": "remove",  "path": "/qux" },
+        ]))?;
+        assert_eq!(val0.validate(&delta), Ok(()));
+        Ok(())
+    }
+
+    #[test]
+    fn Value__from_delta() -> DeltaResult<()> {
+        let val0 = json!([true, 1.5, "two"]);
+        let val1 = Value::from_delta(val0.clone().into_delta()?)?;
+        assert_eq!(val0, val1);
+        Ok(())
+    }
+
+    #[test]
+    fn JsonDiff__json_patch_for() -> DeltaResult<()> {
+        let vec0: Vec<Vec<u32>> = vec![vec![1, 2, 3], vec![]];
+        let vec1: Vec<Vec<u32>> = vec![vec![1, 5]];
+        let delta = vec0.delta(&vec1)?;
+        let patch = vec0.json_patch_for(delta.clone())?;
+        assert_eq!(patch.to_json_patch()?, json!([
+            { "op": "replace", "path": "/0/1", "value": 5 },
+            { "op": "remove",  "path": "/0/2" },
+            { "op": "remove",  "path": "/1" },
+        ]));
+        assert_eq!(vec0.delta_for_json_patch(patch)?, delta);
+        let result = vec0.delta_for_json_patch(ValueDelta(vec![
+            PatchOperation::Add { path: "/1/0".into(), value: json!("x") },
+        ]));
+        assert!(matches!(result, Err(DeltaError::FailedToConvertFromDelta { .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn JsonDiff__merge_patch_for() -> DeltaResult<()> {
+        use std::collections::BTreeMap;
+        let map0: BTreeMap<String, u32> = vec![
+            ("w".into(), 0), ("x".into(), 1), ("y".into(), 2),
+        ].into_iter().collect();
+        let map1: BTreeMap<String, u32> = vec![
+            ("x".into(), 5), ("y".into(), 2), ("z".into(), 3),
+        ].into_iter().collect();
+        let delta = map0.delta(&map1)?;
+        let patch = map0.merge_patch_for(delta)?;
+        assert_eq!(patch, json!({ "w": null, "x": 5, "z": 3 }));
+        let map2 = map0.apply(map0.delta_for_merge_patch(patch)?)?;
+        assert_eq!(map1, map2);
+        assert!(vec![1u8].merge_patch_for(vec![1u8].delta(&vec![2u8])?).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn JsonDiff__merge_patch_for__null() -> DeltaResult<()> {
+        use std::collections::BTreeMap;
+        let map0: BTreeMap<String, Option<u32>> = vec![
+            ("x".into(), Some(1)), ("y".into(), Some(2)),
+        ].into_iter().collect();
+        let map1: BTreeMap<String, Option<u32>> = vec![
+            ("x".into(), None), ("y".into(), Some(2)),
+        ].into_iter().collect();
+        // NOTE: A map doesn't deserialize a missing entry as `None`:
+        assert!(map0.merge_patch_for(map0.delta(&map1)?).is_err());
+        let val0 = json!({ "x": 1, "y": { "z": 2 } });
+        let val1 = json!({ "x": 1, "y": { "z": null } });
+        assert!(val0.merge_patch_for(val0.delta(&val1)?).is_err());
+        let val2 = json!({ "x": 1 });
+        let patch = val0.merge_patch_for(val0.delta(&val2)?)?;
+        assert_eq!(patch, json!({ "y": null }));
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+//! Deltoid impls for [`SmallVec`].
+//!
+//! A `SmallVec` uses the same delta encoding as a `Vec`, i.e. [`VecDelta`].
+//! This means that a delta calculated between 2 `SmallVec`s can be applied
+//! to a `Vec` and vice versa.
+//!
+//! [`SmallVec`]: https://docs.rs/smallvec/latest/smallvec/struct.SmallVec.html
+//! [`VecDelta`]: ../../struct.VecDelta.html
+
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{ApplyPolicy, PathSegment};
+use crate::vec::{EltDelta, VecDelta};
+use ::smallvec::{Array, SmallVec};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+
+impl<A: Array> Core for SmallVec<A>
+where A::Item: Clone + Debug + PartialEq + Core
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    type Delta = VecDelta<A::Item>;
+}
+
+impl<A: Array> Apply for SmallVec<A>
+where A::Item: Clone + Debug + PartialEq + Apply + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
+        delta.apply_to(
+            self,
+            policy,
+            |vec| vec.len(),
+            |vec, index| &vec[index],
+            |vec, index, item| vec[index] = item,
+            |vec, item| { vec.push(item); Ok(()) },
+            |vec| vec.pop().is_some(),
+        )
+    }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        delta.validate_into(self.len(), |index| &self[index], errors);
+    }
+}
+
+impl<A: Array> Delta for SmallVec<A>
+where A::Item: Clone + Debug + PartialEq + Delta + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+        let (lhs_len, rhs_len) = (self.len(), rhs.len());
+        let max_len = usize::max(lhs_len, rhs_len);
+        let mut changes: Vec<EltDelta<A::Item>> = vec![];
+        for index in 0 .. max_len { match (self.get(index), rhs.get(index)) {
+            (None, None) => return bug_detected!(),
+            (Some(lhs), Some(rhs)) if lhs == rhs => {/*NOP*/},
+            (Some(lhs), Some(rhs)) =>
+                changes.push(EltDelta::Edit { index, item: lhs.delta(rhs)? }),
+            (None, Some(rhs)) =>
+                changes.push(EltDelta::Add(rhs.clone().into_delta()?)),
+            (Some(_),   None) => match changes.last_mut() {
+                Some(EltDelta::Remove { ref mut count }) => *count += 1,
+                _ => changes.push(EltDelta::Remove { count: 1 }),
+            },
+        }}
+        Ok(VecDelta(changes))
+    }
+}
+
+impl<A: Array> FromDelta for SmallVec<A>
+where A::Item: Clone + Debug + PartialEq + FromDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn from_delta(delta: <Self as Core>::Delta) -> DeltaResult<Self> {
+        let mut vec: Self = SmallVec::new();
+        for (index, element) in delta.0.into_iter().enumerate() {
+            match element {
+                EltDelta::Add(elt) => vec.push(
+                    <A::Item>::from_delta(elt)
+                        .map_err(|e| e.at(PathSegment::Index(index)))?
+                ),
+                _ => return Err(DeltaError::IllegalDelta { index }),
+            }
+        }
+        Ok(vec)
+    }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        delta.validate_from_delta(errors);
+    }
+}
+
+impl<A: Array> IntoDelta for SmallVec<A>
+where A::Item: Clone + Debug + PartialEq + IntoDelta
+    + for<'de> Deserialize<'de>
+    + Serialize
+{
+    fn into_delta(self) -> DeltaResult<<Self as Core>::Delta> {
+        let mut changes: Vec<EltDelta<A::Item>> = vec![];
+        for elt in self {
+            changes.push(EltDelta::Add(elt.into_delta()?));
+        }
+        Ok(VecDelta(changes))
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use ::smallvec::smallvec;
+    use super::*;
+
+    #[test]
+    fn SmallVec__delta__same_values() -> DeltaResult<()> {
+        let vec0: SmallVec<[i32; 4]> = smallvec![1, 3, 10, 30];
+        let vec1: SmallVec<[i32; 4]> = smallvec![1, 3, 10, 30];
+        let delta = vec0.delta(&vec1)?;
+        assert_eq!(delta, VecDelta(vec![]));
+        let vec2 = vec0.apply(delta)?;
+        assert_eq!(vec1, vec2);
+        Ok(())
+    }
+
+    #[test]
+    fn SmallVec__apply__different_values() -> DeltaResult<()> {
+        let vec0: SmallVec<[i32; 2]> = smallvec![1, 3, 10, 30];
+        let vec1: SmallVec<[i32; 2]> = smallvec![1, 4];
+        let delta = vec0.delta(&vec1)?;
+        assert_eq!(delta, VecDelta(vec![
+            EltDelta::Edit { index: 1, item: 4i32.into_delta()? },
+            EltDelta::Remove { count: 2 },
+        ]));
+        let vec2 = vec0.apply(delta.clone())?;
+        assert_eq!(vec1, vec2);
+        // NOTE: The delta is compatible with that of a `Vec`:
+        let vec3 = vec![1, 3, 10, 30].apply(delta)?;
+        assert_eq!(&vec1[..], &vec3[..]);
+        Ok(())
+    }
+
+    #[test]
+    fn SmallVec__apply_with__out_of_range_edit() -> DeltaResult<()> {
+        let delta = vec![1i32, 2, 3].delta(&vec![1, 2, 4])?;
+        let vec0: SmallVec<[i32; 2]> = smallvec![1, 2];
+        assert!(vec0.apply_with(delta.clone(), &ApplyPolicy::strict()).is_err());
+        assert_eq!(vec0.apply_with(delta.clone(), &ApplyPolicy::lenient())?, vec0);
+        let vec1 = vec0.apply_with(delta, &ApplyPolicy::upsert())?;
+        assert_eq!(&vec1[..], &[1, 2, 4]);
+        Ok(())
+    }
+}
@@ -0,0 +1,357 @@
+//! Deltoid impls for the date and time types of the [`time`] crate.
+//!
+//! Timestamps, i.e. [`OffsetDateTime`], [`PrimitiveDateTime`] and [`Date`],
+//! are encoded as an offset relative to the old value whenever that offset
+//! can be represented as an `i64`, and as the full value otherwise.
+//!
+//! [`time`]: https://docs.rs/time
+//! [`OffsetDateTime`]: https://docs.rs/time/latest/time/struct.OffsetDateTime.html
+//! [`PrimitiveDateTime`]: https://docs.rs/time/latest/time/struct.PrimitiveDateTime.html
+//! [`Date`]: https://docs.rs/time/latest/time/struct.Date.html
+
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{DynDelta, ToDynDelta};
+use ::time::{Date, Duration, OffsetDateTime, PrimitiveDateTime, Time};
+use std::convert::TryFrom;
+
+
+macro_rules! impl_delta_trait_for_timestamp_types {
+    ( $(
+        $type:ty => $delta:ident, $unit:literal,
+        $to_offset:path, $from_offset:path
+    );* $(;)? ) => {
+        $(
+            impl Core for $type {
+                type Delta = $delta;
+            }
+
+            impl Apply for $type {
+                fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+                    match delta {
+                        $delta::Offset(offset) => $from_offset(offset)
+                            .and_then(|offset| self.checked_add(offset))
+                            .ok_or_else(|| {
+                                offset_out_of_range(stringify!($delta), offset)
+                            }),
+                        $delta::Value(value) => Ok(value),
+                    }
+                }
+
+                fn validate_into(
+                    &self,
+                    delta: &Self::Delta,
+                    errors: &mut Vec<DeltaError>,
+                ) {
+                    if let $delta::Value(_) = delta { return; }
+                    if let Err(error) = self.apply(*delta) {
+                        errors.push(error);
+                    }
+                }
+            }
+
+            impl Delta for $type {
+                fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+                    if !self.same_offset(rhs) {
+                        return rhs.into_delta();
+                    }
+                    let offset: Duration = *rhs - *self;
+                    match $to_offset(&offset) {
+                        Some(offset) => Ok($delta::Offset(offset)),
+                        None => rhs.into_delta(),
+                    }
+                }
+            }
+
+            impl FromDelta for $type {
+                fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+                    match delta {
+                        $delta::Value(value) => Ok(value),
+                        $delta::Offset(_) =>
+                            Err(offset_without_base(stringify!($delta))),
+                    }
+                }
+
+                fn validate_from_delta(
+                    delta: &Self::Delta,
+                    errors: &mut Vec<DeltaError>,
+                ) {
+                    if let Err(error) = Self::from_delta(*delta) {
+                        errors.push(error);
+                    }
+                }
+            }
+
+            impl IntoDelta for $type {
+                fn into_delta(self) -> DeltaResult<Self::Delta> {
+                    Ok($delta::Value(self))
+                }
+            }
+
+
+            #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+            #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+            pub enum $delta {
+                #[doc = concat!(
+                    "The new value, as a number of ", $unit,
+                    " relative to the old value."
+                )]
+                Offset(i64),
+                /// The new value.
+                Value($type),
+            }
+
+            impl std::fmt::Debug for $delta {
+                fn fmt(&self, f: &mut std::fmt::Formatter)
+                       -> Result<(), std::fmt::Error>
+                {
+                    match &self {
+                        Self::Offset(offset) => write!(
+                            f, "{}::Offset({})", stringify!($delta), offset
+                        ),
+                        Self::Value(value) => write!(
+                            f, "{}::Value({:?})", stringify!($delta), value
+                        ),
+                    }
+                }
+            }
+
+            impl ToDynDelta for $delta {
+                fn to_dyn_delta(&self) -> DynDelta {
+                    match &self {
+                        Self::Offset(offset) =>
+                            DynDelta::Replace(format!("{:+} {}", offset, $unit)),
+                        Self::Value(value) => DynDelta::replace(value),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+/// An offset only preserves the UTC offset of the old value,
+/// so it is only usable when the new value has that same UTC offset.
+trait SameOffset { fn same_offset(&self, rhs: &Self) -> bool; }
+
+impl SameOffset for OffsetDateTime {
+    fn same_offset(&self, rhs: &Self) -> bool { self.offset() == rhs.offset() }
+}
+
+impl SameOffset for PrimitiveDateTime {
+    fn same_offset(&self, _rhs: &Self) -> bool { true }
+}
+
+impl SameOffset for Date {
+    fn same_offset(&self, _rhs: &Self) -> bool { true }
+}
+
+fn whole_days(duration: &Duration) -> Option<i64> {
+    Some(duration.whole_days())
+}
+
+fn whole_nanoseconds(duration: &Duration) -> Option<i64> {
+    i64::try_from(duration.whole_nanoseconds()).ok()
+}
+
+/// `Duration::days(days)`, or `None` if that would panic.
+fn checked_days(days: i64) -> Option<Duration> {
+    Some(Duration::seconds(days.checked_mul(24 * 60 * 60)?))
+}
+
+/// `Duration::nanoseconds(nanos)`, which can't panic.
+fn nanoseconds(nanos: i64) -> Option<Duration> {
+    Some(Duration::nanoseconds(nanos))
+}
+
+impl_delta_trait_for_timestamp_types! {
+    OffsetDateTime    => OffsetDateTimeDelta,    "nanoseconds",
+        whole_nanoseconds, nanoseconds;
+    PrimitiveDateTime => PrimitiveDateTimeDelta, "nanoseconds",
+        whole_nanoseconds, nanoseconds;
+    Date              => DateDelta,              "days",
+        whole_days, checked_days;
+}
+
+
+
+macro_rules! impl_delta_trait_for_value_types {
+    ( $($type:ty => $delta:ident);* $(;)? ) => {
+        $(
+            impl Core for $type {
+                type Delta = $delta;
+            }
+
+            impl Apply for $type {
+                fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+                    Self::from_delta(delta)
+                }
+
+                fn validate_into(
+                    &self,
+                    delta: &Self::Delta,
+                    errors: &mut Vec<DeltaError>,
+                ) {
+                    Self::validate_from_delta(delta, errors)
+                }
+            }
+
+            impl Delta for $type {
+                fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+                    rhs.into_delta()
+                }
+            }
+
+            impl FromDelta for $type {
+                fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+                    delta.0.ok_or_else(|| ExpectedValue!(stringify!($delta)))
+                }
+
+                fn validate_from_delta(
+                    delta: &Self::Delta,
+                    errors: &mut Vec<DeltaError>,
+                ) {
+                    if delta.0.is_none() {
+                        errors.push(ExpectedValue!(stringify!($delta)));
+                    }
+                }
+            }
+
+            impl IntoDelta for $type {
+                fn into_delta(self) -> DeltaResult<Self::Delta> {
+                    Ok($delta(Some(self)))
+                }
+            }
+
+
+            #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+            #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+            pub struct $delta(#[doc(hidden)] pub Option<$type>);
+
+            impl std::fmt::Debug for $delta {
+                fn fmt(&self, f: &mut std::fmt::Formatter)
+                       -> Result<(), std::fmt::Error>
+                {
+                    match &self.0 {
+                        Some(value) =>
+                            write!(f, "{}({:?})", stringify!($delta), value),
+                        None =>
+                            write!(f, "{}(None)", stringify!($delta)),
+                    }
+                }
+            }
+
+            impl ToDynDelta for $delta {
+                fn to_dyn_delta(&self) -> DynDelta {
+                    match &self.0 {
+                        Some(value) => DynDelta::replace(value),
+                        None => DynDelta::Unchanged,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_delta_trait_for_value_types! {
+    Time     => TimeDelta;
+    Duration => DurationDelta;
+}
+
+
+
+fn offset_out_of_range(type_name: &str, offset: i64) -> DeltaError {
+    DeltaError::FailedToApplyDelta {
+        reason: format!("{} offset {} is out of range", type_name, offset),
+    }
+}
+
+fn offset_without_base(type_name: &str) -> DeltaError {
+    DeltaError::FailedToConvertFromDelta {
+        reason: format!("{} holds an offset, which requires an old value", type_name),
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use ::time::{Month, UtcOffset};
+    use serde_json;
+    use super::*;
+
+    fn date(year: i32, month: Month, day: u8) -> Date {
+        Date::from_calendar_date(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn OffsetDateTime__delta__different_values() -> DeltaResult<()> {
+        let dt0 = OffsetDateTime::from_unix_timestamp(1_600_000_000).unwrap();
+        let dt1 = dt0 + Duration::milliseconds(1500);
+        let delta: <OffsetDateTime as Core>::Delta = dt0.delta(&dt1)?;
+        assert_eq!(delta, OffsetDateTimeDelta::Offset(1_500_000_000));
+        let json_string = serde_json::to_string(&delta)
+            .expect("Could not serialize to json");
+        assert_eq!(json_string, "{\"Offset\":1500000000}");
+        let delta1: <OffsetDateTime as Core>::Delta = serde_json::from_str(
+            &json_string
+        ).expect("Could not deserialize from json");
+        assert_eq!(delta, delta1);
+        Ok(())
+    }
+
+    #[test]
+    fn OffsetDateTime__apply__different_offsets() -> DeltaResult<()> {
+        let dt0 = OffsetDateTime::from_unix_timestamp(1_600_000_000).unwrap();
+        let dt1 = dt0.to_offset(UtcOffset::from_hms(2, 0, 0).unwrap());
+        let delta: <OffsetDateTime as Core>::Delta = dt0.delta(&dt1)?;
+        assert_eq!(delta, OffsetDateTimeDelta::Value(dt1));
+        let dt2 = dt0.apply(delta)?;
+        assert_eq!(dt1.offset(), dt2.offset());
+        Ok(())
+    }
+
+    #[test]
+    fn Date__apply__different_values() -> DeltaResult<()> {
+        let d0 = date(2020, Month::February, 28);
+        let d1 = date(2021, Month::March, 1);
+        let delta: <Date as Core>::Delta = d0.delta(&d1)?;
+        assert_eq!(delta, DateDelta::Offset(367));
+        let d2 = d0.apply(delta)?;
+        assert_eq!(d1, d2);
+        assert!(Date::from_delta(delta).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn Duration__apply__different_values() -> DeltaResult<()> {
+        let d0 = Duration::milliseconds(-2500);
+        let d1 = Duration::weeks(3);
+        let delta: <Duration as Core>::Delta = d0.delta(&d1)?;
+        let d2 = d0.apply(delta)?;
+        assert_eq!(d1, d2);
+        Ok(())
+    }
+
+    #[test]
+    fn Date__apply__offset_out_of_range() {
+        let d0 = date(2020, Month::February, 28);
+        assert!(d0.apply(DateDelta::Offset(i64::MAX)).is_err());
+        assert!(d0.apply(DateDelta::Offset(i64::MIN)).is_err());
+    }
+
+    #[test]
+    fn Date__offset__error_messages() {
+        assert_eq!(Date::from_delta(DateDelta::Offset(1)), Err(
+            DeltaError::FailedToConvertFromDelta {
+                reason: "DateDelta holds an offset, which requires an old value"
+                    .into(),
+            }
+        ));
+        let d0 = date(2020, Month::February, 28);
+        assert_eq!(d0.apply(DateDelta::Offset(i64::MAX)), Err(
+            DeltaError::FailedToApplyDelta {
+                reason: format!("DateDelta offset {} is out of range", i64::MAX),
+            }
+        ));
+    }
+}
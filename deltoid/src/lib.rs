@@ -7,30 +7,45 @@
 #[macro_use] pub mod error;
 #[macro_use] pub mod snapshot;
 pub mod core;
+pub mod dyn_delta;
 
 pub mod arrays;
+pub mod atomic;
 pub mod borrow;
 pub mod boxed;
 pub mod collections;
+pub mod ext;
+pub mod num;
 pub mod option;
+pub mod policy;
 pub mod range;
+pub mod render;
 pub mod result;
 pub mod rc;
 pub mod string;
 pub mod sync;
 pub mod tuple;
 pub mod vec;
+pub mod visit;
 
 
 pub use crate::core::*;
+pub use crate::dyn_delta::{DynDelta, ToDynDelta};
+pub use crate::atomic::AtomicDelta;
 pub use crate::borrow::CowDelta;
 pub use crate::boxed::*;
 pub use crate::collections::*;
 pub use crate::error::{DeltaError, DeltaResult};
+pub use crate::error::{LockAccess, LockError, LockErrorKind};
+pub use crate::num::*;
 pub use crate::option::OptionDelta;
+pub use crate::policy::{ApplyPolicy, OnFailure};
 pub use crate::range::RangeDelta;
+pub use crate::render::{render_diff, DiffRenderer};
 pub use crate::rc::*;
 pub use crate::string::{Str, StringDelta};
-pub use crate::sync::*;
+pub use crate::sync::{ArcDelta, RwLock, RwLockDelta};
+pub use crate::sync::{LockResult, RwLockReadGuard, RwLockWriteGuard};
 pub use crate::tuple::*;
 pub use crate::vec::{EltDelta, VecDelta};
+pub use crate::visit::{ChangeKind, DeltaVisitor, PathSegment, Walk};
@@ -0,0 +1,302 @@
+//! Deltoid impls for the types in [`std::num`], i.e. the `NonZero*`
+//! integer types as well as [`Wrapping`] and [`Saturating`].
+//!
+//! [`std::num`]: https://doc.rust-lang.org/std/num/index.html
+//! [`Wrapping`]: https://doc.rust-lang.org/std/num/struct.Wrapping.html
+//! [`Saturating`]: https://doc.rust-lang.org/std/num/struct.Saturating.html
+
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::ApplyPolicy;
+use crate::{DynDelta, ToDynDelta};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::num::{
+    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize,
+    NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+    Saturating, Wrapping,
+};
+
+
+macro_rules! impl_delta_trait_for_nonzero_types {
+    ( $($type:ty => $prim:ty => $delta:ident);* $(;)? ) => {
+        $(
+            /// The delta of a `NonZero*` value is encoded as the raw
+            /// primitive value. That value is validated to be non-zero
+            /// when the delta is applied.
+            #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+            pub struct $delta(#[doc(hidden)] pub Option<$prim>);
+
+            impl Core for $type {
+                type Delta = $delta;
+            }
+
+            impl Apply for $type {
+                #[inline(always)]
+                fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+                    Self::from_delta(delta)
+                }
+            }
+
+            impl Delta for $type {
+                #[inline(always)]
+                fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+                    rhs.into_delta()
+                }
+            }
+
+            impl FromDelta for $type {
+                fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+                    let prim: $prim = delta.0
+                        .ok_or_else(|| ExpectedValue!(stringify!($delta)))?;
+                    <$type>::new(prim).ok_or_else(|| {
+                        DeltaError::FailedToConvertFromDelta {
+                            reason: format!(
+                                "{} holds 0, which is not a valid {}",
+                                stringify!($delta),
+                                stringify!($type),
+                            ),
+                        }
+                    })
+                }
+            }
+
+            impl IntoDelta for $type {
+                #[inline(always)]
+                fn into_delta(self) -> DeltaResult<Self::Delta> {
+                    Ok($delta(Some(self.get())))
+                }
+            }
+
+            impl std::fmt::Debug for $delta {
+                fn fmt(&self, f: &mut std::fmt::Formatter)
+                       -> Result<(), std::fmt::Error>
+                {
+                    match self.0 {
+                        None =>
+                            write!(f, "{}(None)", stringify!($delta)),
+                        Some(prim) =>
+                            write!(f, "{}({:#?})", stringify!($delta), prim),
+                    }
+                }
+            }
+
+            impl ToDynDelta for $delta {
+                fn to_dyn_delta(&self) -> DynDelta {
+                    match &self.0 {
+                        None => DynDelta::Unchanged,
+                        Some(prim) => DynDelta::replace(prim),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_delta_trait_for_nonzero_types! {
+    NonZeroI8    => i8    => NonZeroI8Delta;
+    NonZeroI16   => i16   => NonZeroI16Delta;
+    NonZeroI32   => i32   => NonZeroI32Delta;
+    NonZeroI64   => i64   => NonZeroI64Delta;
+    NonZeroI128  => i128  => NonZeroI128Delta;
+    NonZeroIsize => isize => NonZeroIsizeDelta;
+
+    NonZeroU8    => u8    => NonZeroU8Delta;
+    NonZeroU16   => u16   => NonZeroU16Delta;
+    NonZeroU32   => u32   => NonZeroU32Delta;
+    NonZeroU64   => u64   => NonZeroU64Delta;
+    NonZeroU128  => u128  => NonZeroU128Delta;
+    NonZeroUsize => usize => NonZeroUsizeDelta;
+}
+
+
+
+macro_rules! impl_delta_trait_for_num_wrapper_types {
+    ( $($wrapper:ident => $delta:ident);* $(;)? ) => {
+        $(
+            impl<T> Core for $wrapper<T>
+            where T: Clone + Debug + PartialEq + Core
+                + for<'de> Deserialize<'de>
+                + Serialize
+            {
+                type Delta = $delta<T>;
+            }
+
+            impl<T> Apply for $wrapper<T>
+            where T: Apply
+                + for<'de> Deserialize<'de>
+                + Serialize
+            {
+                fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+                    self.apply_with(delta, &ApplyPolicy::default())
+                }
+
+                fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                              -> DeltaResult<Self>
+                {
+                    match delta.0 {
+                        Some(delta) =>
+                            self.0.apply_with(delta, policy).map($wrapper),
+                        None => Ok(self.clone()),
+                    }
+                }
+
+                fn validate_into(
+                    &self,
+                    delta: &Self::Delta,
+                    errors: &mut Vec<DeltaError>,
+                ) {
+                    if let Some(delta) = &delta.0 {
+                        self.0.validate_into(delta, errors);
+                    }
+                }
+            }
+
+            impl<T> Delta for $wrapper<T>
+            where T: Delta
+                + for<'de> Deserialize<'de>
+                + Serialize
+            {
+                fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+                    Ok($delta(if self == rhs {
+                        None
+                    } else {
+                        Some(self.0.delta(&rhs.0)?)
+                    }))
+                }
+            }
+
+            impl<T> FromDelta for $wrapper<T>
+            where T: Clone + Debug + PartialEq + FromDelta
+                + for<'de> Deserialize<'de>
+                + Serialize
+            {
+                fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+                    let delta = delta.0
+                        .ok_or_else(|| ExpectedValue!(stringify!($delta)))?;
+                    <T>::from_delta(delta).map($wrapper)
+                }
+            }
+
+            impl<T> IntoDelta for $wrapper<T>
+            where T: Clone + Debug + PartialEq + IntoDelta
+                + for<'de> Deserialize<'de>
+                + Serialize
+            {
+                fn into_delta(self) -> DeltaResult<Self::Delta> {
+                    self.0.into_delta().map(Some).map($delta)
+                }
+            }
+
+
+            #[derive(Clone, PartialEq)]
+            #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+            pub struct $delta<T: Core>(
+                #[doc(hidden)] pub Option<<T as Core>::Delta>
+            );
+
+            impl<T: Core> std::fmt::Debug for $delta<T> {
+                fn fmt(&self, f: &mut std::fmt::Formatter)
+                       -> Result<(), std::fmt::Error>
+                {
+                    match &self.0 {
+                        Some(d) => write!(f, "{}({:#?})", stringify!($delta), d),
+                        None    => write!(f, "{}(None)", stringify!($delta)),
+                    }
+                }
+            }
+
+            impl<T: Core> ToDynDelta for $delta<T>
+            where T::Delta: ToDynDelta {
+                fn to_dyn_delta(&self) -> DynDelta {
+                    self.0.to_dyn_delta()
+                }
+            }
+        )*
+    };
+}
+
+impl_delta_trait_for_num_wrapper_types! {
+    Wrapping   => WrappingDelta;
+    Saturating => SaturatingDelta;
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use super::*;
+
+    #[test]
+    fn NonZeroU32__delta__different_values() -> DeltaResult<()> {
+        let n0 = NonZeroU32::new(42).unwrap();
+        let n1 = NonZeroU32::new(300).unwrap();
+        let delta: <NonZeroU32 as Core>::Delta = n0.delta(&n1)?;
+        let json_string = serde_json::to_string(&delta)
+            .expect("Could not serialize to json");
+        assert_eq!(json_string, "300");
+        let delta1: <NonZeroU32 as Core>::Delta = serde_json::from_str(
+            &json_string
+        ).expect("Could not deserialize from json");
+        assert_eq!(delta, delta1);
+        Ok(())
+    }
+
+    #[test]
+    fn NonZeroU32__apply__different_values() -> DeltaResult<()> {
+        let n0 = NonZeroU32::new(42).unwrap();
+        let n1 = NonZeroU32::new(300).unwrap();
+        let delta: <NonZeroU32 as Core>::Delta = n0.delta(&n1)?;
+        let n2 = n0.apply(delta)?;
+        assert_eq!(n1, n2);
+        Ok(())
+    }
+
+    #[test]
+    fn NonZeroU32__apply__zero() -> DeltaResult<()> {
+        let n0 = NonZeroU32::new(42).unwrap();
+        let result = n0.apply(NonZeroU32Delta(Some(0)));
+        assert!(matches!(
+            result,
+            Err(DeltaError::FailedToConvertFromDelta { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn Wrapping__delta__same_values() -> DeltaResult<()> {
+        let w0 = Wrapping(42u64);
+        let w1 = Wrapping(42u64);
+        let delta: <Wrapping<u64> as Core>::Delta = w0.delta(&w1)?;
+        assert_eq!(delta, WrappingDelta(None));
+        let w2 = w0.apply(delta)?;
+        assert_eq!(w1, w2);
+        Ok(())
+    }
+
+    #[test]
+    fn Wrapping__apply__different_values() -> DeltaResult<()> {
+        let w0 = Wrapping(u64::MAX);
+        let w1 = w0 + Wrapping(2);
+        let delta: <Wrapping<u64> as Core>::Delta = w0.delta(&w1)?;
+        let json_string = serde_json::to_string(&delta)
+            .expect("Could not serialize to json");
+        assert_eq!(json_string, "1");
+        let w2 = w0.apply(delta)?;
+        assert_eq!(w1, w2);
+        Ok(())
+    }
+
+    #[test]
+    fn Saturating__apply__different_values() -> DeltaResult<()> {
+        let s0 = Saturating(250u8);
+        let s1 = s0 + Saturating(10);
+        let delta: <Saturating<u8> as Core>::Delta = s0.delta(&s1)?;
+        let s2 = s0.apply(delta)?;
+        assert_eq!(s1, s2);
+        assert_eq!(s2, Saturating(u8::MAX));
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 //!
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::ApplyPolicy;
+use crate::{DynDelta, ToDynDelta};
 use std::fmt::Debug;
 use serde::{Deserialize, Serialize};
 
@@ -18,16 +20,32 @@ where T: Apply + FromDelta
     + Serialize
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
         Ok(match (&self, delta) {
-            (None,    Self::Delta::None)    => None,
-            (Some(_), Self::Delta::None)    => self.clone(),
+            (_,       Self::Delta::None)    => None,
             (None,    Self::Delta::Some(ref d)) => Some(
                 <T>::from_delta(d.clone(/*TODO: rm clone for more efficiency*/))?
             ),
             (Some(t), Self::Delta::Some(ref d)) =>
-                Some(t.apply(d.clone(/*TODO: rm clone for more efficiency*/))?),
+                Some(t.apply_with(
+                    d.clone(/*TODO: rm clone for more efficiency*/),
+                    policy,
+                )?),
         })
     }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        match (&self, delta) {
+            (_,       Self::Delta::None)    => {},
+            (None,    Self::Delta::Some(d)) => <T>::validate_from_delta(d, errors),
+            (Some(t), Self::Delta::Some(d)) => t.validate_into(d, errors),
+        }
+    }
 }
 
 impl<T> Delta for Option<T>
@@ -56,6 +74,12 @@ where T: Clone + Debug + PartialEq + FromDelta
             Self::Delta::Some(delta) => Some(<T>::from_delta(delta)?),
         })
     }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if let Self::Delta::Some(delta) = delta {
+            <T>::validate_from_delta(delta, errors);
+        }
+    }
 }
 
 impl<T> IntoDelta for Option<T>
@@ -89,6 +113,22 @@ impl<T: Core> std::fmt::Debug for OptionDelta<T> {
     }
 }
 
+impl<T: Core> ToDynDelta for OptionDelta<T>
+where T::Delta: ToDynDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        match &self {
+            // `Some` adds no path segment of its own, so a change inside
+            // it reads as `a.b` rather than `a.Some.b`:
+            Self::Some(d) => match d.to_dyn_delta() {
+                DynDelta::Unchanged =>
+                    DynDelta::variant("Some", DynDelta::Unchanged),
+                dyn_delta => dyn_delta,
+            },
+            Self::None    => DynDelta::variant("None", DynDelta::Unchanged),
+        }
+    }
+}
+
 
 #[allow(non_snake_case)]
 #[cfg(test)]
@@ -155,4 +195,17 @@ mod tests {
         assert_eq!(option1, option2);
         Ok(())
     }
+
+    #[test]
+    fn Option__apply__some_to_none() -> DeltaResult<()> {
+        use std::collections::BTreeMap;
+        let option0 = Some(String::from("foo"));
+        let delta: <Option<String> as Core>::Delta = option0.delta(&None)?;
+        assert_eq!(option0.apply(delta)?, None);
+        // NOTE: A map edit is applied to the optional value it replaces:
+        let map0: BTreeMap<u8, Option<u8>> = BTreeMap::from([(1, Some(1))]);
+        let map1: BTreeMap<u8, Option<u8>> = BTreeMap::from([(1, None)]);
+        assert_eq!(map0.apply(map0.delta(&map1)?)?, map1);
+        Ok(())
+    }
 }
@@ -0,0 +1,164 @@
+//! Policies for handling recoverable failures while applying a delta.
+//!
+//! A delta computed against a stale base may not quite fit the value it is
+//! applied to, e.g. it may edit a `Vec` element that no longer exists.
+//! [`Apply::apply_with`](crate::Apply::apply_with) lets the caller decide
+//! per failure mode whether that is an error:
+//!
+//! ```
+//! use deltoid::{Apply, ApplyPolicy, Delta, DeltaResult};
+//!
+//! # fn main() -> DeltaResult<()> {
+//! let stale: Vec<u8> = vec![1, 2, 3];
+//! let delta = stale.delta(&vec![1, 2, 4])?;
+//! let current: Vec<u8> = vec![1, 2];
+//! assert!(current.apply(delta.clone()).is_err());
+//! assert_eq!(current.apply_with(delta.clone(), &ApplyPolicy::lenient())?, vec![1, 2]);
+//! assert_eq!(current.apply_with(delta, &ApplyPolicy::upsert())?, vec![1, 2, 4]);
+//! # Ok(())
+//! # }
+//! ```
+
+
+/// What to do when part of a delta can't be applied as-is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub enum OnFailure {
+    /// Fail with a `DeltaError`.
+    Error,
+    /// Leave the affected part of the value as it is.
+    Skip,
+    /// Insert the missing element or entry, or use the `Default` value for
+    /// an empty primitive delta.
+    Upsert,
+}
+
+/// Determines how [`Apply::apply_with`](crate::Apply::apply_with) handles
+/// each kind of recoverable failure.
+///
+/// The `Default` policy matches the behavior of `Apply::apply`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub struct ApplyPolicy {
+    /// An `EltDelta::Edit` whose index is out of range.
+    /// Upserting it edits the element at that index of the new value,
+    /// or appends the element if the index is 1 past the end.  Any other
+    /// index is an error, since it would leave a gap.
+    pub out_of_range_edit: OnFailure,
+    /// An `Edit` of a map entry whose key is missing.  Unless this is an
+    /// error, an `IndexMap` is also reordered as far as its keys allow.
+    pub missing_map_key: OnFailure,
+    /// A `Remove` of a set item that is missing.
+    /// Upserting it is the same as skipping it.  Unless this is an error,
+    /// an `IndexSet` is also reordered as far as its items allow.
+    pub missing_set_item: OnFailure,
+    /// A primitive delta without a value.
+    pub empty_delta: OnFailure,
+}
+
+impl ApplyPolicy {
+    /// Use `on_failure` for every kind of failure.
+    pub const fn uniform(on_failure: OnFailure) -> Self {
+        Self {
+            out_of_range_edit: on_failure,
+            missing_map_key: on_failure,
+            missing_set_item: on_failure,
+            empty_delta: on_failure,
+        }
+    }
+
+    /// Fail on every kind of failure.
+    pub const fn strict() -> Self { Self::uniform(OnFailure::Error) }
+
+    /// Skip every part of a delta that can't be applied.
+    pub const fn lenient() -> Self { Self::uniform(OnFailure::Skip) }
+
+    /// Insert whatever is missing, and use `Default` values for empty deltas.
+    pub const fn upsert() -> Self { Self::uniform(OnFailure::Upsert) }
+}
+
+impl Default for ApplyPolicy {
+    fn default() -> Self {
+        Self {
+            missing_set_item: OnFailure::Skip,
+            ..Self::strict()
+        }
+    }
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use crate::{Apply, BTreeMapDelta, Delta, DeltaResult, IntoDelta, U8Delta};
+    use crate::collections::btreemap::EntryDelta;
+    use std::collections::BTreeMap;
+    use super::*;
+
+    #[test]
+    fn ApplyPolicy__default() {
+        assert_eq!(ApplyPolicy::default(), ApplyPolicy {
+            out_of_range_edit: OnFailure::Error,
+            missing_map_key: OnFailure::Error,
+            missing_set_item: OnFailure::Skip,
+            empty_delta: OnFailure::Error,
+        });
+    }
+
+    #[test]
+    fn Vec__apply_with__out_of_range_edit() -> DeltaResult<()> {
+        let stale: Vec<(u8, u8)> = vec![(1, 1), (2, 2)];
+        let delta = stale.delta(&vec![(1, 1), (2, 3)])?;
+        let current: Vec<(u8, u8)> = vec![(1, 1)];
+        assert!(current.apply_with(delta.clone(), &ApplyPolicy::strict()).is_err());
+        assert_eq!(
+            current.apply_with(delta.clone(), &ApplyPolicy::lenient())?,
+            vec![(1, 1)]
+        );
+        // NOTE: the edit only changes the `.1` field, so it can't be upserted:
+        assert!(current.apply_with(delta, &ApplyPolicy::upsert()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn Vec__apply_with__upsert_at_index() -> DeltaResult<()> {
+        use crate::{EltDelta, VecDelta};
+        let delta = VecDelta(vec![
+            EltDelta::Add(5u8.into_delta()?),
+            EltDelta::Edit { index: 1, item: 7u8.into_delta()? },
+        ]);
+        assert_eq!(vec![1u8].apply_with(delta, &ApplyPolicy::upsert())?, vec![1, 7]);
+        let delta = vec![1u8, 2, 3].delta(&vec![1, 2, 4])?;
+        assert_eq!(
+            vec![1u8, 2].apply_with(delta.clone(), &ApplyPolicy::upsert())?,
+            vec![1, 2, 4]
+        );
+        // NOTE: Upserting at index 2 of `[1]` would leave a gap:
+        assert!(vec![1u8].apply_with(delta, &ApplyPolicy::upsert()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn BTreeMap__apply_with__missing_map_key() -> DeltaResult<()> {
+        let map0: BTreeMap<u8, u8> = BTreeMap::new();
+        let delta = BTreeMapDelta(Some(vec![
+            EntryDelta::Edit { key: 1, value: 10u8.into_delta()? },
+        ]));
+        assert!(map0.apply_with(delta.clone(), &ApplyPolicy::strict()).is_err());
+        assert_eq!(map0.apply_with(delta.clone(), &ApplyPolicy::lenient())?, map0);
+        let map1 = map0.apply_with(delta, &ApplyPolicy::upsert())?;
+        assert_eq!(map1.get(&1), Some(&10));
+        Ok(())
+    }
+
+    #[test]
+    fn Option__apply_with__empty_delta() -> DeltaResult<()> {
+        let opt0: Option<u8> = Some(5);
+        let delta = crate::OptionDelta::Some(U8Delta(None));
+        assert!(opt0.apply(delta.clone()).is_err());
+        assert_eq!(opt0.apply_with(delta.clone(), &ApplyPolicy::lenient())?, Some(5));
+        assert_eq!(opt0.apply_with(delta, &ApplyPolicy::upsert())?, Some(0));
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 //!
 
 use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{DynDelta, ToDynDelta};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de;
 use serde::ser::SerializeMap;
@@ -81,6 +82,16 @@ where T: Core + std::fmt::Debug {
     }
 }
 
+impl<T> ToDynDelta for RangeDelta<T>
+where T: Core + std::fmt::Debug {
+    fn to_dyn_delta(&self) -> DynDelta {
+        match &self.0 {
+            Some(field) => DynDelta::replace(field),
+            None        => DynDelta::Unchanged,
+        }
+    }
+}
+
 impl<T> Serialize for RangeDelta<T>
 where T: Core
     + Clone
@@ -3,7 +3,9 @@
 //!
 //! [`Rc`]: https://doc.rust-lang.org/std/rc/struct.Rc.html
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::ApplyPolicy;
+use crate::{DynDelta, ToDynDelta};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de;
 use serde::ser::SerializeMap;
@@ -26,10 +28,22 @@ where T: Apply
     + Serialize
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
         let lhs: &T = self.as_ref();
         match delta.0 {
             None => Ok(self.clone()),
-            Some(delta) => lhs.apply(*delta).map(Rc::new),
+            Some(delta) => lhs.apply_with(*delta, policy).map(Rc::new),
+        }
+    }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if let Some(delta) = &delta.0 {
+            self.as_ref().validate_into(delta, errors);
         }
     }
 }
@@ -88,6 +102,13 @@ impl<T: Core> std::fmt::Debug for RcDelta<T> {
     }
 }
 
+impl<T: Core> ToDynDelta for RcDelta<T>
+where T::Delta: ToDynDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        self.0.to_dyn_delta()
+    }
+}
+
 impl<T: Core> Serialize for RcDelta<T> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where S: Serializer {
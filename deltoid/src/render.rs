@@ -0,0 +1,511 @@
+//! Human-readable rendering of the difference between two values.
+//!
+//! The rendering is a tree diff of the pretty-printed `Debug` output of both
+//! values: every line is prefixed with a marker, i.e. `-` for removed lines,
+//! `+` for added lines and `~` for changed leaf values, and unchanged
+//! subtrees are collapsed to a single line.
+
+use crate::{Apply, DeltaResult};
+use std::fmt::Debug;
+
+const RED:    &str = "\x1b[31m";
+const GREEN:  &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const RESET:  &str = "\x1b[0m";
+
+
+/// Render the difference between `old` and `new` with the default options.
+pub fn render_diff<T: Debug>(old: &T, new: &T) -> String {
+    DiffRenderer::default().render(old, new)
+}
+
+
+/// Options for rendering diffs.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DiffRenderer {
+    /// Color the markers and lines using ANSI escape codes.
+    pub color: bool,
+    /// Collapse unchanged multi-line subtrees to a single line.
+    pub collapse_unchanged: bool,
+    /// The maximum number of lines to render.
+    pub max_lines: Option<usize>,
+    /// The maximum number of characters per line, excluding the marker.
+    pub max_width: Option<usize>,
+}
+
+impl Default for DiffRenderer {
+    fn default() -> Self {
+        Self {
+            color: false,
+            collapse_unchanged: true,
+            max_lines: None,
+            max_width: None,
+        }
+    }
+}
+
+impl DiffRenderer {
+    /// Render the difference between `old` and `new`.
+    pub fn render<T: Debug>(&self, old: &T, new: &T) -> String {
+        let old: String = format!("{:#?}", old);
+        let new: String = format!("{:#?}", new);
+        let old_lines: Vec<&str> = old.lines().collect();
+        let new_lines: Vec<&str> = new.lines().collect();
+        let mut ops: Vec<Op> = diff_lines(&old_lines, &new_lines);
+        slide_hunks_down(&mut ops);
+        let ops: Vec<Op> = pair_changed_lines(ops);
+        let rows: Vec<Op> = if self.collapse_unchanged {
+            collapse_unchanged(ops)
+        } else {
+            ops
+        };
+        let mut buf = String::new();
+        for (idx, row) in rows.iter().enumerate() {
+            if self.max_lines.is_some_and(|max_lines| idx >= max_lines) {
+                let more = rows.len() - idx;
+                buf.push_str(&format!("  ... {} more lines\n", more));
+                break;
+            }
+            let (marker, color, line) = match row {
+                Op::Same(line)    => (' ', "",     line.to_string()),
+                Op::Removed(line) => ('-', RED,    line.to_string()),
+                Op::Added(line)   => ('+', GREEN,  line.to_string()),
+                Op::Changed(old, new) => ('~', YELLOW, render_change(old, new)),
+                Op::Collapsed(open, close) =>
+                    (' ', "", format!("{} ... {}", open, close.trim_start())),
+            };
+            let line = self.truncate(line);
+            if self.color && !color.is_empty() {
+                buf.push_str(&format!("{}{} {}{}\n", color, marker, line, RESET));
+            } else {
+                buf.push_str(&format!("{} {}\n", marker, line));
+            }
+        }
+        buf
+    }
+
+    /// Render the difference between `old` and the result of applying
+    /// `delta` to `old`.
+    pub fn render_delta<T>(&self, old: &T, delta: T::Delta) -> DeltaResult<String>
+    where T: Apply + Debug {
+        let new: T = old.apply(delta)?;
+        Ok(self.render(old, &new))
+    }
+
+    fn truncate(&self, line: String) -> String {
+        match self.max_width {
+            Some(max_width) if line.chars().count() > max_width => {
+                let mut truncated: String = line.chars()
+                    .take(max_width.saturating_sub(3))
+                    .collect();
+                truncated.push_str("...");
+                truncated
+            },
+            _ => line,
+        }
+    }
+}
+
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Op<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+    /// A leaf line whose value changed.
+    Changed(&'a str, &'a str),
+    /// An unchanged subtree, represented by its opening and closing lines.
+    Collapsed(&'a str, &'a str),
+}
+
+/// Compute a line diff based on the shortest edit script between the lines,
+/// using Myers' algorithm in linear space so that large values with changes
+/// far apart don't need a quadratic table.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Op<'a>> {
+    let mut ops: Vec<Op> = Vec::with_capacity(old.len().max(new.len()));
+    diff_range(old, new, &mut ops);
+    removed_before_added(&mut ops);
+    ops
+}
+
+/// Push the ops that turn `old` into `new` onto `ops`.
+fn diff_range<'a>(old: &[&'a str], new: &[&'a str], ops: &mut Vec<Op<'a>>) {
+    // NOTE: strip the common prefix and suffix, which in practice is most
+    //       of the input, and which ensures that `bisect` makes progress.
+    let prefix: usize = old.iter().zip(new.iter())
+        .take_while(|(o, n)| o == n)
+        .count();
+    let suffix: usize = old[prefix..].iter().rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(o, n)| o == n)
+        .count();
+    let old_mid: &[&str] = &old[prefix .. old.len() - suffix];
+    let new_mid: &[&str] = &new[prefix .. new.len() - suffix];
+    ops.extend(old[..prefix].iter().map(|l| Op::Same(l)));
+    match bisect(old_mid, new_mid) {
+        Some((x, y)) => {
+            diff_range(&old_mid[..x], &new_mid[..y], ops);
+            diff_range(&old_mid[x..], &new_mid[y..], ops);
+        },
+        None => {
+            ops.extend(old_mid.iter().map(|l| Op::Removed(l)));
+            ops.extend(new_mid.iter().map(|l| Op::Added(l)));
+        },
+    }
+    ops.extend(old[old.len() - suffix ..].iter().map(|l| Op::Same(l)));
+}
+
+/// Find the point `(x, y)` where a shortest edit script from `old` to `new`
+/// can be split in two, by searching from both ends at once for the middle
+/// snake.  Returns `None` if there is no such point, e.g. when either side
+/// is empty, in which case all of `old` is removed and all of `new` added.
+fn bisect(old: &[&str], new: &[&str]) -> Option<(usize, usize)> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    if n == 0 || m == 0 { return None }
+    let max_d: isize = (n + m + 1) / 2;
+    let offset: isize = max_d;
+    let len: usize = 2 * max_d as usize + 2;
+    // NOTE: `forward[offset + k]` is the furthest `x` reached on diagonal
+    //       `k = x - y` from the start, and `backward` likewise from the end.
+    let mut forward: Vec<isize> = vec![-1; len];
+    let mut backward: Vec<isize> = vec![-1; len];
+    forward[(offset + 1) as usize] = 0;
+    backward[(offset + 1) as usize] = 0;
+    let delta: isize = n - m;
+    let odd: bool = delta % 2 != 0;
+    let (mut k1_start, mut k1_end, mut k2_start, mut k2_end) = (0, 0, 0, 0);
+    for d in 0 .. max_d {
+        let mut k1: isize = -d + k1_start;
+        while k1 <= d - k1_end {
+            let k1_idx: usize = (offset + k1) as usize;
+            let mut x1: isize = if k1 == -d
+                || (k1 != d && forward[k1_idx - 1] < forward[k1_idx + 1])
+            {
+                forward[k1_idx + 1]
+            } else {
+                forward[k1_idx - 1] + 1
+            };
+            let mut y1: isize = x1 - k1;
+            while x1 < n && y1 < m && old[x1 as usize] == new[y1 as usize] {
+                x1 += 1;
+                y1 += 1;
+            }
+            forward[k1_idx] = x1;
+            if x1 > n {
+                k1_end += 2;
+            } else if y1 > m {
+                k1_start += 2;
+            } else if odd {
+                let k2_idx: isize = offset + delta - k1;
+                if 0 <= k2_idx && k2_idx < len as isize
+                    && backward[k2_idx as usize] != -1
+                    && x1 >= n - backward[k2_idx as usize]
+                {
+                    return Some((x1 as usize, y1 as usize));
+                }
+            }
+            k1 += 2;
+        }
+        let mut k2: isize = -d + k2_start;
+        while k2 <= d - k2_end {
+            let k2_idx: usize = (offset + k2) as usize;
+            let mut x2: isize = if k2 == -d
+                || (k2 != d && backward[k2_idx - 1] < backward[k2_idx + 1])
+            {
+                backward[k2_idx + 1]
+            } else {
+                backward[k2_idx - 1] + 1
+            };
+            let mut y2: isize = x2 - k2;
+            while x2 < n && y2 < m
+                && old[(n - x2 - 1) as usize] == new[(m - y2 - 1) as usize]
+            {
+                x2 += 1;
+                y2 += 1;
+            }
+            backward[k2_idx] = x2;
+            if x2 > n {
+                k2_end += 2;
+            } else if y2 > m {
+                k2_start += 2;
+            } else if !odd {
+                let k1_idx: isize = offset + delta - k2;
+                if 0 <= k1_idx && k1_idx < len as isize
+                    && forward[k1_idx as usize] != -1
+                {
+                    let x1: isize = forward[k1_idx as usize];
+                    let y1: isize = offset + x1 - k1_idx;
+                    if x1 >= n - x2 {
+                        return Some((x1 as usize, y1 as usize));
+                    }
+                }
+            }
+            k2 += 2;
+        }
+    }
+    None
+}
+
+/// Reorder each run of removed and added lines so that the removed lines
+/// come first, which is the shape that `pair_changed_lines` expects.
+fn removed_before_added(ops: &mut [Op]) {
+    let mut start = 0;
+    while start < ops.len() {
+        let is_edit = |op: &Op| matches!(op, Op::Removed(_) | Op::Added(_));
+        if !is_edit(&ops[start]) { start += 1; continue }
+        let end: usize = start + ops[start..].iter()
+            .take_while(|op| is_edit(op))
+            .count();
+        ops[start..end].sort_by_key(|op| matches!(op, Op::Added(_)));
+        start = end;
+    }
+}
+
+/// Move each run of removed or added lines down for as long as its first line
+/// equals the unchanged line after it, e.g. so that an added subtree includes
+/// its own closing line rather than that of the previous subtree.
+fn slide_hunks_down(ops: &mut [Op]) {
+    let mut start = 0;
+    while start < ops.len() {
+        let added: bool = match ops[start] {
+            Op::Removed(_) => false,
+            Op::Added(_) => true,
+            _ => { start += 1; continue },
+        };
+        let same_kind = |op: &Op| match op {
+            Op::Removed(_) => !added,
+            Op::Added(_) => added,
+            _ => false,
+        };
+        let mut end: usize = start;
+        while end < ops.len() && same_kind(&ops[end]) { end += 1; }
+        while end < ops.len() {
+            let (first, next) = match (&ops[start], &ops[end]) {
+                (Op::Removed(first), Op::Same(next))
+                    | (Op::Added(first), Op::Same(next)) => (*first, *next),
+                _ => break,
+            };
+            if first != next { break }
+            ops[start] = Op::Same(first);
+            ops[end] = if added { Op::Added(next) } else { Op::Removed(next) };
+            start += 1;
+            end += 1;
+        }
+        start = end;
+    }
+}
+
+/// Pair up the lines of a run of removed lines with those of a directly
+/// following run of added lines, and turn each pair into a changed line
+/// if both lines are leaves with the same key.
+fn pair_changed_lines(ops: Vec<Op>) -> Vec<Op> {
+    let mut paired: Vec<Op> = Vec::with_capacity(ops.len());
+    let mut idx = 0;
+    while idx < ops.len() {
+        let removed: usize = ops[idx..].iter()
+            .take_while(|op| matches!(op, Op::Removed(_)))
+            .count();
+        let added: usize = ops[idx + removed ..].iter()
+            .take_while(|op| matches!(op, Op::Added(_)))
+            .count();
+        if removed == 0 || added == 0 {
+            paired.push(ops[idx].clone());
+            idx += 1;
+            continue;
+        }
+        let (removed_ops, added_ops) = ops[idx .. idx + removed + added]
+            .split_at(removed);
+        let mut unpaired_added: Vec<Op> = vec![];
+        for offset in 0 .. removed.max(added) {
+            match (removed_ops.get(offset), added_ops.get(offset)) {
+                (Some(Op::Removed(old)), Some(Op::Added(new)))
+                    if is_same_leaf(old, new) =>
+                    paired.push(Op::Changed(old, new)),
+                (old, new) => {
+                    paired.extend(old.cloned());
+                    unpaired_added.extend(new.cloned());
+                },
+            }
+        }
+        paired.extend(unpaired_added);
+        idx += removed + added;
+    }
+    paired
+}
+
+fn collapse_unchanged(ops: Vec<Op>) -> Vec<Op> {
+    let mut collapsed: Vec<Op> = Vec::with_capacity(ops.len());
+    let mut idx = 0;
+    while idx < ops.len() {
+        if let Op::Same(open) = ops[idx] {
+            if let Some(close_idx) = find_unchanged_close(&ops, idx, open) {
+                if let Op::Same(close) = ops[close_idx] {
+                    collapsed.push(Op::Collapsed(open, close));
+                    idx = close_idx + 1;
+                    continue;
+                }
+            }
+        }
+        collapsed.push(ops[idx].clone());
+        idx += 1;
+    }
+    collapsed
+}
+
+/// Find the index of the line that closes the subtree opened by `open` at
+/// `open_idx`, provided that the whole subtree is unchanged.
+fn find_unchanged_close(ops: &[Op], open_idx: usize, open: &str) -> Option<usize> {
+    if !is_open(open) { return None }
+    for (idx, op) in ops.iter().enumerate().skip(open_idx + 1) {
+        match op {
+            Op::Same(line) if indent(line) == indent(open) && is_close(line) =>
+                return Some(idx),
+            Op::Same(_) => continue,
+            _ => return None,
+        }
+    }
+    None
+}
+
+fn render_change(old: &str, new: &str) -> String {
+    let key_len: usize = key(new).len();
+    let old_value: &str = old.trim_start()[key_len..].trim_end_matches(',');
+    let new_value: &str = new.trim_start()[key_len..].trim_end_matches(',');
+    let comma: &str = if new.ends_with(',') { "," } else { "" };
+    format!(
+        "{}{}{} -> {}{}",
+        &new[..indent(new)], key(new), old_value, new_value, comma
+    )
+}
+
+fn is_same_leaf(old: &str, new: &str) -> bool {
+    let is_leaf = |line: &str| !is_open(line) && !is_close(line);
+    is_leaf(old) && is_leaf(new)
+        && indent(old) == indent(new)
+        && key(old) == key(new)
+}
+
+/// The key of a line, e.g. `name: ` for the line `    name: "foo",`.
+fn key(line: &str) -> &str {
+    let line: &str = line.trim_start();
+    match line.find(": ") {
+        Some(pos) => &line[.. pos + 2],
+        None => "",
+    }
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn is_open(line: &str) -> bool {
+    line.ends_with('{') || line.ends_with('[') || line.ends_with('(')
+}
+
+fn is_close(line: &str) -> bool {
+    let line: &str = line.trim_start();
+    line.starts_with('}') || line.starts_with(']') || line.starts_with(')')
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use crate::{Delta, DeltaResult};
+    use std::collections::BTreeMap;
+    use super::*;
+
+    #[test]
+    fn render_diff__equal_values() {
+        let map: BTreeMap<&str, Vec<u8>> = vec![("foo", vec![1, 2])]
+            .into_iter()
+            .collect();
+        assert_eq!(render_diff(&map, &map), "  { ... }\n");
+    }
+
+    #[test]
+    fn render_diff__different_values() {
+        let map0: BTreeMap<&str, (u8, Vec<u8>)> = vec![
+            ("bar", (0, vec![7])),
+            ("foo", (1, vec![1, 2])),
+        ].into_iter().collect();
+        let mut map1 = map0.clone();
+        map1.insert("foo", (3, vec![1, 2, 4]));
+        map1.insert("qux", (0, vec![]));
+        assert_eq!(render_diff(&map0, &map1), [
+            "  {",
+            "      \"bar\": ( ... ),",
+            "      \"foo\": (",
+            "~         1 -> 3,",
+            "          [",
+            "              1,",
+            "              2,",
+            "+             4,",
+            "          ],",
+            "      ),",
+            "+     \"qux\": (",
+            "+         0,",
+            "+         [],",
+            "+     ),",
+            "  }",
+            "",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn DiffRenderer__render_delta() -> DeltaResult<()> {
+        let vec0: Vec<u8> = vec![1, 2, 3];
+        let vec1: Vec<u8> = vec![1, 5];
+        let delta = vec0.delta(&vec1)?;
+        let renderer = DiffRenderer { color: true, ..DiffRenderer::default() };
+        assert_eq!(renderer.render_delta(&vec0, delta)?, [
+            "  [",
+            "      1,",
+            "\x1b[33m~     2 -> 5,\x1b[0m",
+            "\x1b[31m-     3,\x1b[0m",
+            "  ]",
+            "",
+        ].join("\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn render_diff__large_values_changed_at_both_ends() {
+        let mut vec0: Vec<u32> = (0 .. 50_000).collect();
+        let mut vec1: Vec<u32> = vec0.clone();
+        vec1[0] = 100_000;
+        vec1[49_999] = 100_001;
+        vec0.insert(25_000, 7);
+        let renderer = DiffRenderer {
+            collapse_unchanged: false,
+            ..DiffRenderer::default()
+        };
+        let rendered: String = renderer.render(&vec0, &vec1);
+        let changed: Vec<&str> = rendered.lines()
+            .filter(|line| !line.starts_with(' '))
+            .collect();
+        assert_eq!(changed, [
+            "~     0 -> 100000,",
+            "-     7,",
+            "~     49999 -> 100001,",
+        ]);
+    }
+
+    #[test]
+    fn DiffRenderer__truncation() {
+        let vec0: Vec<String> = vec!["a".repeat(20), "b".into(), "c".into()];
+        let vec1: Vec<String> = vec![];
+        let renderer = DiffRenderer {
+            max_lines: Some(2),
+            max_width: Some(10),
+            ..DiffRenderer::default()
+        };
+        assert_eq!(renderer.render(&vec0, &vec1), [
+            "- [",
+            "-     \"aa...",
+            "  ... 4 more lines",
+            "",
+        ].join("\n"));
+    }
+}
@@ -1,6 +1,8 @@
 //!
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{ApplyPolicy, PathSegment};
+use crate::{DynDelta, ToDynDelta};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
@@ -16,10 +18,21 @@ where T: Apply + FromDelta + for<'de> Deserialize<'de> + Serialize,
       E: Apply + FromDelta + for<'de> Deserialize<'de> + Serialize
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
+        let at = |name: &str| {
+            let segment = PathSegment::Variant(name.to_string());
+            move |e: DeltaError| e.at(segment)
+        };
         match (self, &delta/*TODO: match by value*/) {
             (Result::Ok(ok), ResultDelta::None) => Ok(Ok(ok.clone())),
             (Result::Ok(ok), ResultDelta::OkDelta(delta)) => {
-                Ok(Ok(ok.apply(delta.clone(/*TODO: rm*/))?))
+                Ok(Ok(ok.apply_with(delta.clone(/*TODO: rm*/), policy)
+                      .map_err(at("Ok"))?))
             },
             (Result::Ok(_ok), delta @ ResultDelta::ErrDelta(_)) => {
                 Ok(Self::from_delta(delta.clone())?)
@@ -29,8 +42,28 @@ where T: Apply + FromDelta + for<'de> Deserialize<'de> + Serialize,
                 Ok(Self::from_delta(delta.clone())?)
             },
             (Result::Err(err), ResultDelta::ErrDelta(delta)) => {
-                Ok(Err(err.apply(delta.clone(/*TODO: rm*/))?))
+                Ok(Err(err.apply_with(delta.clone(/*TODO: rm*/), policy)
+                       .map_err(at("Err"))?))
+            },
+        }
+    }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        match (self, delta) {
+            (_, ResultDelta::None) => {},
+            (Result::Ok(ok), ResultDelta::OkDelta(delta)) => {
+                let start: usize = errors.len();
+                ok.validate_into(delta, errors);
+                let segment = PathSegment::Variant("Ok".into());
+                DeltaError::scope_all(errors, start, &segment);
+            },
+            (Result::Err(err), ResultDelta::ErrDelta(delta)) => {
+                let start: usize = errors.len();
+                err.validate_into(delta, errors);
+                let segment = PathSegment::Variant("Err".into());
+                DeltaError::scope_all(errors, start, &segment);
             },
+            (_, delta) => Self::validate_from_delta(delta, errors),
         }
     }
 }
@@ -66,12 +99,35 @@ where T: Clone + Debug + PartialEq + FromDelta
     fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
         match delta {
             ResultDelta::None => Err(ExpectedValue!("ResultDelta<T, E>")),
-            ResultDelta::OkDelta(delta) =>
-                Ok(Self::Ok(<T>::from_delta(delta)?)),
-            ResultDelta::ErrDelta(delta) =>
-                Ok(Self::Err(<E>::from_delta(delta)?)),
+            ResultDelta::OkDelta(delta) => Ok(Self::Ok(
+                <T>::from_delta(delta)
+                    .map_err(|e| e.at(PathSegment::Variant("Ok".into())))?
+            )),
+            ResultDelta::ErrDelta(delta) => Ok(Self::Err(
+                <E>::from_delta(delta)
+                    .map_err(|e| e.at(PathSegment::Variant("Err".into())))?
+            )),
         }
     }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        let start: usize = errors.len();
+        let segment = match delta {
+            ResultDelta::None => {
+                errors.push(ExpectedValue!("ResultDelta<T, E>"));
+                return;
+            },
+            ResultDelta::OkDelta(delta) => {
+                <T>::validate_from_delta(delta, errors);
+                PathSegment::Variant("Ok".into())
+            },
+            ResultDelta::ErrDelta(delta) => {
+                <E>::validate_from_delta(delta, errors);
+                PathSegment::Variant("Err".into())
+            },
+        };
+        DeltaError::scope_all(errors, start, &segment);
+    }
 }
 
 impl<T, E> IntoDelta for Result<T, E>
@@ -111,6 +167,17 @@ where T: Core, E: Core {
     }
 }
 
+impl<T, E> ToDynDelta for ResultDelta<T, E>
+where T: Core, E: Core, T::Delta: ToDynDelta, E::Delta: ToDynDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        match &self {
+            Self::OkDelta(ok)   => DynDelta::variant("Ok",  ok.to_dyn_delta()),
+            Self::ErrDelta(err) => DynDelta::variant("Err", err.to_dyn_delta()),
+            Self::None          => DynDelta::Unchanged,
+        }
+    }
+}
+
 
 #[allow(non_snake_case)]
 #[cfg(test)]
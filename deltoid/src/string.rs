@@ -1,6 +1,8 @@
 //!
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{ApplyPolicy, OnFailure};
+use crate::{DynDelta, ToDynDelta};
 use std::borrow::Cow;
 
 impl Core for String {
@@ -11,6 +13,21 @@ impl Apply for String {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
         Self::from_delta(delta)
     }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
+        match (delta.0, policy.empty_delta) {
+            (Some(value), _) => Ok(value),
+            (None, OnFailure::Error) => Err(ExpectedValue!("StringDelta<T>")),
+            (None, OnFailure::Skip) => Ok(self.clone()),
+            (None, OnFailure::Upsert) => Ok(Self::default()),
+        }
+    }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        Self::validate_from_delta(delta, errors)
+    }
 }
 
 impl Delta for String {
@@ -23,6 +40,12 @@ impl FromDelta for String {
     fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
         delta.0.ok_or_else(|| ExpectedValue!("StringDelta<T>"))
     }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if delta.0.is_none() {
+            errors.push(ExpectedValue!("StringDelta<T>"));
+        }
+    }
 }
 
 impl IntoDelta for String {
@@ -47,6 +70,15 @@ impl std::fmt::Debug for StringDelta {
     }
 }
 
+impl ToDynDelta for StringDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        match &self.0 {
+            Some(field) => DynDelta::replace(field),
+            None        => DynDelta::Unchanged,
+        }
+    }
+}
+
 
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -93,6 +125,10 @@ impl<'s> Apply for Str<'s> {
             None => self.clone(),
         })
     }
+
+    fn validate_into(&self, _delta: &Self::Delta, _errors: &mut Vec<DeltaError>) {
+        // NOTE: applying a `StrDelta` always succeeds
+    }
 }
 
 impl<'s> Delta for Str<'s> {
@@ -107,6 +143,12 @@ impl<'s> FromDelta for Str<'s> {
             .map(|s| Self(Cow::Owned(s)))
             .ok_or_else(|| ExpectedValue!("StrDelta"))
     }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if delta.0.is_none() {
+            errors.push(ExpectedValue!("StrDelta"));
+        }
+    }
 }
 
 impl<'s> IntoDelta for Str<'s> {
@@ -119,6 +161,55 @@ impl<'s> IntoDelta for Str<'s> {
 }
 
 
+// NOTE: `Cow<'a, B>` is only covered by the impls in `crate::borrow` for
+//       `B: Sized`, so `Cow<'a, str>` gets its own impls.  Its delta is a
+//       `StrDelta` i.e. it owns its data, and doesn't borrow from either the
+//       source value or the deserializer.
+
+impl<'a> Core for Cow<'a, str> {
+    type Delta = StrDelta;
+}
+
+impl<'a> Apply for Cow<'a, str> {
+    fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        Ok(match delta.0 {
+            Some(d) => Cow::Owned(d),
+            None => self.clone(),
+        })
+    }
+
+    fn validate_into(&self, _delta: &Self::Delta, _errors: &mut Vec<DeltaError>) {
+        // NOTE: applying a `StrDelta` always succeeds
+    }
+}
+
+impl<'a> Delta for Cow<'a, str> {
+    fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+        rhs.clone().into_delta()
+    }
+}
+
+impl<'a> FromDelta for Cow<'a, str> {
+    fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+        delta.0
+            .map(Cow::Owned)
+            .ok_or_else(|| ExpectedValue!("StrDelta"))
+    }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if delta.0.is_none() {
+            errors.push(ExpectedValue!("StrDelta"));
+        }
+    }
+}
+
+impl<'a> IntoDelta for Cow<'a, str> {
+    fn into_delta(self) -> DeltaResult<Self::Delta> {
+        Ok(StrDelta(Some(self.into_owned())))
+    }
+}
+
+
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
 pub struct StrDelta( // TODO: Improve delta space efficiency
@@ -134,6 +225,15 @@ impl std::fmt::Debug for StrDelta {
     }
 }
 
+impl ToDynDelta for StrDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        match &self.0 {
+            Some(field) => DynDelta::replace(field),
+            None        => DynDelta::Unchanged,
+        }
+    }
+}
+
 
 
 #[allow(non_snake_case)]
@@ -250,4 +350,22 @@ mod tests {
         assert_eq!(s1, s2);
         Ok(())
     }
+
+    #[test]
+    fn Cow_str__apply__different_values() -> DeltaResult<()> {
+        let foo = String::from("foo");
+        let s0: Cow<str> = Cow::Borrowed(&foo);
+        let s1: Cow<str> = Cow::Borrowed("bar");
+        let delta: <Cow<str> as Core>::Delta = s0.delta(&s1)?;
+        let json_string = serde_json::to_string(&delta)
+            .expect("Could not serialize to json");
+        drop(s0);
+        drop(foo);
+        let delta1: <Cow<str> as Core>::Delta = serde_json::from_str(
+            &json_string
+        ).expect("Could not deserialize from json");
+        let s2 = Cow::Borrowed("baz").apply(delta1)?;
+        assert_eq!(s1, s2);
+        Ok(())
+    }
 }
@@ -3,7 +3,9 @@
 //!
 //! [`Arc`]: https://doc.rust-lang.org/std/sync/struct.Arc.html
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::ApplyPolicy;
+use crate::{DynDelta, ToDynDelta};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de;
 use serde::ser::SerializeMap;
@@ -27,10 +29,22 @@ where T: Clone + Debug + PartialEq + Apply
 {
     #[inline]
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
         let lhs: &T = self.as_ref();
         match delta.0 {
             None => Ok(self.clone()),
-            Some(delta) => lhs.apply(*delta).map(Arc::new),
+            Some(delta) => lhs.apply_with(*delta, policy).map(Arc::new),
+        }
+    }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if let Some(delta) = &delta.0 {
+            self.as_ref().validate_into(delta, errors);
         }
     }
 }
@@ -93,6 +107,13 @@ impl<T: Core> std::fmt::Debug for ArcDelta<T> {
     }
 }
 
+impl<T: Core> ToDynDelta for ArcDelta<T>
+where T::Delta: ToDynDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        self.0.to_dyn_delta()
+    }
+}
+
 impl<T: Core + Clone> Serialize for ArcDelta<T> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where S: Serializer {
@@ -0,0 +1,269 @@
+//! Newtypes wrapping the integer and boolean types in [`std::sync::atomic`]
+//! that provide extra functionality in the form of delta support,
+//! de/serialization, partial equality and more.
+//!
+//! Each newtype is generic over an [`AtomicOrdering`], which determines the
+//! [`Ordering`] used to load and store the wrapped value.  This defaults to
+//! [`SeqCst`].
+//!
+//! [`std::sync::atomic`]: https://doc.rust-lang.org/std/sync/atomic/index.html
+//! [`Ordering`]: https://doc.rust-lang.org/std/sync/atomic/enum.Ordering.html
+
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{DynDelta, ToDynDelta};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::sync::atomic::Ordering;
+
+
+/// Determines the [`Ordering`]s used to access the value of an atomic newtype.
+pub trait AtomicOrdering {
+    /// The `Ordering` used to read the atomic value.
+    const LOAD: Ordering;
+    /// The `Ordering` used to write the atomic value.
+    const STORE: Ordering;
+}
+
+/// Load and store with `Ordering::SeqCst`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SeqCst;
+
+impl AtomicOrdering for SeqCst {
+    const LOAD: Ordering = Ordering::SeqCst;
+    const STORE: Ordering = Ordering::SeqCst;
+}
+
+/// Load with `Ordering::Acquire` and store with `Ordering::Release`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AcqRel;
+
+impl AtomicOrdering for AcqRel {
+    const LOAD: Ordering = Ordering::Acquire;
+    const STORE: Ordering = Ordering::Release;
+}
+
+/// Load and store with `Ordering::Relaxed`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Relaxed;
+
+impl AtomicOrdering for Relaxed {
+    const LOAD: Ordering = Ordering::Relaxed;
+    const STORE: Ordering = Ordering::Relaxed;
+}
+
+
+macro_rules! impl_delta_trait_for_atomic_types {
+    ( $($atomic:ident => $prim:ty => $delta:ident);* $(;)? ) => {
+        $(
+            #[derive(Default)]
+            pub struct $atomic<O: AtomicOrdering = SeqCst>(
+                std::sync::atomic::$atomic,
+                PhantomData<O>,
+            );
+
+            impl<O: AtomicOrdering> $atomic<O> {
+                /// Wrap a new atomic that holds `value`.
+                pub fn new(value: $prim) -> Self {
+                    Self(std::sync::atomic::$atomic::new(value), PhantomData)
+                }
+
+                /// Unwrap the value.
+                pub fn into_inner(self) -> $prim { self.0.into_inner() }
+
+                /// Read the value using `O::LOAD`.
+                pub fn load(&self) -> $prim { self.0.load(O::LOAD) }
+
+                /// Write the value using `O::STORE`.
+                pub fn store(&self, value: $prim) { self.0.store(value, O::STORE) }
+
+                /// Borrow the value mutably, which needs no ordering.
+                pub fn get_mut(&mut self) -> &mut $prim { self.0.get_mut() }
+
+                /// Borrow the wrapped `std` atomic.
+                pub fn as_std(&self) -> &std::sync::atomic::$atomic { &self.0 }
+            }
+
+            impl<O: AtomicOrdering> From<$prim> for $atomic<O> {
+                fn from(value: $prim) -> Self { Self::new(value) }
+            }
+
+            impl<O: AtomicOrdering> Clone for $atomic<O> {
+                fn clone(&self) -> Self { Self::new(self.load()) }
+            }
+
+            impl<O: AtomicOrdering> fmt::Debug for $atomic<O> {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.debug_tuple(stringify!($atomic))
+                        .field(&self.load())
+                        .finish()
+                }
+            }
+
+            impl<O: AtomicOrdering> Hash for $atomic<O> {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    self.load().hash(state)
+                }
+            }
+
+            impl<O: AtomicOrdering> PartialEq for $atomic<O> {
+                fn eq(&self, rhs: &Self) -> bool {
+                    self.load() == rhs.load()
+                }
+            }
+
+            impl<O: AtomicOrdering> Eq for $atomic<O> { }
+
+            impl<O: AtomicOrdering> Serialize for $atomic<O> {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where S: Serializer {
+                    self.load().serialize(serializer)
+                }
+            }
+
+            impl<'de, O: AtomicOrdering> Deserialize<'de> for $atomic<O> {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where D: Deserializer<'de> {
+                    <$prim>::deserialize(deserializer).map(Self::new)
+                }
+            }
+
+
+            impl<O: AtomicOrdering> Core for $atomic<O> {
+                type Delta = $delta;
+            }
+
+            impl<O: AtomicOrdering> Apply for $atomic<O> {
+                fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+                    Self::from_delta(delta)
+                }
+
+                fn validate_into(
+                    &self,
+                    delta: &Self::Delta,
+                    errors: &mut Vec<DeltaError>,
+                ) {
+                    Self::validate_from_delta(delta, errors)
+                }
+            }
+
+            impl<O: AtomicOrdering> Delta for $atomic<O> {
+                fn delta(&self, rhs: &Self) -> DeltaResult<Self::Delta> {
+                    Ok($delta(Some(rhs.load())))
+                }
+            }
+
+            impl<O: AtomicOrdering> FromDelta for $atomic<O> {
+                fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
+                    delta.0
+                        .map(Self::new)
+                        .ok_or_else(|| ExpectedValue!(stringify!($delta)))
+                }
+
+                fn validate_from_delta(
+                    delta: &Self::Delta,
+                    errors: &mut Vec<DeltaError>,
+                ) {
+                    if delta.0.is_none() {
+                        errors.push(ExpectedValue!(stringify!($delta)));
+                    }
+                }
+            }
+
+            impl<O: AtomicOrdering> IntoDelta for $atomic<O> {
+                fn into_delta(self) -> DeltaResult<Self::Delta> {
+                    Ok($delta(Some(self.load())))
+                }
+            }
+
+
+            #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+            pub struct $delta(#[doc(hidden)] pub Option<$prim>);
+
+            impl std::fmt::Debug for $delta {
+                fn fmt(&self, f: &mut std::fmt::Formatter)
+                       -> Result<(), std::fmt::Error>
+                {
+                    match self.0 {
+                        None =>
+                            write!(f, "{}(None)", stringify!($delta)),
+                        Some(prim) =>
+                            write!(f, "{}({:#?})", stringify!($delta), prim),
+                    }
+                }
+            }
+
+            impl ToDynDelta for $delta {
+                fn to_dyn_delta(&self) -> DynDelta {
+                    match &self.0 {
+                        None => DynDelta::Unchanged,
+                        Some(prim) => DynDelta::replace(prim),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_delta_trait_for_atomic_types! {
+    AtomicBool  => bool  => AtomicBoolDelta;
+
+    AtomicI8    => i8    => AtomicI8Delta;
+    AtomicI16   => i16   => AtomicI16Delta;
+    AtomicI32   => i32   => AtomicI32Delta;
+    AtomicI64   => i64   => AtomicI64Delta;
+    AtomicIsize => isize => AtomicIsizeDelta;
+
+    AtomicU8    => u8    => AtomicU8Delta;
+    AtomicU16   => u16   => AtomicU16Delta;
+    AtomicU32   => u32   => AtomicU32Delta;
+    AtomicU64   => u64   => AtomicU64Delta;
+    AtomicUsize => usize => AtomicUsizeDelta;
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use super::*;
+
+    #[test]
+    fn AtomicU64__delta__different_values() -> DeltaResult<()> {
+        let a0: AtomicU64 = AtomicU64::new(42);
+        let a1: AtomicU64 = AtomicU64::new(300);
+        let delta: <AtomicU64 as Core>::Delta = a0.delta(&a1)?;
+        let json_string = serde_json::to_string(&delta)
+            .expect("Could not serialize to json");
+        assert_eq!(json_string, "300");
+        let delta1: <AtomicU64 as Core>::Delta = serde_json::from_str(
+            &json_string
+        ).expect("Could not deserialize from json");
+        assert_eq!(delta, delta1);
+        Ok(())
+    }
+
+    #[test]
+    fn AtomicU64__apply__different_values() -> DeltaResult<()> {
+        let a0: AtomicU64<Relaxed> = AtomicU64::new(42);
+        let a1: AtomicU64<Relaxed> = AtomicU64::new(300);
+        let delta: <AtomicU64<Relaxed> as Core>::Delta = a0.delta(&a1)?;
+        let a2 = a0.apply(delta)?;
+        assert_eq!(a1, a2);
+        assert_eq!(a2.load(), 300);
+        Ok(())
+    }
+
+    #[test]
+    fn AtomicBool__apply__different_values() -> DeltaResult<()> {
+        let a0: AtomicBool<AcqRel> = AtomicBool::new(false);
+        let a1: AtomicBool<AcqRel> = AtomicBool::new(true);
+        let delta = a0.delta(&a1)?;
+        let a2 = a0.apply(delta)?;
+        assert_eq!(a1, a2);
+        Ok(())
+    }
+}
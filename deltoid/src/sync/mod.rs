@@ -1,5 +1,7 @@
 mod arc;
+mod atomic;
 mod rwlock;
 
 pub use arc::*;
+pub use atomic::*;
 pub use rwlock::*;
@@ -4,6 +4,7 @@
 //! [`RwLock`]: https://doc.rust-lang.org/std/sync/struct.RwLock.html
 
 use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{ApplyPolicy, DynDelta, ToDynDelta};
 use serde::{Serialize, Serializer, Deserialize, Deserializer};
 use serde::de::Visitor;
 use std::cmp::Ordering;
@@ -23,11 +24,11 @@ impl<T> RwLock<T> {
     pub fn into_inner(self) -> LockResult<T> { self.0.into_inner() }
 
     pub fn try_read(&self) -> DeltaResult<RwLockReadGuard<T>> {
-        self.0.try_read().map_err(DeltaError::from)
+        self.0.try_read().map_err(DeltaError::rwlock_read)
     }
 
     pub fn try_write(&self) -> DeltaResult<RwLockWriteGuard<T>> {
-        self.0.try_write().map_err(DeltaError::from)
+        self.0.try_write().map_err(DeltaError::rwlock_write)
     }
 }
 
@@ -121,12 +122,27 @@ where T: Clone + Debug + PartialEq + Apply
     + Serialize
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
         let lhs: &T = &*self.0.try_read().unwrap(/*TODO*/);
         match delta.0 {
-            Some(delta) => lhs.apply(delta).map(Self::new),
+            Some(delta) => lhs.apply_with(delta, policy).map(Self::new),
             None => Ok(Self::new(lhs.clone())),
         }
     }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if let Some(delta) = &delta.0 {
+            match self.try_read() {
+                Ok(lhs) => lhs.validate_into(delta, errors),
+                Err(error) => errors.push(error),
+            }
+        }
+    }
 }
 
 impl<T> Delta for RwLock<T>
@@ -150,6 +166,13 @@ where T: Clone + Debug + PartialEq + FromDelta
         let delta = delta.0.ok_or_else(|| ExpectedValue!("RwLockDelta<T>"))?;
         <T>::from_delta(delta).map(Self::new)
     }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        match &delta.0 {
+            Some(delta) => <T>::validate_from_delta(delta, errors),
+            None => errors.push(ExpectedValue!("RwLockDelta<T>")),
+        }
+    }
 }
 
 impl<T> IntoDelta for RwLock<T>
@@ -181,6 +204,13 @@ impl<T: Core> std::fmt::Debug for RwLockDelta<T> {
     }
 }
 
+impl<T: Core> ToDynDelta for RwLockDelta<T>
+where T::Delta: ToDynDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        self.0.to_dyn_delta()
+    }
+}
+
 
 
 
@@ -243,4 +273,24 @@ mod tests {
         assert_eq!(s1, s2);
         Ok(())
     }
+
+    #[test]
+    fn RwLock__try_read__poisoned() {
+        use crate::{LockAccess, LockError, LockErrorKind};
+        use std::error::Error;
+        let lock = std::sync::Arc::new(RwLock::new(42u8));
+        let lock1 = lock.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = lock1.try_write().unwrap();
+            panic!("poisoning the lock");
+        }).join();
+        let err: DeltaError = lock.try_read().unwrap_err();
+        let expected = LockError {
+            access: LockAccess::Read,
+            kind: LockErrorKind::Poisoned,
+        };
+        assert_eq!(err, DeltaError::RwLock(expected.clone()));
+        assert_eq!(err.to_string(), "failed to lock an RwLock for reading");
+        assert_eq!(err.source().unwrap().to_string(), expected.to_string());
+    }
 }
@@ -1,6 +1,8 @@
 //!
 
-use crate::{Apply, Core, Delta, DeltaResult, FromDelta, IntoDelta};
+use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{ApplyPolicy, PathSegment};
+use crate::{DynDelta, ToDynDelta};
 
 
 impl<T0> Core for (T0,)
@@ -15,12 +17,27 @@ impl<T0> Apply for (T0,)
 where T0: Apply,
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
         let field0: T0 = match delta.0 {
-            Some(d) => self.0.apply(d)?,
+            Some(d) => self.0.apply_with(d, policy)
+                .map_err(|e| e.at(PathSegment::Position(0)))?,
             None    => self.0.clone(),
         };
         Ok((field0,))
     }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if let Some(d) = &delta.0 {
+            let start: usize = errors.len();
+            self.0.validate_into(d, errors);
+            DeltaError::scope_all(errors, start, &PathSegment::Position(0));
+        }
+    }
 }
 
 impl<T0> Delta for (T0,)
@@ -40,7 +57,10 @@ where T0: FromDelta,
     fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
         Ok((
             delta.0.map(<T0>::from_delta)
-                .ok_or_else(|| ExpectedValue!("Option<<T0 as Core>::Delta>"))??,
+                .unwrap_or_else(|| {
+                    Err(ExpectedValue!("Option<<T0 as Core>::Delta>"))
+                })
+                .map_err(|e| e.at(PathSegment::Position(0)))?,
         ))
     }
 }
@@ -73,16 +93,37 @@ where T0: Apply,
       T1: Apply,
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
         let field0: T0 = match delta.0 {
-            Some(d) => self.0.apply(d)?,
+            Some(d) => self.0.apply_with(d, policy)
+                .map_err(|e| e.at(PathSegment::Position(0)))?,
             None    => self.0.clone(),
         };
         let field1: T1 = match delta.1 {
-            Some(d) => self.1.apply(d)?,
+            Some(d) => self.1.apply_with(d, policy)
+                .map_err(|e| e.at(PathSegment::Position(1)))?,
             None    => self.1.clone(),
         };
         Ok((field0, field1))
     }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if let Some(d) = &delta.0 {
+            let start: usize = errors.len();
+            self.0.validate_into(d, errors);
+            DeltaError::scope_all(errors, start, &PathSegment::Position(0));
+        }
+        if let Some(d) = &delta.1 {
+            let start: usize = errors.len();
+            self.1.validate_into(d, errors);
+            DeltaError::scope_all(errors, start, &PathSegment::Position(1));
+        }
+    }
 }
 
 impl<T0, T1> Delta for (T0, T1)
@@ -106,9 +147,15 @@ where T0: FromDelta,
     fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
         Ok((
             delta.0.map(<T0>::from_delta)
-                .ok_or_else(|| ExpectedValue!("Option<<T0 as Core>::Delta>"))??,
+                .unwrap_or_else(|| {
+                    Err(ExpectedValue!("Option<<T0 as Core>::Delta>"))
+                })
+                .map_err(|e| e.at(PathSegment::Position(0)))?,
             delta.1.map(<T1>::from_delta)
-                .ok_or_else(|| ExpectedValue!("Option<<T1 as Core>::Delta>"))??,
+                .unwrap_or_else(|| {
+                    Err(ExpectedValue!("Option<<T1 as Core>::Delta>"))
+                })
+                .map_err(|e| e.at(PathSegment::Position(1)))?,
         ))
     }
 }
@@ -146,20 +193,47 @@ where T0: Apply,
       T2: Apply,
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
         let field0: T0 = match delta.0 {
-            Some(d) => self.0.apply(d)?,
+            Some(d) => self.0.apply_with(d, policy)
+                .map_err(|e| e.at(PathSegment::Position(0)))?,
             None    => self.0.clone(),
         };
         let field1: T1 = match delta.1 {
-            Some(d) => self.1.apply(d)?,
+            Some(d) => self.1.apply_with(d, policy)
+                .map_err(|e| e.at(PathSegment::Position(1)))?,
             None    => self.1.clone(),
         };
         let field2: T2 = match delta.2 {
-            Some(d) => self.2.apply(d)?,
+            Some(d) => self.2.apply_with(d, policy)
+                .map_err(|e| e.at(PathSegment::Position(2)))?,
             None    => self.2.clone(),
         };
         Ok((field0, field1, field2))
     }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if let Some(d) = &delta.0 {
+            let start: usize = errors.len();
+            self.0.validate_into(d, errors);
+            DeltaError::scope_all(errors, start, &PathSegment::Position(0));
+        }
+        if let Some(d) = &delta.1 {
+            let start: usize = errors.len();
+            self.1.validate_into(d, errors);
+            DeltaError::scope_all(errors, start, &PathSegment::Position(1));
+        }
+        if let Some(d) = &delta.2 {
+            let start: usize = errors.len();
+            self.2.validate_into(d, errors);
+            DeltaError::scope_all(errors, start, &PathSegment::Position(2));
+        }
+    }
 }
 
 impl<T0, T1, T2> Delta for (T0, T1, T2)
@@ -187,11 +261,20 @@ where T0: FromDelta,
     fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
         Ok((
             delta.0.map(<T0>::from_delta)
-                .ok_or_else(|| ExpectedValue!("Option<<T0 as Core>::Delta>"))??,
+                .unwrap_or_else(|| {
+                    Err(ExpectedValue!("Option<<T0 as Core>::Delta>"))
+                })
+                .map_err(|e| e.at(PathSegment::Position(0)))?,
             delta.1.map(<T1>::from_delta)
-                .ok_or_else(|| ExpectedValue!("Option<<T1 as Core>::Delta>"))??,
+                .unwrap_or_else(|| {
+                    Err(ExpectedValue!("Option<<T1 as Core>::Delta>"))
+                })
+                .map_err(|e| e.at(PathSegment::Position(1)))?,
             delta.2.map(<T2>::from_delta)
-                .ok_or_else(|| ExpectedValue!("Option<<T2 as Core>::Delta>"))??,
+                .unwrap_or_else(|| {
+                    Err(ExpectedValue!("Option<<T2 as Core>::Delta>"))
+                })
+                .map_err(|e| e.at(PathSegment::Position(2)))?,
         ))
     }
 }
@@ -234,24 +317,57 @@ where T0: Apply,
       T3: Apply,
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
         let field0: T0 = match delta.0 {
-            Some(d) => self.0.apply(d)?,
+            Some(d) => self.0.apply_with(d, policy)
+                .map_err(|e| e.at(PathSegment::Position(0)))?,
             None    => self.0.clone(),
         };
         let field1: T1 = match delta.1 {
-            Some(d) => self.1.apply(d)?,
+            Some(d) => self.1.apply_with(d, policy)
+                .map_err(|e| e.at(PathSegment::Position(1)))?,
             None    => self.1.clone(),
         };
         let field2: T2 = match delta.2 {
-            Some(d) => self.2.apply(d)?,
+            Some(d) => self.2.apply_with(d, policy)
+                .map_err(|e| e.at(PathSegment::Position(2)))?,
             None    => self.2.clone(),
         };
         let field3: T3 = match delta.3 {
-            Some(d) => self.3.apply(d)?,
+            Some(d) => self.3.apply_with(d, policy)
+                .map_err(|e| e.at(PathSegment::Position(3)))?,
             None    => self.3.clone(),
         };
         Ok((field0, field1, field2, field3))
     }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if let Some(d) = &delta.0 {
+            let start: usize = errors.len();
+            self.0.validate_into(d, errors);
+            DeltaError::scope_all(errors, start, &PathSegment::Position(0));
+        }
+        if let Some(d) = &delta.1 {
+            let start: usize = errors.len();
+            self.1.validate_into(d, errors);
+            DeltaError::scope_all(errors, start, &PathSegment::Position(1));
+        }
+        if let Some(d) = &delta.2 {
+            let start: usize = errors.len();
+            self.2.validate_into(d, errors);
+            DeltaError::scope_all(errors, start, &PathSegment::Position(2));
+        }
+        if let Some(d) = &delta.3 {
+            let start: usize = errors.len();
+            self.3.validate_into(d, errors);
+            DeltaError::scope_all(errors, start, &PathSegment::Position(3));
+        }
+    }
 }
 
 impl<T0, T1, T2, T3> Delta for (T0, T1, T2, T3)
@@ -283,13 +399,25 @@ where T0: FromDelta,
     fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
         Ok((
             delta.0.map(<T0>::from_delta)
-                .ok_or_else(|| ExpectedValue!("Option<<T0 as Core>::Delta>"))??,
+                .unwrap_or_else(|| {
+                    Err(ExpectedValue!("Option<<T0 as Core>::Delta>"))
+                })
+                .map_err(|e| e.at(PathSegment::Position(0)))?,
             delta.1.map(<T1>::from_delta)
-                .ok_or_else(|| ExpectedValue!("Option<<T1 as Core>::Delta>"))??,
+                .unwrap_or_else(|| {
+                    Err(ExpectedValue!("Option<<T1 as Core>::Delta>"))
+                })
+                .map_err(|e| e.at(PathSegment::Position(1)))?,
             delta.2.map(<T2>::from_delta)
-                .ok_or_else(|| ExpectedValue!("Option<<T2 as Core>::Delta>"))??,
+                .unwrap_or_else(|| {
+                    Err(ExpectedValue!("Option<<T2 as Core>::Delta>"))
+                })
+                .map_err(|e| e.at(PathSegment::Position(2)))?,
             delta.3.map(<T3>::from_delta)
-                .ok_or_else(|| ExpectedValue!("Option<<T3 as Core>::Delta>"))??,
+                .unwrap_or_else(|| {
+                    Err(ExpectedValue!("Option<<T3 as Core>::Delta>"))
+                })
+                .map_err(|e| e.at(PathSegment::Position(3)))?,
         ))
     }
 }
@@ -338,28 +466,67 @@ where T0: Apply,
       T4: Apply,
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
         let field0: T0 = match delta.0 {
-            Some(d) => self.0.apply(d)?,
+            Some(d) => self.0.apply_with(d, policy)
+                .map_err(|e| e.at(PathSegment::Position(0)))?,
             None    => self.0.clone(),
         };
         let field1: T1 = match delta.1 {
-            Some(d) => self.1.apply(d)?,
+            Some(d) => self.1.apply_with(d, policy)
+                .map_err(|e| e.at(PathSegment::Position(1)))?,
             None    => self.1.clone(),
         };
         let field2: T2 = match delta.2 {
-            Some(d) => self.2.apply(d)?,
+            Some(d) => self.2.apply_with(d, policy)
+                .map_err(|e| e.at(PathSegment::Position(2)))?,
             None    => self.2.clone(),
         };
         let field3: T3 = match delta.3 {
-            Some(d) => self.3.apply(d)?,
+            Some(d) => self.3.apply_with(d, policy)
+                .map_err(|e| e.at(PathSegment::Position(3)))?,
             None    => self.3.clone(),
         };
         let field4: T4 = match delta.4 {
-            Some(d) => self.4.apply(d)?,
+            Some(d) => self.4.apply_with(d, policy)
+                .map_err(|e| e.at(PathSegment::Position(4)))?,
             None    => self.4.clone(),
         };
         Ok((field0, field1, field2, field3, field4))
     }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        if let Some(d) = &delta.0 {
+            let start: usize = errors.len();
+            self.0.validate_into(d, errors);
+            DeltaError::scope_all(errors, start, &PathSegment::Position(0));
+        }
+        if let Some(d) = &delta.1 {
+            let start: usize = errors.len();
+            self.1.validate_into(d, errors);
+            DeltaError::scope_all(errors, start, &PathSegment::Position(1));
+        }
+        if let Some(d) = &delta.2 {
+            let start: usize = errors.len();
+            self.2.validate_into(d, errors);
+            DeltaError::scope_all(errors, start, &PathSegment::Position(2));
+        }
+        if let Some(d) = &delta.3 {
+            let start: usize = errors.len();
+            self.3.validate_into(d, errors);
+            DeltaError::scope_all(errors, start, &PathSegment::Position(3));
+        }
+        if let Some(d) = &delta.4 {
+            let start: usize = errors.len();
+            self.4.validate_into(d, errors);
+            DeltaError::scope_all(errors, start, &PathSegment::Position(4));
+        }
+    }
 }
 
 impl<T0, T1, T2, T3, T4> Delta for (T0, T1, T2, T3, T4)
@@ -395,15 +562,30 @@ where T0: FromDelta,
     fn from_delta(delta: Self::Delta) -> DeltaResult<Self> {
         Ok((
             delta.0.map(<T0>::from_delta)
-                .ok_or_else(|| ExpectedValue!("Option<<T0 as Core>::Delta>"))??,
+                .unwrap_or_else(|| {
+                    Err(ExpectedValue!("Option<<T0 as Core>::Delta>"))
+                })
+                .map_err(|e| e.at(PathSegment::Position(0)))?,
             delta.1.map(<T1>::from_delta)
-                .ok_or_else(|| ExpectedValue!("Option<<T1 as Core>::Delta>"))??,
+                .unwrap_or_else(|| {
+                    Err(ExpectedValue!("Option<<T1 as Core>::Delta>"))
+                })
+                .map_err(|e| e.at(PathSegment::Position(1)))?,
             delta.2.map(<T2>::from_delta)
-                .ok_or_else(|| ExpectedValue!("Option<<T2 as Core>::Delta>"))??,
+                .unwrap_or_else(|| {
+                    Err(ExpectedValue!("Option<<T2 as Core>::Delta>"))
+                })
+                .map_err(|e| e.at(PathSegment::Position(2)))?,
             delta.3.map(<T3>::from_delta)
-                .ok_or_else(|| ExpectedValue!("Option<<T3 as Core>::Delta>"))??,
+                .unwrap_or_else(|| {
+                    Err(ExpectedValue!("Option<<T3 as Core>::Delta>"))
+                })
+                .map_err(|e| e.at(PathSegment::Position(3)))?,
             delta.4.map(<T4>::from_delta)
-                .ok_or_else(|| ExpectedValue!("Option<<T4 as Core>::Delta>"))??,
+                .unwrap_or_else(|| {
+                    Err(ExpectedValue!("Option<<T4 as Core>::Delta>"))
+                })
+                .map_err(|e| e.at(PathSegment::Position(4)))?,
         ))
     }
 }
@@ -428,6 +610,30 @@ where T0: IntoDelta,
 
 
 
+macro_rules! impl_to_dyn_delta_for_tuple_deltas {
+    ( $( ($($D:ident : $idx:tt),+) );* $(;)? ) => {
+        $(
+            impl<$($D: ToDynDelta),+> ToDynDelta for ($(Option<$D>,)+) {
+                fn to_dyn_delta(&self) -> DynDelta {
+                    DynDelta::changes(vec![
+                        $( DynDelta::field($idx, self.$idx.to_dyn_delta()), )+
+                    ])
+                }
+            }
+        )*
+    };
+}
+
+impl_to_dyn_delta_for_tuple_deltas! {
+    (D0: 0);
+    (D0: 0, D1: 1);
+    (D0: 0, D1: 1, D2: 2);
+    (D0: 0, D1: 1, D2: 2, D3: 3);
+    (D0: 0, D1: 1, D2: 2, D3: 3, D4: 4);
+}
+
+
+
 #[allow(non_snake_case)]
 #[cfg(test)]
 mod tests {
@@ -1,6 +1,8 @@
 //!
 
 use crate::{Apply, Core, Delta, DeltaError, DeltaResult, FromDelta, IntoDelta};
+use crate::{ApplyPolicy, OnFailure, PathSegment};
+use crate::{DynDelta, ToDynDelta};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
@@ -24,21 +26,25 @@ where T: Clone + Debug + PartialEq + Apply + FromDelta
     + Serialize
 {
     fn apply(&self, delta: Self::Delta) -> DeltaResult<Self> {
-        let mut new: Self = self.clone();
-        for change in delta.into_iter() { match change {
-            EltDelta::Edit { index, item } => {
-                // NOTE: If self.len() == 0, the Edit should have been an Add:
-                ensure_gt![self.len(), 0]?;
-                // NOTE: Ensure index is not out of bounds:
-                ensure_lt![index, self.len()]?;
-                new[index] = self[index].apply(item)?;
-            },
-            EltDelta::Add(delta) =>  new.push(<T>::from_delta(delta)?),
-            EltDelta::Remove { count } =>  for _ in 0 .. count {
-                new.pop().ok_or_else(|| ExpectedValue!("VecDelta<T>"))?;
-            },
-        }}
-        Ok(new)
+        self.apply_with(delta, &ApplyPolicy::default())
+    }
+
+    fn apply_with(&self, delta: Self::Delta, policy: &ApplyPolicy)
+                  -> DeltaResult<Self>
+    {
+        delta.apply_to(
+            self,
+            policy,
+            |vec| vec.len(),
+            |vec, index| &vec[index],
+            |vec, index, item| vec[index] = item,
+            |vec, item| { vec.push(item); Ok(()) },
+            |vec| vec.pop().is_some(),
+        )
+    }
+
+    fn validate_into(&self, delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        delta.validate_into(self.len(), |index| &self[index], errors);
     }
 }
 
@@ -76,12 +82,19 @@ where T: Clone + Debug + PartialEq + FromDelta
         let mut vec: Vec<T> = vec![];
         for (index, element) in delta.0.into_iter().enumerate() {
             match element {
-                EltDelta::Add(elt) => vec.push(<T>::from_delta(elt)?),
+                EltDelta::Add(elt) => vec.push(
+                    <T>::from_delta(elt)
+                        .map_err(|e| e.at(PathSegment::Index(index)))?
+                ),
                 _ => return Err(DeltaError::IllegalDelta { index })?,
             }
         }
         Ok(vec)
     }
+
+    fn validate_from_delta(delta: &Self::Delta, errors: &mut Vec<DeltaError>) {
+        delta.validate_from_delta(errors);
+    }
 }
 
 impl<T> IntoDelta for Vec<T>
@@ -98,6 +111,127 @@ where T: Clone + Debug + PartialEq + IntoDelta
     }
 }
 
+impl<T: Apply + FromDelta> VecDelta<T> {
+    /// Apply `self` with `policy` to `elts`, a `Vec<T>` or another sequence
+    /// that uses a `VecDelta<T>`.  The new sequence starts out as a clone of
+    /// `elts`.  Both are accessed with `len` and `get`, and the new one is
+    /// modified with `set`, `push` and `pop`, the last of which returns
+    /// whether there was an element to remove.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn apply_to<S: Clone>(
+        self,
+        elts: &S,
+        policy: &ApplyPolicy,
+        len: impl Fn(&S) -> usize,
+        get: impl Fn(&S, usize) -> &T,
+        set: impl Fn(&mut S, usize, T),
+        push: impl Fn(&mut S, T) -> DeltaResult<()>,
+        pop: impl Fn(&mut S) -> bool,
+    ) -> DeltaResult<S> {
+        let mut new: S = elts.clone();
+        for change in self.into_iter() { match change {
+            EltDelta::Edit { index, item } => {
+                let at = |e: DeltaError| e.at(PathSegment::Index(index));
+                let (elts_len, new_len) = (len(elts), len(&new));
+                if index < usize::min(elts_len, new_len) {
+                    let item: T = get(elts, index).apply_with(item, policy)
+                        .map_err(at)?;
+                    set(&mut new, index, item);
+                    continue;
+                }
+                match policy.out_of_range_edit {
+                    OnFailure::Error => {
+                        // NOTE: If elts_len == 0, the Edit should
+                        //       have been an Add:
+                        ensure_gt![elts_len, 0].map_err(at)?;
+                        // NOTE: Ensure index is within bounds:
+                        ensure_lt![index, usize::min(elts_len, new_len)]
+                            .map_err(at)?;
+                    },
+                    OnFailure::Skip => {},
+                    OnFailure::Upsert if index < new_len => {
+                        let item: T = get(&new, index).apply_with(item, policy)
+                            .map_err(at)?;
+                        set(&mut new, index, item);
+                    },
+                    OnFailure::Upsert => {
+                        // NOTE: Upserting past the end would leave a gap:
+                        ensure_eq![index, new_len].map_err(at)?;
+                        push(&mut new, <T>::from_delta(item).map_err(at)?)?;
+                    },
+                }
+            },
+            EltDelta::Add(delta) => {
+                let index = len(&new);
+                let at = |e: DeltaError| e.at(PathSegment::Index(index));
+                push(&mut new, <T>::from_delta(delta).map_err(at)?)?;
+            },
+            EltDelta::Remove { count } => for _ in 0 .. count {
+                // NOTE: A Remove that runs out of elements is an error of
+                //       the sequence itself, as there's no element to blame:
+                if !pop(&mut new) {
+                    return Err(ExpectedValue!("VecDelta<T>"));
+                }
+            },
+        }}
+        Ok(new)
+    }
+
+    /// Push every problem with applying `self` to a `Vec<T>`, or to another
+    /// sequence that uses a `VecDelta<T>`, onto `errors`.  The sequence has
+    /// `elts_len` elements, and `elt(index)` returns the element at `index`.
+    pub(crate) fn validate_into<'e>(
+        &self,
+        elts_len: usize,
+        elt: impl Fn(usize) -> &'e T,
+        errors: &mut Vec<DeltaError>,
+    ) where T: 'e {
+        let mut len: usize = elts_len;
+        for change in self.iter() { match change {
+            EltDelta::Edit { index, item } => {
+                // NOTE: `apply()` edits the original element at `index`,
+                //       which must also still be present at that point:
+                let start: usize = errors.len();
+                match ensure_lt![*index, usize::min(elts_len, len)] {
+                    Ok(()) => elt(*index).validate_into(item, errors),
+                    Err(error) => errors.push(error),
+                }
+                let segment = PathSegment::Index(*index);
+                DeltaError::scope_all(errors, start, &segment);
+            },
+            EltDelta::Add(item) => {
+                let start: usize = errors.len();
+                <T>::validate_from_delta(item, errors);
+                let segment = PathSegment::Index(len);
+                DeltaError::scope_all(errors, start, &segment);
+                len += 1;
+            },
+            EltDelta::Remove { count } => {
+                if *count > len {
+                    errors.push(ExpectedValue!("VecDelta<T>"));
+                }
+                len = len.saturating_sub(*count);
+            },
+        }}
+    }
+}
+
+impl<T: FromDelta> VecDelta<T> {
+    /// Push every problem with converting `self` to a `Vec<T>`, or to another
+    /// sequence that uses a `VecDelta<T>`, onto `errors`.
+    pub(crate) fn validate_from_delta(&self, errors: &mut Vec<DeltaError>) {
+        for (index, element) in self.iter().enumerate() { match element {
+            EltDelta::Add(elt) => {
+                let start: usize = errors.len();
+                <T>::validate_from_delta(elt, errors);
+                let segment = PathSegment::Index(index);
+                DeltaError::scope_all(errors, start, &segment);
+            },
+            _ => errors.push(DeltaError::IllegalDelta { index }),
+        }}
+    }
+}
+
 
 
 #[derive(Clone, PartialEq)]
@@ -131,6 +265,18 @@ impl<T: Core> std::fmt::Debug for EltDelta<T> {
     }
 }
 
+impl<T: Core> ToDynDelta for EltDelta<T>
+where T::Delta: ToDynDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        match &self {
+            Self::Edit { index, item } =>
+                DynDelta::index(*index, item.to_dyn_delta()),
+            Self::Remove { count } => DynDelta::Truncate { count: *count },
+            Self::Add(delta) => DynDelta::Push(Box::new(delta.to_dyn_delta())),
+        }
+    }
+}
+
 
 #[derive(Clone, PartialEq)]
 #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
@@ -159,6 +305,13 @@ impl<T: Core> std::fmt::Debug for VecDelta<T> {
     }
 }
 
+impl<T: Core> ToDynDelta for VecDelta<T>
+where T::Delta: ToDynDelta {
+    fn to_dyn_delta(&self) -> DynDelta {
+        DynDelta::changes(self.0.iter().map(ToDynDelta::to_dyn_delta).collect())
+    }
+}
+
 
 
 
@@ -262,4 +415,21 @@ mod tests {
         Ok(())
     }
 
+    #[allow(non_snake_case)]
+    #[test]
+    fn Vec__validate() -> DeltaResult<()> {
+        let vec0 = vec![1, 3, 10];
+        let vec1 = vec![1, 4];
+        let delta = vec0.delta(&vec1)?;
+        assert_eq!(vec0.validate(&delta), Ok(()));
+        let delta = VecDelta(vec![
+            EltDelta::Edit { index: 5, item: 7i32.into_delta()? },
+            EltDelta::Remove { count: 4 },
+        ]);
+        let errors = vec0.validate(&delta).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(vec0.apply(delta).is_err());
+        Ok(())
+    }
+
 }
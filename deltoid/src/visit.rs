@@ -0,0 +1,425 @@
+//! Walking the leaf changes of a delta, together with their paths.
+//!
+//! This is built on top of [`DynDelta`], so every type that implements
+//! [`ToDynDelta`], including derived types, can be walked:
+//!
+//! ```
+//! use deltoid::{ChangeKind, Delta, DeltaResult, PathSegment, Walk};
+//! use deltoid::visit::{format_path, walk_against};
+//!
+//! # fn main() -> DeltaResult<()> {
+//! let v0: Vec<(u8, bool)> = vec![(1, true)];
+//! let v1: Vec<(u8, bool)> = vec![(1, false)];
+//! let mut changes: Vec<String> = vec![];
+//! v0.delta(&v1)?.walk(&mut |path: &[PathSegment], change: ChangeKind| {
+//!     changes.push(format!("{} {}", format_path(path), change));
+//! });
+//! assert_eq!(changes, vec!["[0].1 changed to false"]);
+//!
+//! // NOTE: Walking the delta against `v0` also yields the replaced values:
+//! changes.clear();
+//! let delta = v0.delta(&v1)?;
+//! walk_against(&v0, &delta, &mut |path: &[PathSegment], change: ChangeKind| {
+//!     changes.push(format!("{} {}", format_path(path), change));
+//! })?;
+//! assert_eq!(changes, vec!["[0].1 changed from true to false"]);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::IntoDelta;
+use crate::dyn_delta::{DynDelta, ToDynDelta};
+use crate::error::DeltaResult;
+use std::fmt;
+
+
+/// A single step in the path from the root of a value to a part of it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+pub enum PathSegment {
+    /// A named field of a struct or enum variant.
+    Field(String),
+    /// A positional field of a tuple, tuple struct or tuple variant.
+    Position(usize),
+    /// An element of a sequence.
+    Index(usize),
+    /// An entry of a map, identified by the `Debug` representation of its key.
+    Key(String),
+    /// An enum variant.
+    Variant(String),
+}
+
+impl PathSegment {
+    /// A `Key` segment for the map entry with `key`.
+    pub fn key<K: fmt::Debug + ?Sized>(key: &K) -> Self {
+        Self::Key(format!("{:?}", key))
+    }
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Field(name)     => write!(f, "{}", name),
+            Self::Position(pos)   => write!(f, "{}", pos),
+            Self::Index(index)    => write!(f, "[{}]", index),
+            Self::Key(key)        => write!(f, "[{}]", key),
+            Self::Variant(name)   => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Format a `path` as e.g. `user.addresses[0].city` or `scores["alice"]`.
+pub fn format_path(path: &[PathSegment]) -> String {
+    let mut buf = String::new();
+    for segment in path {
+        match segment {
+            PathSegment::Index(_) | PathSegment::Key(_) => {},
+            _ if buf.is_empty() => {},
+            _ => buf.push('.'),
+        }
+        buf.push_str(&segment.to_string());
+    }
+    buf
+}
+
+/// Format `value`, the `DynDelta` of a delta that builds a value from
+/// scratch, e.g. that of an appended element, much like the `Debug` impl
+/// of the value would, e.g. `(1, true)`, `[1, 2]` or `Some(1)`.  The names
+/// of structs aren't part of a `DynDelta`, so a struct is formatted as e.g.
+/// `{ x: 1, y: 2 }`.
+pub fn format_value(value: &DynDelta) -> String {
+    match value {
+        DynDelta::Unchanged => "()".to_string(),
+        DynDelta::Replace(value) => value.clone(),
+        DynDelta::Variant { name, delta } => match &**delta {
+            DynDelta::Unchanged => name.clone(),
+            DynDelta::Replace(value) => format!("{}({})", name, value),
+            delta => match format_value(delta) {
+                // NOTE: A `DynDelta` doesn't tell the fields of a tuple
+                //       variant apart from a tuple in a 1-tuple variant:
+                fields if fields.starts_with('(') =>
+                    format!("{}{}", name, fields),
+                fields if fields.starts_with("{ ") =>
+                    format!("{} {}", name, fields),
+                value => format!("{}({})", name, value),
+            },
+        },
+        DynDelta::Changes(parts) => format_parts(parts),
+        part => format_parts(std::slice::from_ref(part)),
+    }
+}
+
+/// Format the `parts` of a value, e.g. the fields of a struct or the
+/// elements of a sequence, according to the kind of the first part.
+fn format_parts(parts: &[DynDelta]) -> String {
+    let values = || parts.iter().map(|part| match part {
+        DynDelta::Field { delta, .. }
+            | DynDelta::Index { delta, .. }
+            | DynDelta::Push(delta)
+            | DynDelta::SetAdd(delta) => format_value(delta),
+        DynDelta::Key { key, delta } | DynDelta::Insert { key, delta } =>
+            format!("{}: {}", key, format_value(delta)),
+        part => format_value(part),
+    });
+    let join = |values: Vec<String>| values.join(", ");
+    match parts.first() {
+        Some(DynDelta::Field { name, .. }) if name.parse::<usize>().is_ok() =>
+            format!("({})", join(values().collect())),
+        Some(DynDelta::Field { .. }) => {
+            let fields: Vec<String> = parts.iter()
+                .zip(values())
+                .map(|(part, value)| match part {
+                    DynDelta::Field { name, .. } => format!("{}: {}", name, value),
+                    _ => value,
+                })
+                .collect();
+            format!("{{ {} }}", join(fields))
+        },
+        Some(DynDelta::Index { .. }) | Some(DynDelta::Push(_)) =>
+            format!("[{}]", join(values().collect())),
+        Some(DynDelta::Key { .. })
+            | Some(DynDelta::Insert { .. })
+            | Some(DynDelta::SetAdd(_)) =>
+            format!("{{{}}}", join(values().collect())),
+        _ => join(values().collect()),
+    }
+}
+
+
+/// A leaf change to the part of a value at some path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChangeKind<'d> {
+    /// The part is replaced by the value with the `Debug` representation
+    /// `to`.  `from` is that of the value it replaces, if the delta was
+    /// walked against that value with [`walk_against`].
+    Replaced { from: Option<&'d str>, to: &'d str },
+    /// The map entry is added, with the value that the `DynDelta` describes.
+    Inserted(&'d DynDelta),
+    /// The part, e.g. a map entry, is removed.
+    Removed,
+    /// An element is appended to the sequence.
+    Appended(&'d DynDelta),
+    /// `count` elements are removed from the end of the sequence.
+    Truncated { count: usize },
+    /// An item is added to the set.
+    AddedToSet(&'d DynDelta),
+    /// An item is removed from the set.
+    RemovedFromSet(&'d DynDelta),
+    /// The part is, or becomes, this enum variant without any other changes.
+    /// Deltas don't record whether the variant itself changed.
+    Variant(&'d str),
+}
+
+/// Formats the change for humans, e.g. `changed from 1 to 2` or
+/// `appended (3, true)`, using [`format_value`] for the values.
+impl<'d> fmt::Display for ChangeKind<'d> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Replaced { from: Some(from), to } =>
+                write!(f, "changed from {} to {}", from, to),
+            Self::Replaced { from: None, to } =>
+                write!(f, "changed to {}", to),
+            Self::Inserted(value) =>
+                write!(f, "inserted {}", format_value(value)),
+            Self::Removed => write!(f, "removed"),
+            Self::Appended(item) =>
+                write!(f, "appended {}", format_value(item)),
+            Self::Truncated { count } => write!(f, "truncated by {}", count),
+            Self::AddedToSet(item) =>
+                write!(f, "added {}", format_value(item)),
+            Self::RemovedFromSet(item) =>
+                write!(f, "removed {}", format_value(item)),
+            Self::Variant(name) => write!(f, "set to variant {}", name),
+        }
+    }
+}
+
+
+/// Receives every leaf change of a delta, along with its path.
+pub trait DeltaVisitor {
+    fn visit(&mut self, path: &[PathSegment], change: ChangeKind);
+}
+
+impl<F> DeltaVisitor for F
+where F: FnMut(&[PathSegment], ChangeKind) {
+    fn visit(&mut self, path: &[PathSegment], change: ChangeKind) {
+        self(path, change)
+    }
+}
+
+
+impl DynDelta {
+    /// Walk the leaf changes of `self` in order, passing each to `visitor`.
+    pub fn walk<V: DeltaVisitor + ?Sized>(&self, visitor: &mut V) {
+        self.walk_from(None, &mut vec![], visitor)
+    }
+
+    /// Like `walk()`, but `self` is a delta of the value that `old`
+    /// builds from scratch, i.e. the `DynDelta` of its `IntoDelta` delta,
+    /// and each `Replaced` change carries the part of `old` it replaces.
+    pub fn walk_against<V>(&self, old: &DynDelta, visitor: &mut V)
+    where V: DeltaVisitor + ?Sized {
+        self.walk_from(Some(old), &mut vec![], visitor)
+    }
+
+    fn walk_from<V>(
+        &self,
+        old: Option<&DynDelta>,
+        path: &mut Vec<PathSegment>,
+        visitor: &mut V,
+    ) where V: DeltaVisitor + ?Sized {
+        let mut descend = |segment: PathSegment, delta: &DynDelta| {
+            let old: Option<&DynDelta> = old.and_then(|old| old.part(&segment));
+            path.push(segment);
+            delta.walk_from(old, path, visitor);
+            path.pop();
+        };
+        match self {
+            DynDelta::Unchanged => {},
+            DynDelta::Field { name, delta } => match name.parse::<usize>() {
+                Ok(pos) => descend(PathSegment::Position(pos), delta),
+                Err(_) => descend(PathSegment::Field(name.clone()), delta),
+            },
+            DynDelta::Index { index, delta } =>
+                descend(PathSegment::Index(*index), delta),
+            DynDelta::Key { key, delta } =>
+                descend(PathSegment::Key(key.clone()), delta),
+            DynDelta::Insert { key, delta } => {
+                path.push(PathSegment::Key(key.clone()));
+                visitor.visit(path, ChangeKind::Inserted(delta));
+                path.pop();
+            },
+            DynDelta::Variant { name, delta } if delta.is_unchanged() =>
+                visitor.visit(path, ChangeKind::Variant(name)),
+            DynDelta::Variant { name, delta } =>
+                descend(PathSegment::Variant(name.clone()), delta),
+            DynDelta::Changes(changes) => for change in changes {
+                change.walk_from(old, path, visitor);
+            },
+            DynDelta::Replace(value) => visitor.visit(path, ChangeKind::Replaced {
+                from: match old {
+                    Some(DynDelta::Replace(old)) => Some(old),
+                    _ => None,
+                },
+                to: value,
+            }),
+            DynDelta::Remove => visitor.visit(path, ChangeKind::Removed),
+            DynDelta::Push(item) =>
+                visitor.visit(path, ChangeKind::Appended(item)),
+            DynDelta::Truncate { count } =>
+                visitor.visit(path, ChangeKind::Truncated { count: *count }),
+            DynDelta::SetAdd(item) =>
+                visitor.visit(path, ChangeKind::AddedToSet(item)),
+            DynDelta::SetRemove(item) =>
+                visitor.visit(path, ChangeKind::RemovedFromSet(item)),
+        }
+    }
+
+    /// The part at `segment` of the value that `self` builds from scratch.
+    fn part(&self, segment: &PathSegment) -> Option<&DynDelta> {
+        let parts: &[DynDelta] = match self {
+            DynDelta::Changes(parts) => parts,
+            DynDelta::Variant { name, delta } => return match segment {
+                PathSegment::Variant(variant) if variant == name => Some(delta),
+                _ => None,
+            },
+            part => std::slice::from_ref(part),
+        };
+        let mut elements = parts.iter().filter_map(|part| match part {
+            DynDelta::Push(delta) => Some(delta),
+            _ => None,
+        });
+        match segment {
+            PathSegment::Index(index) => parts.iter()
+                .find_map(|part| match part {
+                    DynDelta::Index { index: i, delta } if i == index =>
+                        Some(&**delta),
+                    _ => None,
+                })
+                .or_else(|| elements.nth(*index).map(|delta| &**delta)),
+            _ => parts.iter().find_map(|part| match (part, segment) {
+                (DynDelta::Field { name, delta }, PathSegment::Field(field))
+                    if name == field => Some(&**delta),
+                (DynDelta::Field { name, delta }, PathSegment::Position(pos))
+                    if *name == pos.to_string() => Some(&**delta),
+                (DynDelta::Key { key, delta }, PathSegment::Key(k))
+                    | (DynDelta::Insert { key, delta }, PathSegment::Key(k))
+                    if key == k => Some(&**delta),
+                _ => None,
+            }),
+        }
+    }
+}
+
+/// Walk the leaf changes of any delta.
+pub trait Walk {
+    fn walk<V: DeltaVisitor + ?Sized>(&self, visitor: &mut V);
+}
+
+impl<D: ToDynDelta> Walk for D {
+    fn walk<V: DeltaVisitor + ?Sized>(&self, visitor: &mut V) {
+        self.to_dyn_delta().walk(visitor)
+    }
+}
+
+/// Walk the leaf changes of `delta`, a delta of `old`, such that each
+/// `Replaced` change carries the part of `old` that it replaces.
+pub fn walk_against<T, V>(old: &T, delta: &T::Delta, visitor: &mut V)
+                          -> DeltaResult<()>
+where T: Clone + IntoDelta,
+      T::Delta: ToDynDelta,
+      V: DeltaVisitor + ?Sized,
+{
+    let old: DynDelta = old.clone().into_delta()?.to_dyn_delta();
+    delta.to_dyn_delta().walk_against(&old, visitor);
+    Ok(())
+}
+
+
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use crate::{Delta, DeltaResult};
+    use std::collections::BTreeMap;
+    use super::*;
+
+    fn changes<D: ToDynDelta>(delta: &D) -> Vec<String> {
+        let mut changes: Vec<String> = vec![];
+        Walk::walk(delta, &mut |path: &[PathSegment], change: ChangeKind| {
+            changes.push(format!("{}: {}", format_path(path), change));
+        });
+        changes
+    }
+
+    #[test]
+    fn format_path__mixed_segments() {
+        let path = vec![
+            PathSegment::Field("user".into()),
+            PathSegment::Key("\"home\"".into()),
+            PathSegment::Field("addresses".into()),
+            PathSegment::Index(2),
+            PathSegment::Position(0),
+        ];
+        assert_eq!(format_path(&path), "user[\"home\"].addresses[2].0");
+        assert_eq!(format_path(&[]), "");
+    }
+
+    #[test]
+    fn BTreeMap__walk() -> DeltaResult<()> {
+        let mut map0: BTreeMap<String, (u8, Vec<u8>)> = BTreeMap::new();
+        map0.insert("alice".into(), (1, vec![1, 2]));
+        map0.insert("bob".into(), (2, vec![]));
+        let mut map1 = map0.clone();
+        map1.insert("alice".into(), (1, vec![1, 3, 4]));
+        map1.remove("bob");
+        let delta = map0.delta(&map1)?;
+        assert_eq!(changes(&delta), vec![
+            "[\"alice\"].1[1]: changed to 3",
+            "[\"alice\"].1: appended 4",
+            "[\"bob\"]: removed",
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn BTreeMap__walk_against() -> DeltaResult<()> {
+        let mut map0: BTreeMap<String, (u8, Vec<u8>)> = BTreeMap::new();
+        map0.insert("alice".into(), (1, vec![1, 2]));
+        let mut map1 = map0.clone();
+        map1.insert("alice".into(), (2, vec![1, 3, 4]));
+        map1.insert("bob".into(), (3, vec![5]));
+        let delta = map0.delta(&map1)?;
+        let mut changes: Vec<String> = vec![];
+        walk_against(&map0, &delta, &mut |path: &[PathSegment], change: ChangeKind| {
+            changes.push(format!("{}: {}", format_path(path), change));
+        })?;
+        assert_eq!(changes, vec![
+            "[\"alice\"].0: changed from 1 to 2",
+            "[\"alice\"].1[1]: changed from 2 to 3",
+            "[\"alice\"].1: appended 4",
+            "[\"bob\"]: inserted (3, [5])",
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn format_value__nested_values() -> DeltaResult<()> {
+        use crate::IntoDelta;
+        let mut map: BTreeMap<u8, Option<(bool, String)>> = BTreeMap::new();
+        map.insert(1, Some((true, "one".into())));
+        map.insert(2, None);
+        let value: DynDelta = map.into_delta()?.to_dyn_delta();
+        assert_eq!(format_value(&value), "{1: (true, \"one\"), 2: None}");
+        Ok(())
+    }
+
+    #[test]
+    fn Option__walk() -> DeltaResult<()> {
+        let opt0: Option<(u8, bool)> = Some((1, true));
+        let opt1: Option<(u8, bool)> = Some((1, false));
+        assert_eq!(changes(&opt0.delta(&opt1)?), vec!["1: changed to false"]);
+        assert_eq!(changes(&opt0.delta(&None)?), vec![": set to variant None"]);
+        Ok(())
+    }
+}